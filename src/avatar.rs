@@ -0,0 +1,79 @@
+//! `slack avatar`: set your profile photo from a local image (users.setPhoto).
+
+use crate::files::human_size;
+use crate::output::outln;
+use crate::{ensure_token, http, slack_post_multipart, AvatarArgs, SlackError};
+use anyhow::{anyhow, Context, Result};
+use reqwest::blocking::multipart::{Form, Part};
+
+pub fn run(args: AvatarArgs) -> Result<()> {
+    let path = &args.file;
+    let bytes =
+        std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let mime = image_type(&bytes)
+        .ok_or_else(|| anyhow!("{} is not a PNG, JPEG, GIF or BMP image", path.display()))?;
+    let size = bytes.len() as u64;
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("avatar")
+        .to_string();
+    let mut form = Form::new().part("image", Part::bytes(bytes).file_name(name).mime_str(mime)?);
+    for (key, value) in [
+        ("crop_x", args.crop_x),
+        ("crop_y", args.crop_y),
+        ("crop_w", args.crop_w),
+    ] {
+        if let Some(v) = value {
+            form = form.text(key, v.to_string());
+        }
+    }
+    let token = ensure_token()?;
+    slack_post_multipart(&http(), "users.setPhoto", &token, form).map_err(|e| {
+        match SlackError::code_of(&e) {
+            Some("too_large") => e.context(format!(
+                "{} is {}, more than Slack accepts for a profile photo",
+                path.display(),
+                human_size(size)
+            )),
+            Some("too_many_frames") => e.context("animated images can't be profile photos"),
+            Some("bad_image" | "invalid_image") => e.context(format!(
+                "Slack couldn't read {} as an image",
+                path.display()
+            )),
+            _ => e,
+        }
+    })?;
+    outln!(
+        "profile photo set from {} ({})",
+        path.display(),
+        human_size(size)
+    );
+    Ok(())
+}
+
+/// The MIME type of an image users.setPhoto takes, by its magic bytes; the file name
+/// may say anything.
+fn image_type(bytes: &[u8]) -> Option<&'static str> {
+    match bytes {
+        [0x89, b'P', b'N', b'G', ..] => Some("image/png"),
+        [0xFF, 0xD8, 0xFF, ..] => Some("image/jpeg"),
+        [b'G', b'I', b'F', b'8', ..] => Some("image/gif"),
+        [b'B', b'M', ..] => Some("image/bmp"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn image_type_goes_by_content() {
+        assert_eq!(image_type(b"\x89PNG\r\n\x1a\n...."), Some("image/png"));
+        assert_eq!(image_type(&[0xFF, 0xD8, 0xFF, 0xE0]), Some("image/jpeg"));
+        assert_eq!(image_type(b"GIF89a"), Some("image/gif"));
+        assert_eq!(image_type(b"<svg xmlns="), None);
+        assert_eq!(image_type(b""), None);
+    }
+}
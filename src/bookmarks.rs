@@ -0,0 +1,92 @@
+//! `slack bookmarks`, `bookmark-add` and `bookmark-remove`: links in a channel's header.
+
+use crate::output::outln;
+use crate::{
+    ensure_token, http, resolve_channel, slack_post, BookmarkAddArgs, BookmarkRemoveArgs,
+    BookmarksArgs, SlackError,
+};
+use anyhow::Result;
+use serde_json::Value;
+
+pub fn list(args: BookmarksArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let resp = slack_post(
+        &client,
+        "bookmarks.list",
+        &token,
+        Some(&[("channel_id", channel.as_str())]),
+    )
+    .map_err(|e| explain(e, &channel))?;
+    let bookmarks = resp
+        .get("bookmarks")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    if bookmarks.is_empty() {
+        outln!("no bookmarks");
+    }
+    for b in &bookmarks {
+        outln!("{}", bookmark_line(b));
+    }
+    Ok(())
+}
+
+pub fn add(args: BookmarkAddArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let mut form = vec![
+        ("channel_id", channel.as_str()),
+        ("title", args.title.as_str()),
+        ("type", "link"),
+        ("link", args.link.as_str()),
+    ];
+    if let Some(emoji) = args.emoji.as_deref() {
+        form.push(("emoji", emoji));
+    }
+    let resp = slack_post(&client, "bookmarks.add", &token, Some(&form))
+        .map_err(|e| explain(e, &channel))?;
+    let id = resp
+        .pointer("/bookmark/id")
+        .and_then(|v| v.as_str())
+        .unwrap_or("-");
+    outln!("added bookmark {id}");
+    Ok(())
+}
+
+pub fn remove(args: BookmarkRemoveArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let form = [
+        ("channel_id", channel.as_str()),
+        ("bookmark_id", args.bookmark.as_str()),
+    ];
+    slack_post(&client, "bookmarks.remove", &token, Some(&form))
+        .map_err(|e| explain(e, &channel))?;
+    outln!("removed bookmark {}", args.bookmark);
+    Ok(())
+}
+
+/// `id  title  link  emoji` for one bookmark.
+fn bookmark_line(b: &Value) -> String {
+    let str_at = |k: &str| b.get(k).and_then(|v| v.as_str()).unwrap_or("");
+    format!(
+        "{}\t{}\t{}\t{}",
+        str_at("id"),
+        str_at("title"),
+        str_at("link"),
+        str_at("emoji")
+    )
+}
+
+fn explain(e: anyhow::Error, channel: &str) -> anyhow::Error {
+    match SlackError::code_of(&e) {
+        Some("not_in_channel") => e.context(format!(
+            "not a member of {channel}; run `slack join --channel {channel}` first"
+        )),
+        _ => e,
+    }
+}
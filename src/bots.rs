@@ -0,0 +1,72 @@
+//! `slack bot-info`: which integration a `B…` bot ID belongs to (bots.info).
+
+use crate::output::{errln, outln};
+use crate::{ensure_token, http, slack_post, BotInfoArgs, SlackError};
+use anyhow::{anyhow, Result};
+use reqwest::blocking::Client;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Bot {
+    pub name: String,
+    pub app_id: Option<String>,
+    pub deleted: bool,
+}
+
+pub fn run(args: BotInfoArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let mut failed = 0;
+    for id in &args.bots {
+        match bot_info(&client, &token, id) {
+            Ok(bot) => outln!(
+                "{id}\t{}\t{}\t{}",
+                bot.name,
+                bot.app_id.as_deref().unwrap_or("-"),
+                if bot.deleted { "deleted" } else { "active" }
+            ),
+            Err(e) => {
+                failed += 1;
+                errln!("{id}: {e:#}");
+            }
+        }
+    }
+    if failed > 0 {
+        return Err(anyhow!(
+            "{failed} of {} bots could not be looked up",
+            args.bots.len()
+        ));
+    }
+    Ok(())
+}
+
+/// bots.info for one bot ID, remembered for the rest of the process so a listing that
+/// names the same bot many times asks once.
+pub(crate) fn bot_info(client: &Client, token: &str, id: &str) -> Result<Bot> {
+    static CACHE: OnceLock<Mutex<HashMap<(String, String), Bot>>> = OnceLock::new();
+    let cache = CACHE.get_or_init(Default::default);
+    let key = (token.to_string(), id.to_string());
+    if let Some(bot) = cache.lock().unwrap().get(&key) {
+        return Ok(bot.clone());
+    }
+    let resp = slack_post(client, "bots.info", token, Some(&[("bot", id)])).map_err(|e| {
+        match SlackError::code_of(&e) {
+            Some("bot_not_found") => e.context(format!("no bot with ID {id}")),
+            _ => e,
+        }
+    })?;
+    let str_at = |ptr: &str| {
+        resp.pointer(ptr)
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    let bot = Bot {
+        name: str_at("/bot/name").unwrap_or_else(|| id.to_string()),
+        app_id: str_at("/bot/app_id"),
+        deleted: resp.pointer("/bot/deleted").and_then(|v| v.as_bool()) == Some(true),
+    };
+    cache.lock().unwrap().insert(key, bot.clone());
+    Ok(bot)
+}
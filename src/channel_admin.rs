@@ -0,0 +1,344 @@
+//! `slack create`, `archive`, `unarchive`, `rename`, `topic` and `purpose`: making,
+//! retiring and describing channels.
+
+use crate::output::{self, errln, outln};
+use crate::{
+    ensure_token, http, resolve_channel, slack_post, ArchiveArgs, ChannelAboutArgs, CreateArgs,
+    RenameArgs, SlackError, UnarchiveArgs,
+};
+use anyhow::{anyhow, Context, Result};
+use reqwest::blocking::Client;
+use serde_json::Value;
+
+pub fn create(args: CreateArgs) -> Result<()> {
+    let name = normalize_name(&args.name);
+    check_name(&name)?;
+    if name != args.name {
+        errln!("note: Slack will name it #{name}");
+    }
+    let token = ensure_token()?;
+    let client = http();
+    let private = if args.private { "true" } else { "false" };
+    let resp = slack_post(
+        &client,
+        "conversations.create",
+        &token,
+        Some(&[("name", name.as_str()), ("is_private", private)]),
+    )
+    .map_err(|e| explain(e, &format!("#{name}")))?;
+    let id = resp
+        .pointer("/channel/id")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow!("conversations.create did not return a channel"))?
+        .to_string();
+    outln!("created channel: {id} #{name}");
+    // The channel exists from here on, so later failures say so rather than
+    // leaving a rerun to hit name_taken.
+    let made = |e: anyhow::Error, what: &str| {
+        e.context(format!("#{name} ({id}) was created, but {what} failed"))
+    };
+    if let Some(topic) = args.topic.as_deref() {
+        set_about(&client, &token, &id, About::Topic, topic)
+            .map_err(|e| made(e, "setting its topic"))?;
+    }
+    let users: Vec<&str> = args
+        .invite
+        .iter()
+        .map(|u| u.trim())
+        .filter(|u| !u.is_empty())
+        .collect();
+    if !users.is_empty() {
+        let list = users.join(",");
+        slack_post(
+            &client,
+            "conversations.invite",
+            &token,
+            Some(&[("channel", id.as_str()), ("users", list.as_str())]),
+        )
+        .map_err(|e| made(e, "inviting --invite users"))?;
+        outln!("invited {} user(s)", users.len());
+    }
+    Ok(())
+}
+
+pub fn archive(args: ArchiveArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let mut label = channel.clone();
+    if !args.yes {
+        let info = channel_info(&client, &token, &channel).map_err(|e| explain(e, &channel))?;
+        label = channel_label(&info, &channel);
+        if output::is_capturing() {
+            return Err(anyhow!("no terminal to confirm on; pass --yes"));
+        }
+        let go = dialoguer::Confirm::new()
+            .with_prompt(format!("Archive {label}? Members can no longer post in it"))
+            .default(false)
+            .interact()
+            .context("failed to read confirmation")?;
+        if !go {
+            return Err(anyhow!("cancelled; nothing archived"));
+        }
+    }
+    slack_post(
+        &client,
+        "conversations.archive",
+        &token,
+        Some(&[("channel", channel.as_str())]),
+    )
+    .map_err(|e| explain(e, &label))?;
+    outln!("archived {label}");
+    Ok(())
+}
+
+pub fn unarchive(args: UnarchiveArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    slack_post(
+        &client,
+        "conversations.unarchive",
+        &token,
+        Some(&[("channel", channel.as_str())]),
+    )
+    .map_err(|e| explain(e, &channel))?;
+    outln!("unarchived {channel}");
+    Ok(())
+}
+
+pub fn rename(args: RenameArgs) -> Result<()> {
+    let name = normalize_name(&args.name);
+    check_name(&name)?;
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let info = channel_info(&client, &token, &channel).map_err(|e| explain(e, &channel))?;
+    let old = info.get("name").and_then(|v| v.as_str()).unwrap_or("");
+    if old == name {
+        outln!("#{name} ({channel}) already has that name");
+        return Ok(());
+    }
+    let resp = slack_post(
+        &client,
+        "conversations.rename",
+        &token,
+        Some(&[("channel", channel.as_str()), ("name", name.as_str())]),
+    )
+    .map_err(|e| explain(e, &format!("#{name}")))?;
+    // Slack may adjust the name further; report what it kept.
+    let new = resp
+        .pointer("/channel/name")
+        .and_then(|v| v.as_str())
+        .unwrap_or(&name);
+    outln!("renamed #{old} to #{new} ({channel})");
+    Ok(())
+}
+
+/// Longest purpose Slack stores; past it conversations.setPurpose fails outright.
+const MAX_PURPOSE_CHARS: usize = 250;
+
+/// The two free-text fields under a channel's name, which `topic` and `purpose` read
+/// and write the same way.
+#[derive(Clone, Copy)]
+enum About {
+    Topic,
+    Purpose,
+}
+
+impl About {
+    /// The field's key, in both conversations.info and the set call's form.
+    fn key(self) -> &'static str {
+        match self {
+            About::Topic => "topic",
+            About::Purpose => "purpose",
+        }
+    }
+
+    fn method(self) -> &'static str {
+        match self {
+            About::Topic => "conversations.setTopic",
+            About::Purpose => "conversations.setPurpose",
+        }
+    }
+}
+
+pub fn topic(args: ChannelAboutArgs) -> Result<()> {
+    show_or_set(args, About::Topic)
+}
+
+pub fn purpose(args: ChannelAboutArgs) -> Result<()> {
+    if let Some(text) = args.text.as_deref() {
+        let len = text.chars().count();
+        if len > MAX_PURPOSE_CHARS {
+            return Err(anyhow!(
+                "--text is {len} characters; a purpose can be at most {MAX_PURPOSE_CHARS}"
+            ));
+        }
+    }
+    show_or_set(args, About::Purpose)
+}
+
+/// Print the channel's topic or purpose, or set it when there's `--text`.
+fn show_or_set(args: ChannelAboutArgs, about: About) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let Some(text) = args.text.as_deref() else {
+        let info = channel_info(&client, &token, &channel).map_err(|e| explain(e, &channel))?;
+        let current = info
+            .get(about.key())
+            .and_then(|v| v.get("value"))
+            .and_then(|v| v.as_str());
+        match current {
+            Some(value) if !value.is_empty() => outln!("{value}"),
+            _ => outln!("no {}", about.key()),
+        }
+        return Ok(());
+    };
+    set_about(&client, &token, &channel, about, text).map_err(|e| explain(e, &channel))
+}
+
+/// conversations.setTopic/setPurpose, then print what Slack stored, warning when it
+/// cut `text` short.
+fn set_about(client: &Client, token: &str, channel: &str, about: About, text: &str) -> Result<()> {
+    let key = about.key();
+    let resp = slack_post(
+        client,
+        about.method(),
+        token,
+        Some(&[("channel", channel), (key, text)]),
+    )?;
+    // Older responses carry the value at the top level rather than in `channel`.
+    let stored = resp
+        .get("channel")
+        .and_then(|c| c.get(key))
+        .and_then(|v| v.get("value"))
+        .or_else(|| resp.get(key))
+        .and_then(|v| v.as_str())
+        .unwrap_or(text);
+    outln!("{key} set: {stored}");
+    let (kept, given) = (stored.chars().count(), text.chars().count());
+    if kept < given {
+        errln!("warning: Slack kept only the first {kept} of {given} characters");
+    }
+    Ok(())
+}
+
+/// conversations.info's `channel` object.
+fn channel_info(client: &Client, token: &str, channel: &str) -> Result<Value> {
+    let resp = slack_post(
+        client,
+        "conversations.info",
+        token,
+        Some(&[("channel", channel)]),
+    )?;
+    Ok(resp.get("channel").cloned().unwrap_or_default())
+}
+
+/// `#name (C123)`, or just the ID for a conversation without a name.
+fn channel_label(info: &Value, channel: &str) -> String {
+    match info.get("name").and_then(|v| v.as_str()) {
+        Some(name) => format!("#{name} ({channel})"),
+        None => channel.to_string(),
+    }
+}
+
+/// Longest channel name Slack accepts.
+const MAX_NAME_CHARS: usize = 80;
+
+/// Slack's rules for a normalized channel name: 1 to 80 characters, all letters,
+/// digits, hyphens or underscores.
+fn check_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        return Err(anyhow!("--name is empty"));
+    }
+    let len = name.chars().count();
+    if len > MAX_NAME_CHARS {
+        return Err(anyhow!(
+            "#{name} is {len} characters; channel names can be at most {MAX_NAME_CHARS}"
+        ));
+    }
+    // Lowercasing is normalize_name's job, so only the character set is left to check.
+    if let Some(bad) = name
+        .chars()
+        .find(|c| !(c.is_alphanumeric() || matches!(c, '-' | '_')))
+    {
+        return Err(anyhow!(
+            "`{bad}` can't be in a channel name; use lowercase letters, numbers, hyphens and underscores"
+        ));
+    }
+    Ok(())
+}
+
+/// A channel name as Slack stores it: no leading `#`, lowercase, and runs of spaces
+/// turned into single dashes.
+pub(crate) fn normalize_name(name: &str) -> String {
+    name.trim()
+        .trim_start_matches('#')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase()
+}
+
+/// Readable reasons for the errors people hit; `name` is the channel as shown to them.
+fn explain(e: anyhow::Error, name: &str) -> anyhow::Error {
+    match SlackError::code_of(&e) {
+        Some("name_taken") => e.context(format!(
+            "{name} already exists (maybe archived); find it with `slack channels --all | grep -w {}`",
+            name.trim_start_matches('#')
+        )),
+        Some("invalid_name_specials") | Some("invalid_name_punctuation") => e.context(
+            "channel names may only use lowercase letters, numbers, hyphens and underscores",
+        ),
+        Some("invalid_name_maxlength") => {
+            e.context("channel names can be at most 80 characters")
+        }
+        Some("restricted_action") => {
+            e.context("your workspace's settings don't allow you to do that; ask an admin")
+        }
+        Some("already_archived") => e.context(format!("{name} is already archived")),
+        Some("not_archived") => e.context(format!("{name} isn't archived")),
+        Some("not_authorized") => e.context(
+            "only the channel's creator and workspace admins can rename it",
+        ),
+        Some("cant_archive_general") => e.context("the workspace's #general can't be archived"),
+        Some("not_in_channel") => e.context(format!(
+            "you're not in {name}; join it first (`slack join --channel {name}`)"
+        )),
+        Some("channel_not_found") => e.context(format!(
+            "no channel {name} visible to you; archived ones need their ID, not #name"
+        )),
+        _ => e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_show_name_when_there_is_one() {
+        let info = serde_json::json!({ "id": "C1", "name": "ops" });
+        assert_eq!(channel_label(&info, "C1"), "#ops (C1)");
+        assert_eq!(channel_label(&Value::Null, "D1"), "D1");
+    }
+
+    #[test]
+    fn names_are_checked_before_calling_slack() {
+        assert!(check_name("incident-2025_07").is_ok());
+        assert!(check_name("プロジェクト").is_ok());
+        assert!(check_name("").is_err());
+        assert!(check_name("ops!").is_err());
+        assert!(check_name(&"a".repeat(81)).is_err());
+        assert!(check_name(&"a".repeat(80)).is_ok());
+    }
+
+    #[test]
+    fn names_are_normalized_like_slack() {
+        assert_eq!(normalize_name("Incident 2025-07"), "incident-2025-07");
+        assert_eq!(normalize_name("#ops"), "ops");
+        assert_eq!(normalize_name("  Big   Launch "), "big-launch");
+    }
+}
@@ -0,0 +1,146 @@
+//! `config.toml` in the config dir: optional, user-edited settings.
+//!
+//! ```toml
+//! team_id = "T0123ABCD"   # Enterprise Grid: workspace to target by default
+//! key_file = "token.key"  # opens the encrypted token file instead of a passphrase
+//!
+//! [workflows]
+//! deploy = "https://hooks.slack.com/triggers/T0/1/abc"
+//! intake = { url = "https://hooks.slack.com/triggers/T0/2/def", variables = ["title", "severity"] }
+//!
+//! [status.lunch]
+//! emoji = ":sandwich:"
+//! text = "Lunch"
+//! duration = "1h"
+//! ```
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Default workspace for org-wide (Enterprise Grid) tokens; `--team-id` overrides it.
+    pub team_id: Option<String>,
+    /// Key for the encrypted token file; relative paths are from the config dir.
+    pub key_file: Option<PathBuf>,
+    /// Workflow Builder webhook triggers by name.
+    pub workflows: BTreeMap<String, Workflow>,
+    /// `slack status NAME` presets by name.
+    pub status: BTreeMap<String, StatusPreset>,
+}
+
+/// A named status; `duration` (e.g. 45m, 2h, 1d) sets when it clears, counted from when
+/// it is applied.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct StatusPreset {
+    pub emoji: String,
+    pub text: String,
+    pub duration: Option<String>,
+}
+
+/// A workflow is either just its URL or a table with the variables it expects.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum Workflow {
+    Url(String),
+    Full {
+        url: String,
+        #[serde(default)]
+        variables: Vec<String>,
+    },
+}
+
+impl Workflow {
+    pub fn url(&self) -> &str {
+        match self {
+            Workflow::Url(url) | Workflow::Full { url, .. } => url,
+        }
+    }
+
+    pub fn variables(&self) -> &[String] {
+        match self {
+            Workflow::Url(_) => &[],
+            Workflow::Full { variables, .. } => variables,
+        }
+    }
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    crate::config_dir().map(|d| d.join("config.toml"))
+}
+
+/// Load `config.toml`; a missing file is an empty config, a malformed one is an error.
+pub fn load() -> Result<Config> {
+    let Some(path) = config_path() else {
+        return Ok(Config::default());
+    };
+    let text = match std::fs::read_to_string(&path) {
+        Ok(t) => t,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Config::default()),
+        Err(e) => return Err(e).with_context(|| format!("failed to read {}", path.display())),
+    };
+    parse(&text).with_context(|| format!("invalid config in {}", path.display()))
+}
+
+fn parse(text: &str) -> Result<Config> {
+    Ok(toml::from_str(text)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workflows_accept_a_url_or_a_table() {
+        let cfg = parse(
+            r#"
+            [workflows]
+            deploy = "https://hooks.test/deploy"
+            intake = { url = "https://hooks.test/intake", variables = ["title", "severity"] }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(cfg.workflows["deploy"].url(), "https://hooks.test/deploy");
+        assert!(cfg.workflows["deploy"].variables().is_empty());
+        assert_eq!(cfg.workflows["intake"].variables(), ["title", "severity"]);
+        assert!(parse("").unwrap().workflows.is_empty());
+        assert!(parse("[workflows]\nbad = 3").is_err());
+    }
+
+    #[test]
+    fn status_presets_are_tables_by_name() {
+        let cfg = parse(
+            r#"
+            [status.lunch]
+            emoji = ":sandwich:"
+            text = "Lunch"
+            duration = "1h"
+
+            [status.focus]
+            text = "Heads down"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            cfg.status["lunch"],
+            StatusPreset {
+                emoji: ":sandwich:".into(),
+                text: "Lunch".into(),
+                duration: Some("1h".into()),
+            }
+        );
+        assert_eq!(cfg.status["focus"].duration, None);
+        assert!(parse("[status.x]\nduration = 5").is_err());
+    }
+
+    #[test]
+    fn team_id_is_a_top_level_key() {
+        let cfg = parse("team_id = \"T1\"\n[workflows]\n").unwrap();
+        assert_eq!(cfg.team_id.as_deref(), Some("T1"));
+        assert_eq!(parse("").unwrap().team_id, None);
+    }
+}
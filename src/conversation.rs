@@ -0,0 +1,164 @@
+//! `slack info`: one conversation at a glance (conversations.info).
+
+use crate::export_html::day_and_time;
+use crate::membership::user_label;
+use crate::output::outln;
+use crate::{
+    conversation_type, ensure_token, http, resolve_channel, slack_post, InfoArgs, ReportFormat,
+    SlackError,
+};
+use anyhow::Result;
+use reqwest::blocking::Client;
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Debug, PartialEq, Serialize)]
+struct Conversation {
+    id: String,
+    name: Option<String>,
+    /// public, private, im or mpim.
+    #[serde(rename = "type")]
+    kind: &'static str,
+    /// The other person in an IM.
+    with: Option<String>,
+    topic: Option<String>,
+    purpose: Option<String>,
+    members: Option<u64>,
+    creator: Option<String>,
+    /// `YYYY-MM-DD HH:MM` UTC.
+    created: Option<String>,
+    archived: bool,
+    /// Shared with another organization (Slack Connect).
+    shared_externally: bool,
+}
+
+pub fn run(args: InfoArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let resp = slack_post(
+        &client,
+        "conversations.info",
+        &token,
+        Some(&[
+            ("channel", channel.as_str()),
+            ("include_num_members", "true"),
+        ]),
+    )
+    .map_err(|e| explain(e, &channel))?;
+    let mut convo = parse(resp.get("channel").unwrap_or(&Value::Null), &channel);
+    resolve_names(&client, &token, &mut convo);
+    match args.format {
+        ReportFormat::Json => outln!("{}", serde_json::to_string_pretty(&convo)?),
+        ReportFormat::Text => {
+            let yes_no = |b: bool| Some(if b { "yes" } else { "no" }.to_string());
+            let mut shown = vec![
+                ("id", Some(convo.id.clone())),
+                ("name", convo.name.clone().map(|n| format!("#{n}"))),
+                ("type", Some(convo.kind.to_string())),
+            ];
+            if convo.kind == "im" {
+                shown.push(("with", convo.with.clone()));
+            }
+            shown.extend([
+                ("topic", convo.topic.clone()),
+                ("purpose", convo.purpose.clone()),
+                ("members", convo.members.map(|n| n.to_string())),
+                ("creator", convo.creator.clone()),
+                ("created", convo.created.clone().map(|c| format!("{c} UTC"))),
+                ("archived", yes_no(convo.archived)),
+                ("shared externally", yes_no(convo.shared_externally)),
+            ]);
+            for (key, value) in shown {
+                outln!("{key}: {}", value.as_deref().unwrap_or("-"));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// conversations.info's `channel`, with users still as IDs.
+fn parse(ch: &Value, id: &str) -> Conversation {
+    let str_at = |ptr: &str| {
+        ch.pointer(ptr)
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    let flag = |k: &str| ch.get(k).and_then(|v| v.as_bool()) == Some(true);
+    let kind = match conversation_type(ch) {
+        "public_channel" => "public",
+        "private_channel" => "private",
+        other => other,
+    };
+    Conversation {
+        id: str_at("/id").unwrap_or_else(|| id.to_string()),
+        name: if kind == "im" { None } else { str_at("/name") },
+        kind,
+        with: str_at("/user"),
+        topic: str_at("/topic/value"),
+        purpose: str_at("/purpose/value"),
+        members: ch.get("num_members").and_then(|v| v.as_u64()),
+        creator: str_at("/creator"),
+        created: ch
+            .get("created")
+            .and_then(|v| v.as_i64())
+            .filter(|t| *t > 0)
+            .map(|t| {
+                let (day, time) = day_and_time(t as f64);
+                format!("{day} {time}")
+            }),
+        archived: flag("is_archived"),
+        shared_externally: flag("is_ext_shared"),
+    }
+}
+
+/// Swap the creator's and IM counterpart's IDs for `@name (U123)`.
+fn resolve_names(client: &Client, token: &str, convo: &mut Conversation) {
+    for user in [&mut convo.creator, &mut convo.with].into_iter().flatten() {
+        *user = user_label(client, token, user);
+    }
+}
+
+fn explain(e: anyhow::Error, channel: &str) -> anyhow::Error {
+    match SlackError::code_of(&e) {
+        Some("channel_not_found") => e.context(format!(
+            "no conversation {channel} visible to you; private ones need you to be a member"
+        )),
+        _ => e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_reads_channels_and_ims() {
+        let ch = json!({
+            "id": "C1",
+            "name": "ops",
+            "is_private": true,
+            "topic": { "value": "Pager rotation" },
+            "purpose": { "value": "" },
+            "num_members": 12,
+            "creator": "U1",
+            "created": 1_712_345_678,
+            "is_archived": false,
+            "is_ext_shared": true
+        });
+        let c = parse(&ch, "C1");
+        assert_eq!(c.kind, "private");
+        assert_eq!(c.name.as_deref(), Some("ops"));
+        assert_eq!(c.purpose, None);
+        assert_eq!(c.members, Some(12));
+        assert_eq!(c.created.as_deref(), Some("2024-04-05 19:34"));
+        assert!(c.shared_externally);
+
+        let im = parse(&json!({ "id": "D1", "is_im": true, "user": "U2" }), "D1");
+        assert_eq!(im.kind, "im");
+        assert_eq!(im.with.as_deref(), Some("U2"));
+        assert_eq!(im.name, None);
+    }
+}
@@ -0,0 +1,259 @@
+//! `slack daemon`: serve CLI invocations over a Unix socket so tight scripting loops
+//! reuse one process, one HTTP connection pool and the warm validation cache.
+//!
+//! Protocol: one JSON object per line. Requests are
+//! `{"argv": ["send", ...], "cwd": "/home/me", "env": {"SLACK_PROFILE": "work"}}` (argv
+//! without the program name; the client's directory and `FORWARDED_ENV`) or
+//! `{"shutdown": true}`; replies are `{"stdout": "...", "stderr": "...", "code": 0}`.
+
+use crate::output::errln;
+use anyhow::Result;
+use clap::CommandFactory;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct Request {
+    #[serde(default)]
+    argv: Vec<String>,
+    /// The client's working directory, which relative paths (`--attach`, `--out`, …)
+    /// are resolved against.
+    #[serde(default)]
+    cwd: Option<PathBuf>,
+    /// The client's values of `FORWARDED_ENV`; a variable missing here is unset for
+    /// the request even if the daemon has it.
+    #[serde(default)]
+    env: BTreeMap<String, String>,
+    #[serde(default)]
+    shutdown: bool,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Reply {
+    pub stdout: String,
+    pub stderr: String,
+    pub code: i32,
+}
+
+/// Commands that make no sense inside the daemon (long-running or nested).
+const NOT_VIA_DAEMON: &[&str] = &["daemon", "watch", "init", "unlock", "lock", "mcp"];
+
+/// Environment that changes what a command does, taken from the client rather than
+/// from whatever the daemon was started with. The token deliberately isn't here: the
+/// daemon holding it is the point.
+const FORWARDED_ENV: &[&str] = &["SLACK_PROFILE", "SLACK_TEAM_ID"];
+
+fn socket_path() -> Option<PathBuf> {
+    crate::state_dir().map(|d| d.join("daemon.sock"))
+}
+
+/// Run `f` in the client's directory and environment, then put the daemon's back.
+/// Requests are served one at a time, so nothing else observes the switch.
+fn in_client_context<T>(req: &Request, f: impl FnOnce() -> T) -> Result<T> {
+    use anyhow::Context;
+
+    let saved_dir = std::env::current_dir().ok();
+    let saved_env: Vec<(&str, Option<std::ffi::OsString>)> = FORWARDED_ENV
+        .iter()
+        .map(|k| (*k, std::env::var_os(k)))
+        .collect();
+    if let Some(dir) = &req.cwd {
+        std::env::set_current_dir(dir)
+            .with_context(|| format!("daemon cannot enter {}", dir.display()))?;
+    }
+    for key in FORWARDED_ENV {
+        match req.env.get(*key) {
+            Some(value) => std::env::set_var(key, value),
+            None => std::env::remove_var(key),
+        }
+    }
+    let result = f();
+    for (key, value) in saved_env {
+        match value {
+            Some(value) => std::env::set_var(key, value),
+            None => std::env::remove_var(key),
+        }
+    }
+    if let Some(dir) = saved_dir {
+        std::env::set_current_dir(dir).ok();
+    }
+    Ok(result)
+}
+
+/// Execute one request in-process with output captured.
+fn handle(req: Request) -> Reply {
+    match in_client_context(&req, || run(req.argv.clone())) {
+        Ok(reply) => reply,
+        Err(e) => Reply {
+            stderr: format!("Error: {e:#}\n"),
+            code: 1,
+            ..Default::default()
+        },
+    }
+}
+
+fn run(argv: Vec<String>) -> Reply {
+    let sub = argv.iter().find(|a| !a.starts_with('-'));
+    if let Some(sub) = sub.filter(|s| NOT_VIA_DAEMON.contains(&s.as_str())) {
+        return Reply {
+            stderr: format!("`{sub}` cannot run through the daemon\n"),
+            code: 2,
+            ..Default::default()
+        };
+    }
+    let args = std::iter::once("slack".to_string()).chain(argv);
+    let ((result, code), stdout, stderr) =
+        crate::output::capture(|| match crate::Cli::command().try_get_matches_from(args) {
+            Ok(matches) => match crate::execute(&matches) {
+                Ok(()) => (String::new(), 0),
+                Err(e) => (format!("Error: {e:?}\n"), 1),
+            },
+            Err(e) => {
+                let code = e.exit_code();
+                let rendered = e.render().to_string();
+                if code == 0 {
+                    crate::output::write_out(&rendered);
+                    (String::new(), 0)
+                } else {
+                    (rendered, code)
+                }
+            }
+        });
+    Reply {
+        stdout,
+        stderr: stderr + &result,
+        code,
+    }
+}
+
+#[cfg(unix)]
+pub fn serve() -> Result<()> {
+    use anyhow::{anyhow, Context};
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    let path = socket_path().ok_or_else(|| anyhow!("no state directory for the daemon socket"))?;
+    let dir = path.parent().expect("socket path has a parent");
+    std::fs::create_dir_all(dir).context("failed to create state dir")?;
+    std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))
+        .context("failed to restrict state dir permissions")?;
+    if path.exists() {
+        if UnixStream::connect(&path).is_ok() {
+            return Err(anyhow!(
+                "a daemon is already listening on {}",
+                path.display()
+            ));
+        }
+        // Left over from a daemon that didn't shut down cleanly.
+        std::fs::remove_file(&path).ok();
+    }
+    // Bind under a temporary name and rename once restricted, so clients never see the
+    // socket with default permissions.
+    let tmp = path.with_extension(format!("sock.tmp-{}", std::process::id()));
+    std::fs::remove_file(&tmp).ok();
+    let listener =
+        UnixListener::bind(&tmp).with_context(|| format!("failed to bind {}", tmp.display()))?;
+    std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o600))
+        .context("failed to restrict socket permissions")?;
+    std::fs::rename(&tmp, &path)
+        .with_context(|| format!("failed to move socket to {}", path.display()))?;
+    errln!("daemon listening on {}", path.display());
+
+    for conn in listener.incoming() {
+        let stream = match conn {
+            Ok(s) => s,
+            Err(e) => {
+                errln!("daemon: accept failed: {e}");
+                continue;
+            }
+        };
+        let mut writer = stream.try_clone().context("failed to clone socket")?;
+        let mut stop = false;
+        for line in BufReader::new(stream).lines() {
+            let Ok(line) = line else { break };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let reply = match serde_json::from_str::<Request>(&line) {
+                Ok(req) if req.shutdown => {
+                    stop = true;
+                    Reply::default()
+                }
+                Ok(req) => handle(req),
+                Err(e) => Reply {
+                    stderr: format!("bad request: {e}\n"),
+                    code: 2,
+                    ..Default::default()
+                },
+            };
+            let mut body = serde_json::to_string(&reply)?;
+            body.push('\n');
+            if writer.write_all(body.as_bytes()).is_err() || stop {
+                break;
+            }
+        }
+        if stop {
+            break;
+        }
+    }
+    std::fs::remove_file(&path).ok();
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn serve() -> Result<()> {
+    Err(anyhow::anyhow!(
+        "`slack daemon` currently needs Unix domain sockets and is not available on this platform"
+    ))
+}
+
+/// Send `argv` to a running daemon. `None` means no daemon answered and the caller
+/// should run the command in-process.
+#[cfg(unix)]
+pub fn forward(argv: &[String]) -> Option<Reply> {
+    send(&Request {
+        argv: argv.to_vec(),
+        cwd: std::env::current_dir().ok(),
+        env: FORWARDED_ENV
+            .iter()
+            .filter_map(|k| Some((k.to_string(), std::env::var(k).ok()?)))
+            .collect(),
+        shutdown: false,
+    })
+}
+
+#[cfg(not(unix))]
+pub fn forward(_argv: &[String]) -> Option<Reply> {
+    None
+}
+
+/// Ask a running daemon to exit. Returns false if none was listening.
+#[cfg(unix)]
+pub fn stop() -> bool {
+    send(&Request {
+        shutdown: true,
+        ..Default::default()
+    })
+    .is_some()
+}
+
+#[cfg(not(unix))]
+pub fn stop() -> bool {
+    false
+}
+
+#[cfg(unix)]
+fn send(req: &Request) -> Option<Reply> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+
+    let mut stream = UnixStream::connect(socket_path()?).ok()?;
+    let mut body = serde_json::to_string(req).ok()?;
+    body.push('\n');
+    stream.write_all(body.as_bytes()).ok()?;
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line).ok()?;
+    serde_json::from_str(&line).ok()
+}
@@ -0,0 +1,227 @@
+//! `slack dnd`: your Do Not Disturb state, snoozing notifications and ending it.
+
+use crate::export_html::day_and_time;
+use crate::output::{out, outln};
+use crate::reminders::{local_time, tz_offset};
+use crate::{
+    ensure_token, http, resolve_users, slack_post, table, unix_now, DndArgs, DndCommand,
+    DndTeamArgs, SlackError,
+};
+use anyhow::Result;
+use serde_json::Value;
+
+pub fn run(args: DndArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    match args.command {
+        None => {
+            let resp = slack_post(&client, "dnd.info", &token, None)?;
+            let offset = tz_offset(&client, &token);
+            outln!("{}", describe(&resp, unix_now() as i64, offset));
+        }
+        Some(DndCommand::Snooze(args)) => {
+            let minutes = args.minutes.to_string();
+            let resp = slack_post(
+                &client,
+                "dnd.setSnooze",
+                &token,
+                Some(&[("num_minutes", minutes.as_str())]),
+            )?;
+            match int_at(&resp, "snooze_endtime") {
+                0 => outln!("snoozed for {} minutes", args.minutes),
+                end => outln!(
+                    "snoozed until {}",
+                    local_time(end, tz_offset(&client, &token))
+                ),
+            }
+        }
+        Some(DndCommand::End(args)) if args.dnd => {
+            slack_post(&client, "dnd.endDnd", &token, None)?;
+            outln!("do not disturb ended");
+        }
+        Some(DndCommand::End(_)) => match slack_post(&client, "dnd.endSnooze", &token, None) {
+            Ok(_) => outln!("snooze ended"),
+            Err(e) => match SlackError::code_of(&e) {
+                Some("snooze_not_active") => outln!("no snooze was active"),
+                _ => return Err(e),
+            },
+        },
+    }
+    Ok(())
+}
+
+pub fn team(args: DndTeamArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let ids = resolve_users(&client, &token, &args.users)?;
+    let joined = ids.join(",");
+    let resp = slack_post(
+        &client,
+        "dnd.teamInfo",
+        &token,
+        Some(&[("users", joined.as_str())]),
+    )?;
+    let now = unix_now() as i64;
+    let mut rows: Vec<(u8, Vec<String>)> = ids
+        .iter()
+        .map(|id| {
+            let dnd = resp
+                .pointer(&format!("/users/{id}"))
+                .cloned()
+                .unwrap_or_default();
+            match slack_post(
+                &client,
+                "users.info",
+                &token,
+                Some(&[("user", id.as_str())]),
+            ) {
+                Ok(info) => {
+                    let (active, row) = team_row(id, &info, &dnd, now);
+                    (u8::from(active), row)
+                }
+                Err(e) => (2, vec![id.clone(), format!("error: {e:#}"), "-".into()]),
+            }
+        })
+        .collect();
+    // Who can be pinged now first, then those in DND, then lookups that failed; the sort
+    // is stable, so --users order breaks ties.
+    rows.sort_by_key(|(rank, _)| *rank);
+    let rows: Vec<Vec<String>> = rows.into_iter().map(|(_, row)| row).collect();
+    out!(
+        "{}",
+        table::render(
+            &["NAME", "DND", "NEXT DND (THEIR TIME)"],
+            &rows,
+            &[Some(32), None, None]
+        )
+    );
+    Ok(())
+}
+
+/// (DND active now, `name  DND  next window`) for one user, with the window in the
+/// time zone of their profile.
+fn team_row(id: &str, info: &Value, dnd: &Value, now: i64) -> (bool, Vec<String>) {
+    let str_at = |ptr: &str| info.pointer(ptr).and_then(|v| v.as_str()).unwrap_or("");
+    let name = [str_at("/user/profile/display_name"), str_at("/user/name")]
+        .into_iter()
+        .find(|s| !s.is_empty())
+        .unwrap_or(id)
+        .to_string();
+    let offset = info
+        .pointer("/user/tz_offset")
+        .and_then(|v| v.as_i64())
+        .unwrap_or(0);
+    let tz = match str_at("/user/tz") {
+        "" => "UTC",
+        tz => tz,
+    };
+    let (start, end) = (
+        int_at(dnd, "next_dnd_start_ts"),
+        int_at(dnd, "next_dnd_end_ts"),
+    );
+    let enabled = dnd.get("dnd_enabled").and_then(|v| v.as_bool()) == Some(true);
+    if !enabled || end == 0 {
+        return (false, vec![name, "off".into(), "-".into()]);
+    }
+    let at = |t: i64| {
+        let (day, time) = day_and_time((t + offset) as f64);
+        format!("{day} {time}")
+    };
+    let active = start <= now && now < end;
+    let state = if active { "on" } else { "off" };
+    (
+        active,
+        vec![
+            name,
+            state.into(),
+            format!("{} to {} {tz}", at(start), at(end)),
+        ],
+    )
+}
+
+/// One line for dnd.info: snoozed, inside scheduled DND hours, or off (with the next
+/// scheduled window when there is one).
+fn describe(resp: &Value, now: i64, offset: i64) -> String {
+    let flag = |k: &str| resp.get(k).and_then(|v| v.as_bool()).unwrap_or(false);
+    let (start, end) = (
+        int_at(resp, "next_dnd_start_ts"),
+        int_at(resp, "next_dnd_end_ts"),
+    );
+    if flag("snooze_enabled") {
+        return format!(
+            "snoozed until {}",
+            local_time(int_at(resp, "snooze_endtime"), offset)
+        );
+    }
+    if !flag("dnd_enabled") || end == 0 {
+        return "off".to_string();
+    }
+    if start <= now && now < end {
+        return format!("on (scheduled) until {}", local_time(end, offset));
+    }
+    format!(
+        "off; next scheduled {} to {}",
+        local_time(start, offset),
+        local_time(end, offset)
+    )
+}
+
+fn int_at(v: &Value, key: &str) -> i64 {
+    v.get(key).and_then(|v| v.as_i64()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn describe_covers_snooze_schedule_and_off() {
+        let snoozed =
+            json!({ "dnd_enabled": true, "snooze_enabled": true, "snooze_endtime": 1_712_345_678 });
+        assert_eq!(
+            describe(&snoozed, 0, 0),
+            "snoozed until 2024-04-05 19:34 +00:00"
+        );
+        let schedule = json!({
+            "dnd_enabled": true,
+            "next_dnd_start_ts": 1_712_340_000,
+            "next_dnd_end_ts": 1_712_345_678,
+            "snooze_enabled": false
+        });
+        assert_eq!(
+            describe(&schedule, 1_712_341_000, 0),
+            "on (scheduled) until 2024-04-05 19:34 +00:00"
+        );
+        assert!(describe(&schedule, 1_712_000_000, 0).starts_with("off; next scheduled "));
+        assert_eq!(describe(&json!({ "dnd_enabled": false }), 0, 0), "off");
+    }
+
+    #[test]
+    fn team_row_shows_the_window_in_their_zone() {
+        let info = json!({ "user": {
+            "name": "jane",
+            "profile": { "display_name": "" },
+            "tz": "Europe/Berlin",
+            "tz_offset": 7200
+        }});
+        let dnd = json!({
+            "dnd_enabled": true,
+            "next_dnd_start_ts": 1_712_340_000,
+            "next_dnd_end_ts": 1_712_345_678
+        });
+        let (active, row) = team_row("U1", &info, &dnd, 1_712_341_000);
+        assert!(active);
+        assert_eq!(
+            row,
+            [
+                "jane",
+                "on",
+                "2024-04-05 20:00 to 2024-04-05 21:34 Europe/Berlin"
+            ]
+        );
+        let (active, row) = team_row("U1", &info, &json!({ "dnd_enabled": false }), 0);
+        assert!(!active);
+        assert_eq!(row[1], "off");
+    }
+}
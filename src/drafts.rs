@@ -0,0 +1,295 @@
+//! `slack draft`: messages kept locally until you decide to send them.
+//!
+//! Drafts live in `drafts.json` under the config dir. Every change rewrites the whole
+//! file through a temp file and a rename, so a crash or a second `slack` process never
+//! leaves a half-written file behind; a file that still fails to parse is moved aside
+//! rather than silently overwritten.
+
+use crate::output::{errln, outln};
+use crate::{
+    config_dir, ensure_token, http, read_text_arg, resolve_channel, unix_now, DraftArgs,
+    DraftCommand, DraftSaveArgs, SendArgs, DEFAULT_SNIPPET_THRESHOLD, DEFAULT_SPLIT_AT,
+};
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Draft {
+    /// Channel ID or `#name`, as given when saving.
+    pub channel: String,
+    pub text: String,
+    #[serde(default)]
+    pub thread_ts: Option<String>,
+    pub created_at: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DraftFile {
+    drafts: Vec<Draft>,
+}
+
+pub fn run(args: DraftArgs) -> Result<()> {
+    let path = drafts_path()?;
+    match args.command {
+        DraftCommand::Save(args) => save(&path, args),
+        DraftCommand::List => {
+            let drafts = load(&path)?;
+            if drafts.is_empty() {
+                outln!("no drafts");
+            }
+            for (i, d) in drafts.iter().enumerate() {
+                let thread = d
+                    .thread_ts
+                    .as_deref()
+                    .map(|ts| format!(" (thread {ts})"))
+                    .unwrap_or_default();
+                outln!("{}\t{}{thread}\t{}", i + 1, d.channel, preview(&d.text, 60));
+            }
+            Ok(())
+        }
+        DraftCommand::Send(args) => {
+            let draft = get(&load(&path)?, args.index)?.clone();
+            let token = ensure_token()?;
+            let channel = resolve_channel(&http(), &token, &draft.channel)?;
+            let send_args = SendArgs {
+                channel,
+                text: draft.text.clone(),
+                thread_ts: draft.thread_ts.clone(),
+                split_at: DEFAULT_SPLIT_AT,
+                split: false,
+                no_split: false,
+                no_thread_split: false,
+                attach: Vec::new(),
+                attach_inline: false,
+                permalink: false,
+                broadcast: false,
+                attachments_file: None,
+                unfurl_links: false,
+                no_unfurl_links: false,
+                unfurl_media: false,
+                no_unfurl_media: false,
+                link_names: false,
+                parse: None,
+                username: None,
+                icon_emoji: None,
+                icon_url: None,
+                as_snippet_if_long: false,
+                snippet_threshold: DEFAULT_SNIPPET_THRESHOLD,
+                mention_group: Vec::new(),
+            };
+            crate::send(&send_args, draft.text.clone())?;
+            // Re-read so drafts saved meanwhile by another process are kept.
+            update(&path, |drafts| drafts.retain(|d| *d != draft))?;
+            Ok(())
+        }
+        DraftCommand::Edit(args) => {
+            let current = get(&load(&path)?, args.index)?.clone();
+            let text = edit_text(&current.text)?;
+            update(&path, |drafts| {
+                if let Some(d) = drafts.iter_mut().find(|d| **d == current) {
+                    d.text = text;
+                }
+            })?;
+            outln!("updated draft {}", args.index);
+            Ok(())
+        }
+        DraftCommand::Delete(args) => {
+            let gone = get(&load(&path)?, args.index)?.clone();
+            update(&path, |drafts| drafts.retain(|d| *d != gone))?;
+            outln!("deleted draft {}", args.index);
+            Ok(())
+        }
+    }
+}
+
+fn save(path: &Path, args: DraftSaveArgs) -> Result<()> {
+    let text = match (&args.text, args.edit) {
+        (Some(t), true) => edit_text(&read_text_arg(t)?)?,
+        (Some(t), false) => read_text_arg(t)?,
+        (None, true) => edit_text("")?,
+        (None, false) => return Err(anyhow!("give the draft text with --text or --edit")),
+    };
+    if text.trim().is_empty() {
+        return Err(anyhow!("draft is empty; nothing saved"));
+    }
+    let draft = Draft {
+        channel: args.channel,
+        text,
+        thread_ts: args.thread_ts,
+        created_at: unix_now(),
+    };
+    let mut count = 0;
+    update(path, |drafts| {
+        drafts.push(draft);
+        count = drafts.len();
+    })?;
+    outln!("saved draft {count}");
+    Ok(())
+}
+
+fn drafts_path() -> Result<PathBuf> {
+    config_dir()
+        .map(|d| d.join("drafts.json"))
+        .ok_or_else(|| anyhow!("no config directory for drafts (set SLACK_CONFIG_DIR)"))
+}
+
+/// 1-based lookup, as shown by `draft list`.
+fn get(drafts: &[Draft], index: usize) -> Result<&Draft> {
+    index
+        .checked_sub(1)
+        .and_then(|i| drafts.get(i))
+        .ok_or_else(|| {
+            anyhow!(
+                "no draft {index}; `slack draft list` shows {}",
+                drafts.len()
+            )
+        })
+}
+
+/// Read the drafts. A missing file is no drafts; an unreadable one is renamed to
+/// `drafts.json.corrupt-<time>` (and reported) so the next save starts clean.
+fn load(path: &Path) -> Result<Vec<Draft>> {
+    let data = match std::fs::read(path) {
+        Ok(d) => d,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("failed to read {}", path.display())),
+    };
+    match serde_json::from_slice::<DraftFile>(&data) {
+        Ok(f) => Ok(f.drafts),
+        Err(e) => {
+            let aside = path.with_extension(format!("json.corrupt-{}", unix_now()));
+            std::fs::rename(path, &aside)
+                .with_context(|| format!("failed to move aside corrupt {}", path.display()))?;
+            errln!(
+                "warning: {} was unreadable ({e}); moved it to {} and started fresh",
+                path.display(),
+                aside.display()
+            );
+            Ok(Vec::new())
+        }
+    }
+}
+
+/// Write all drafts via a temp file in the same directory and an atomic rename.
+fn store(path: &Path, drafts: &[Draft]) -> Result<()> {
+    let body = serde_json::to_vec_pretty(&DraftFile {
+        drafts: drafts.to_vec(),
+    })?;
+    crate::write_atomic(path, &body)
+}
+
+/// Load, change and store in one go, keeping the window for lost updates small.
+fn update(path: &Path, change: impl FnOnce(&mut Vec<Draft>)) -> Result<()> {
+    let mut drafts = load(path)?;
+    change(&mut drafts);
+    store(path, &drafts)
+}
+
+/// `text` on one line, cut to `max` chars with a trailing `…`.
+pub(crate) fn preview(text: &str, max: usize) -> String {
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() > max {
+        let cut: String = flat.chars().take(max.saturating_sub(1)).collect();
+        format!("{cut}…")
+    } else {
+        flat
+    }
+}
+
+/// Open `initial` in $VISUAL/$EDITOR (default `vi`) and return what was saved.
+fn edit_text(initial: &str) -> Result<String> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let path = std::env::temp_dir().join(format!("slack-draft-{}.md", std::process::id()));
+    crate::token_store::write_private(&path, initial.as_bytes())
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    let status = crate::watch::shell(&format!("{editor} \"{}\"", path.display()))
+        .status()
+        .with_context(|| format!("could not run editor `{editor}`"));
+    let text = std::fs::read_to_string(&path);
+    std::fs::remove_file(&path).ok();
+    let status = status?;
+    if !status.success() {
+        return Err(anyhow!(
+            "editor `{editor}` exited with {status}; draft unchanged"
+        ));
+    }
+    Ok(text
+        .context("failed to read back the edited draft")?
+        .trim_end()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draft(text: &str) -> Draft {
+        Draft {
+            channel: "C1".into(),
+            text: text.into(),
+            thread_ts: None,
+            created_at: 1,
+        }
+    }
+
+    #[test]
+    fn store_then_load_round_trips_without_leftovers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("drafts.json");
+        assert!(load(&path).unwrap().is_empty());
+        store(&path, &[draft("one"), draft("two")]).unwrap();
+        assert_eq!(load(&path).unwrap(), vec![draft("one"), draft("two")]);
+        let names: Vec<_> = std::fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        assert_eq!(names, ["drafts.json"]);
+    }
+
+    #[test]
+    fn corrupt_file_is_moved_aside() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("drafts.json");
+        std::fs::write(&path, "{ not json").unwrap();
+        assert!(load(&path).unwrap().is_empty());
+        assert!(!path.exists());
+        let aside: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        assert_eq!(aside.len(), 1);
+        assert!(aside[0].starts_with("drafts.json.corrupt-"), "{aside:?}");
+
+        update(&path, |d| d.push(draft("fresh"))).unwrap();
+        assert_eq!(load(&path).unwrap(), vec![draft("fresh")]);
+    }
+
+    #[test]
+    fn update_keeps_drafts_written_by_someone_else() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("drafts.json");
+        store(&path, &[draft("mine")]).unwrap();
+        let mine = get(&load(&path).unwrap(), 1).unwrap().clone();
+        // Another process saves a draft between our read and our delete.
+        update(&path, |d| d.push(draft("theirs"))).unwrap();
+        update(&path, |d| d.retain(|x| *x != mine)).unwrap();
+        assert_eq!(load(&path).unwrap(), vec![draft("theirs")]);
+    }
+
+    #[test]
+    fn indexes_are_one_based() {
+        let drafts = vec![draft("a"), draft("b")];
+        assert_eq!(get(&drafts, 2).unwrap().text, "b");
+        assert!(get(&drafts, 0).is_err());
+        assert!(get(&drafts, 3).is_err());
+    }
+
+    #[test]
+    fn preview_flattens_and_truncates() {
+        assert_eq!(preview("a\n  b", 10), "a b");
+        assert_eq!(preview("abcdefghij", 5), "abcd…");
+    }
+}
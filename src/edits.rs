@@ -0,0 +1,83 @@
+//! `slack edit` and `slack delete`: change or remove a message that was already posted.
+
+use crate::output::{self, outln};
+use crate::remindme::{excerpt, fetch_message};
+use crate::{
+    ensure_token, http, read_text_arg, resolve_channel, resolve_ts, slack_post, DeleteArgs,
+    EditArgs, SlackError,
+};
+use anyhow::{anyhow, Context, Result};
+
+/// Characters of the message shown when asking to confirm a delete.
+const PREVIEW_CHARS: usize = 80;
+
+pub fn edit(args: EditArgs) -> Result<()> {
+    let text = read_text_arg(&args.text)?;
+    if text.trim().is_empty() {
+        return Err(anyhow!(
+            "--text is empty; Slack can't blank a message (use `slack delete`)"
+        ));
+    }
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let ts = resolve_ts(&client, &token, &channel, args.ts.as_deref(), args.last)?;
+    let form = [
+        ("channel", channel.as_str()),
+        ("ts", ts.as_str()),
+        ("text", text.as_str()),
+    ];
+    let resp = slack_post(&client, "chat.update", &token, Some(&form))
+        .map_err(|e| explain(e, &channel, &ts))?;
+    let ts = resp.get("ts").and_then(|v| v.as_str()).unwrap_or("-");
+    outln!("edited ok, ts={ts}");
+    Ok(())
+}
+
+pub fn delete(args: DeleteArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let ts = resolve_ts(&client, &token, &channel, args.ts.as_deref(), args.last)?;
+    if !args.yes {
+        let message = fetch_message(&client, &token, &channel, &ts, args.thread_ts.as_deref())
+            .map_err(|e| explain(e, &channel, &ts))?;
+        if output::is_capturing() {
+            return Err(anyhow!("no terminal to confirm on; pass --yes"));
+        }
+        let go = dialoguer::Confirm::new()
+            .with_prompt(format!(
+                "Delete \u{201c}{}\u{201d} ({})?",
+                excerpt(&message, PREVIEW_CHARS),
+                ts
+            ))
+            .default(false)
+            .interact()
+            .context("failed to read confirmation")?;
+        if !go {
+            return Err(anyhow!("cancelled; nothing deleted"));
+        }
+    }
+    let form = [("channel", channel.as_str()), ("ts", ts.as_str())];
+    slack_post(&client, "chat.delete", &token, Some(&form))
+        .map_err(|e| explain(e, &channel, &ts))?;
+    outln!("deleted ts={ts}");
+    Ok(())
+}
+
+/// Add a plain-language reason to the Slack errors people actually hit when editing or deleting.
+fn explain(e: anyhow::Error, channel: &str, ts: &str) -> anyhow::Error {
+    match SlackError::code_of(&e) {
+        Some("cant_update_message") => {
+            e.context("you can only edit your own messages (and bots only their own)")
+        }
+        Some("cant_delete_message") => {
+            e.context("this token can only delete its own messages, not someone else's")
+        }
+        Some("edit_window_closed") => {
+            e.context("this workspace no longer allows editing that message")
+        }
+        Some("message_not_found") => e.context(format!("no message with ts {ts} in {channel}")),
+        _ => e,
+    }
+}
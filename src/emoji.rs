@@ -0,0 +1,215 @@
+//! `slack emoji` and `emoji-download`: the workspace's custom emoji (emoji.list).
+
+use crate::files::human_size;
+use crate::output::{errln, outln};
+use crate::{
+    ensure_token, http, parallel_map, slack_post, EmojiArgs, EmojiDownloadArgs, ReportFormat,
+};
+use anyhow::{anyhow, Context, Result};
+use reqwest::blocking::Client;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Images fetched at once by `emoji-download`, and each worker's pause between them, so a
+/// few thousand emoji don't turn into a burst against Slack's CDN.
+const DOWNLOAD_WORKERS: usize = 3;
+const DOWNLOAD_PACING: Duration = Duration::from_millis(200);
+
+#[derive(Debug, PartialEq, Serialize)]
+struct Emoji<'a> {
+    name: &'a str,
+    url: Option<&'a str>,
+    alias_of: Option<&'a str>,
+}
+
+pub fn run(args: EmojiArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let all = custom_emoji(&http(), &token)?;
+    let needle = args.filter.as_deref().map(str::to_lowercase);
+    let shown: Vec<Emoji> = all
+        .iter()
+        .filter(|(name, _)| {
+            needle
+                .as_deref()
+                .is_none_or(|n| name.to_lowercase().contains(n))
+        })
+        .map(|(name, value)| match alias_target(value) {
+            Some(target) => Emoji {
+                name,
+                url: None,
+                alias_of: Some(target),
+            },
+            None => Emoji {
+                name,
+                url: Some(value),
+                alias_of: None,
+            },
+        })
+        .collect();
+    match args.format {
+        ReportFormat::Json => outln!("{}", serde_json::to_string_pretty(&shown)?),
+        ReportFormat::Text => {
+            for e in &shown {
+                match e.alias_of {
+                    Some(target) => outln!("{}\talias of {target}", e.name),
+                    None => outln!("{}\t{}", e.name, e.url.unwrap_or("-")),
+                }
+            }
+            match args.filter.as_deref() {
+                Some(filter) => outln!(
+                    "{} of {} custom emoji match `{filter}`",
+                    shown.len(),
+                    all.len()
+                ),
+                None => outln!("{} custom emoji", shown.len()),
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn download(args: EmojiDownloadArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let all = custom_emoji(&client, &token)?;
+    let (aliases, images): (BTreeMap<_, _>, BTreeMap<_, _>) = all
+        .iter()
+        .partition(|(_, value)| alias_target(value).is_some());
+    std::fs::create_dir_all(&args.out)
+        .with_context(|| format!("failed to create {}", args.out.display()))?;
+    if !aliases.is_empty() {
+        let map: BTreeMap<&str, &str> = aliases
+            .iter()
+            .filter_map(|(name, value)| Some((name.as_str(), alias_target(value)?)))
+            .collect();
+        let path = args.out.join("aliases.json");
+        std::fs::write(&path, serde_json::to_string_pretty(&map)? + "\n")
+            .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+    let images: Vec<(&String, &String)> = images.into_iter().collect();
+    let total = images.len();
+    // Progress goes through the reporter so it still prints as each image lands.
+    let (started, finished) = (AtomicUsize::new(0), AtomicUsize::new(0));
+    let results = parallel_map(&images, DOWNLOAD_WORKERS, |&(name, url), report| {
+        if started.fetch_add(1, Ordering::Relaxed) >= DOWNLOAD_WORKERS {
+            std::thread::sleep(DOWNLOAD_PACING);
+        }
+        let result = fetch_image(&client, name, url, &args.out);
+        let done = finished.fetch_add(1, Ordering::Relaxed) + 1;
+        match &result {
+            Ok((file, n)) => report(format!("[{done}/{total}] {file} ({n} bytes)")),
+            Err(e) => report(format!("[{done}/{total}] {name} failed: {e:#}")),
+        }
+        result
+    });
+    let (mut saved, mut bytes) = (0, 0);
+    let mut failures = Vec::new();
+    for (&(name, _), result) in images.iter().zip(results) {
+        match result {
+            Ok((_, n)) => {
+                saved += 1;
+                bytes += n;
+            }
+            Err(e) => failures.push(format!("{name}: {e:#}")),
+        }
+    }
+    outln!(
+        "downloaded {saved} of {total} emoji, {} to {}",
+        human_size(bytes),
+        args.out.display()
+    );
+    if !aliases.is_empty() {
+        outln!("{} alias(es) listed in aliases.json", aliases.len());
+    }
+    if !failures.is_empty() {
+        failures.sort();
+        for f in &failures {
+            errln!("  {f}");
+        }
+        return Err(anyhow!("{} emoji could not be downloaded", failures.len()));
+    }
+    Ok(())
+}
+
+/// Fetch one emoji image into `dir` as `<name>.<ext>`; returns the file name and size.
+/// The CDN URLs are public, so the token is not sent along.
+fn fetch_image(client: &Client, name: &str, url: &str, dir: &Path) -> Result<(String, u64)> {
+    let resp = client.get(url).send().context("request failed")?;
+    let st = resp.status();
+    if !st.is_success() {
+        return Err(anyhow!("HTTP {st}"));
+    }
+    let body = resp.bytes().context("download failed")?;
+    let file = format!("{}.{}", safe_name(name), extension(url));
+    let path = dir.join(&file);
+    std::fs::write(&path, &body).with_context(|| format!("failed to write {}", path.display()))?;
+    Ok((file, body.len() as u64))
+}
+
+/// The image's extension from its URL (`.../shipit/1a2b3c.png` is png), or png.
+fn extension(url: &str) -> &str {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let last = path.rsplit('/').next().unwrap_or("");
+    match last.rsplit_once('.') {
+        Some((_, ext))
+            if !ext.is_empty()
+                && ext.len() <= 5
+                && ext.chars().all(|c| c.is_ascii_alphanumeric()) =>
+        {
+            ext
+        }
+        _ => "png",
+    }
+}
+
+/// An emoji name usable as a file name; Slack allows little besides letters, digits,
+/// `-`, `_` and `'`, but nothing here should escape `--out`.
+fn safe_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if matches!(c, '/' | '\\' | '\0') {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect::<String>()
+        .trim_start_matches('.')
+        .to_string()
+}
+
+/// The workspace's custom emoji, name to image URL or `alias:<name>`, sorted by name.
+pub(crate) fn custom_emoji(client: &Client, token: &str) -> Result<BTreeMap<String, String>> {
+    let resp = slack_post(client, "emoji.list", token, None)?;
+    Ok(resp
+        .get("emoji")
+        .and_then(|v| v.as_object())
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, v)| Some((name.clone(), v.as_str()?.to_string())))
+        .collect())
+}
+
+/// The emoji an `alias:<name>` entry points at; `None` for an image URL.
+pub(crate) fn alias_target(value: &str) -> Option<&str> {
+    value.strip_prefix("alias:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extension_comes_from_the_url() {
+        assert_eq!(
+            extension("https://emoji.slack-edge.com/T1/shipit/1a2b3c.png"),
+            "png"
+        );
+        assert_eq!(extension("https://e.example/T1/parrot/9f.gif?v=2"), "gif");
+        assert_eq!(extension("https://e.example/T1/odd/noext"), "png");
+        assert_eq!(safe_name("../etc"), "_etc");
+    }
+}
@@ -0,0 +1,348 @@
+//! Self-contained HTML rendering for `slack export --format html`.
+//!
+//! Everything that comes from Slack (names, message text, URLs) is escaped before it is
+//! placed in the page; the only markup emitted is what this module writes itself.
+
+use crate::UserInfo;
+use serde_json::Value;
+use std::collections::HashMap;
+
+const TEMPLATE: &str = include_str!("export_template.html");
+
+/// Render messages (oldest first, each optionally carrying a `replies` array) as a page.
+/// `avatars` maps user IDs to an image `src` (a URL or a `data:` URI).
+pub fn render(
+    title: &str,
+    subtitle: &str,
+    messages: &[Value],
+    users: &HashMap<String, UserInfo>,
+    avatars: &HashMap<String, String>,
+) -> String {
+    let mut body = String::new();
+    let mut current_day = String::new();
+    for m in messages {
+        let (day, _) = day_and_time(ts_of(m));
+        if day != current_day {
+            if !current_day.is_empty() {
+                body.push_str("</section>\n");
+            }
+            body.push_str(&format!(
+                "<section class=\"day\">\n<h2>{}</h2>\n",
+                escape(&day)
+            ));
+            current_day = day;
+        }
+        body.push_str(&render_message(m, users, avatars));
+        let replies = m
+            .get("replies")
+            .and_then(|v| v.as_array())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        if !replies.is_empty() {
+            body.push_str("<div class=\"replies\">\n");
+            for r in replies {
+                body.push_str(&render_message(r, users, avatars));
+            }
+            body.push_str("</div>\n");
+        }
+    }
+    if !current_day.is_empty() {
+        body.push_str("</section>\n");
+    }
+    fill(
+        TEMPLATE,
+        &[
+            ("title", escape(title)),
+            ("subtitle", escape(subtitle)),
+            ("body", body),
+        ],
+    )
+}
+
+fn render_message(
+    m: &Value,
+    users: &HashMap<String, UserInfo>,
+    avatars: &HashMap<String, String>,
+) -> String {
+    let uid = m
+        .get("user")
+        .and_then(|v| v.as_str())
+        .or_else(|| m.get("bot_id").and_then(|v| v.as_str()))
+        .unwrap_or("unknown");
+    let name = users
+        .get(uid)
+        .map(|u| u.display_name.clone())
+        .or_else(|| {
+            m.get("username")
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| uid.to_string());
+    let avatar = match avatars.get(uid) {
+        Some(src) => format!("<img class=\"avatar\" src=\"{}\" alt=\"\">", escape(src)),
+        None => "<div class=\"avatar\"></div>".to_string(),
+    };
+    let (_, time) = day_and_time(ts_of(m));
+    let text = m.get("text").and_then(|v| v.as_str()).unwrap_or("");
+    let mut out = format!(
+        "<div class=\"msg\">{avatar}<div>\n<div class=\"meta\"><span class=\"author\">{}</span><span class=\"time\">{}</span></div>\n<div class=\"text\">{}</div>\n",
+        escape(&name),
+        escape(&time),
+        render_mrkdwn(text, users)
+    );
+    if let Some(reactions) = m.get("reactions").and_then(|v| v.as_array()) {
+        out.push_str("<div class=\"reactions\">");
+        for r in reactions {
+            let emoji = r.get("name").and_then(|v| v.as_str()).unwrap_or("?");
+            let count = r.get("count").and_then(|v| v.as_u64()).unwrap_or(0);
+            out.push_str(&format!(
+                "<span class=\"reaction\">:{}: {count}</span>",
+                escape(emoji)
+            ));
+        }
+        out.push_str("</div>\n");
+    }
+    out.push_str("</div></div>\n");
+    out
+}
+
+fn ts_of(m: &Value) -> f64 {
+    m.get("ts")
+        .and_then(|v| v.as_str())
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0.0)
+}
+
+/// `("2024-04-05", "14:03")` in UTC for a Slack ts.
+pub(crate) fn day_and_time(ts: f64) -> (String, String) {
+    let secs = ts.max(0.0) as i64;
+    let (y, m, d) = civil_from_days(secs.div_euclid(86_400));
+    let rem = secs.rem_euclid(86_400);
+    (
+        format!("{y:04}-{m:02}-{d:02}"),
+        format!("{:02}:{:02}", rem / 3600, (rem % 3600) / 60),
+    )
+}
+
+/// A (year, month, day) civil date to days since 1970-01-01; the inverse of `civil_from_days`.
+pub(crate) fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let mp = i64::from(if m > 2 { m - 3 } else { m + 9 });
+    let doy = (153 * mp + 2) / 5 + i64::from(d) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Days since 1970-01-01 to a (year, month, day) civil date.
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    (y, m, d)
+}
+
+/// Replace `{{key}}` placeholders in one pass so substituted values are never re-scanned.
+fn fill(template: &str, values: &[(&str, String)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                let key = &after[..end];
+                match values.iter().find(|(k, _)| *k == key) {
+                    Some((_, v)) => out.push_str(v),
+                    None => out.push_str(&rest[start..start + 2 + end + 2]),
+                }
+                rest = &after[end + 2..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+pub fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Slack sends `&`, `<`, `>` in message text as entities; undo that before escaping for HTML.
+fn unslack(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+/// Render Slack mrkdwn: ```pre```, `code`, *bold*, _italic_, ~strike~, <links|label>,
+/// <@U…> and <#C…|name> mentions.
+fn render_mrkdwn(text: &str, users: &HashMap<String, UserInfo>) -> String {
+    let mut out = String::new();
+    for (i, chunk) in text.split("```").enumerate() {
+        if i % 2 == 1 {
+            out.push_str(&format!("<pre>{}</pre>", escape(&unslack(chunk))));
+        } else {
+            out.push_str(&render_inline(chunk, users));
+        }
+    }
+    out
+}
+
+fn render_inline(text: &str, users: &HashMap<String, UserInfo>) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    // Angle-bracket tokens are real `<`/`>` in Slack's wire format.
+    while let Some(open) = rest.find('<') {
+        let Some(close) = rest[open..].find('>') else {
+            break;
+        };
+        out.push_str(&format_spans(&rest[..open]));
+        out.push_str(&render_token(&rest[open + 1..open + close], users));
+        rest = &rest[open + close + 1..];
+    }
+    out.push_str(&format_spans(rest));
+    out
+}
+
+fn render_token(token: &str, users: &HashMap<String, UserInfo>) -> String {
+    let (target, label) = match token.split_once('|') {
+        Some((t, l)) => (t, Some(l)),
+        None => (token, None),
+    };
+    if let Some(uid) = target.strip_prefix('@') {
+        let name = label
+            .map(str::to_string)
+            .or_else(|| users.get(uid).map(|u| u.display_name.clone()))
+            .unwrap_or_else(|| uid.to_string());
+        return format!("<b>@{}</b>", escape(&unslack(&name)));
+    }
+    if let Some(cid) = target.strip_prefix('#') {
+        return format!("<b>#{}</b>", escape(&unslack(label.unwrap_or(cid))));
+    }
+    if let Some(special) = target.strip_prefix('!') {
+        return format!("<b>@{}</b>", escape(&unslack(label.unwrap_or(special))));
+    }
+    let url = unslack(target);
+    let shown = escape(&unslack(label.unwrap_or(target)));
+    if ["http://", "https://", "mailto:"]
+        .iter()
+        .any(|p| url.to_ascii_lowercase().starts_with(p))
+    {
+        format!("<a href=\"{}\">{shown}</a>", escape(&url))
+    } else {
+        shown
+    }
+}
+
+/// Escape plain text and apply inline `code`, *bold*, _italic_ and ~strike~ spans.
+fn format_spans(text: &str) -> String {
+    let mut out = String::new();
+    for (i, chunk) in text.split('`').enumerate() {
+        let escaped = escape(&unslack(chunk));
+        if i % 2 == 1 {
+            out.push_str(&format!("<code>{escaped}</code>"));
+        } else {
+            let s = wrap_pairs(&escaped, '*', "b");
+            let s = wrap_pairs(&s, '_', "i");
+            out.push_str(&wrap_pairs(&s, '~', "s"));
+        }
+    }
+    out
+}
+
+/// Turn `<d>text<d>` into `<tag>text</tag>` when the delimiters hug non-space text.
+fn wrap_pairs(s: &str, delim: char, tag: &str) -> String {
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find(delim) {
+        let inner_start = start + delim.len_utf8();
+        let Some(len) = rest[inner_start..].find(delim) else {
+            break;
+        };
+        let inner = &rest[inner_start..inner_start + len];
+        let hugs = !inner.is_empty()
+            && !inner.starts_with(char::is_whitespace)
+            && !inner.ends_with(char::is_whitespace)
+            && !inner.contains('\n');
+        if hugs {
+            out.push_str(&rest[..start]);
+            out.push_str(&format!("<{tag}>{inner}</{tag}>"));
+            rest = &rest[inner_start + len + delim.len_utf8()..];
+        } else {
+            out.push_str(&rest[..inner_start]);
+            rest = &rest[inner_start..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_markup_in_text() {
+        let users = HashMap::new();
+        let html = render_mrkdwn("&lt;script&gt;alert(1)&lt;/script&gt; \"q\"", &users);
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(html.contains("&quot;q&quot;"));
+    }
+
+    #[test]
+    fn renders_inline_formatting_and_links() {
+        let users = HashMap::new();
+        let html = render_mrkdwn(
+            "*bold* _it_ ~gone~ `a*b*` <https://x.test/?a=1&amp;b=2|site> <javascript:alert(1)|bad>",
+            &users,
+        );
+        assert!(html.contains("<b>bold</b>"));
+        assert!(html.contains("<i>it</i>"));
+        assert!(html.contains("<s>gone</s>"));
+        assert!(html.contains("<code>a*b*</code>"));
+        assert!(html.contains("<a href=\"https://x.test/?a=1&amp;b=2\">site</a>"));
+        assert!(!html.contains("javascript:alert"));
+    }
+
+    #[test]
+    fn fill_does_not_rescan_values() {
+        let out = fill(
+            "{{a}}|{{b}}|{{c}}",
+            &[("a", "{{b}}".into()), ("b", "B".into())],
+        );
+        assert_eq!(out, "{{b}}|B|{{c}}");
+    }
+
+    #[test]
+    fn day_and_time_in_utc() {
+        assert_eq!(
+            day_and_time(1_712_345_678.000_1),
+            ("2024-04-05".to_string(), "19:34".to_string())
+        );
+        assert_eq!(day_and_time(0.0).0, "1970-01-01");
+    }
+}
@@ -0,0 +1,624 @@
+//! `slack files`, `download`, `download-all`, `file-delete`, `files-cleanup` and
+//! `file-public`: list uploaded files (files.list, which pages by number), fetch them to
+//! disk, remove one or many, or share one publicly.
+
+use crate::export_html::{day_and_time, days_from_civil};
+use crate::output::{self, errln, outln};
+use crate::stats::parse_since;
+use crate::{
+    auth_test, ensure_token, fetch_users_map, http, resolve_channel, slack_get, slack_post,
+    slack_post_retrying, transfer_http, unix_now, DownloadAllArgs, DownloadArgs, FileDeleteArgs,
+    FilePublicArgs, FilesArgs, FilesCleanupArgs, SlackError,
+};
+use anyhow::{anyhow, Context, Result};
+use reqwest::blocking::Client;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Files asked for per files.list page.
+const PAGE_SIZE: usize = 100;
+/// Pause between files.delete calls (a Tier 3 method, ~50 calls a minute).
+const DELETE_PACING: Duration = Duration::from_millis(1200);
+
+pub fn list(args: FilesArgs) -> Result<()> {
+    let ts_from = args
+        .from
+        .as_deref()
+        .map(|d| parse_day(d, false))
+        .transpose()?;
+    let ts_to = args.to.as_deref().map(|d| parse_day(d, true)).transpose()?;
+    let token = ensure_token()?;
+    let client = http();
+    let channel = match args.channel.as_deref() {
+        Some(c) => Some(resolve_channel(&client, &token, c)?),
+        None => None,
+    };
+    let (ts_from, ts_to) = (ts_from.map(|t| t.to_string()), ts_to.map(|t| t.to_string()));
+    let filters = [
+        ("channel", channel.as_deref()),
+        ("user", args.user.as_deref()),
+        ("types", args.types.as_deref()),
+        ("ts_from", ts_from.as_deref()),
+        ("ts_to", ts_to.as_deref()),
+    ];
+    // Each page is printed as it arrives; names are looked up once there is a file.
+    let mut names: Option<HashMap<String, String>> = None;
+    each_file_page(&client, &token, &filters, args.limit as usize, |files| {
+        let names = match &mut names {
+            Some(names) => names,
+            None => names.insert(
+                fetch_users_map(&client, &token)?
+                    .into_iter()
+                    .map(|(id, u)| (id, u.display_name))
+                    .collect(),
+            ),
+        };
+        for f in files {
+            outln!("{}", file_line(f, names));
+        }
+        Ok(())
+    })?;
+    if names.is_none() {
+        outln!("no files");
+    }
+    Ok(())
+}
+
+pub fn cleanup(args: FilesCleanupArgs) -> Result<()> {
+    let cutoff = parse_since(&args.older_than, unix_now())
+        .map_err(|_| anyhow!("--older-than must look like 365d, 12w or a unix time"))?;
+    let token = ensure_token()?;
+    let client = http();
+    let user = if args.user == "me" {
+        auth_test(&client, &token)?
+            .user_id
+            .ok_or_else(|| anyhow!("auth.test did not say who you are; pass --user"))?
+    } else {
+        args.user.clone()
+    };
+    let channel = match args.channel.as_deref() {
+        Some(c) => Some(resolve_channel(&client, &token, c)?),
+        None => None,
+    };
+    let ts_to = cutoff.to_string();
+    let filters = [
+        ("user", Some(user.as_str())),
+        ("types", args.types.as_deref()),
+        ("channel", channel.as_deref()),
+        ("ts_to", Some(ts_to.as_str())),
+    ];
+    let files = fetch_files(&client, &token, &filters, usize::MAX)?;
+    if files.is_empty() {
+        outln!("no files older than {}", args.older_than);
+        return Ok(());
+    }
+    let size = |f: &Value| f.get("size").and_then(|v| v.as_u64()).unwrap_or(0);
+    for f in &files {
+        outln!("{}", file_line(f, &HashMap::new()));
+    }
+    let total: u64 = files.iter().map(size).sum();
+    if !args.execute {
+        outln!(
+            "{} files, {} (dry run; pass --execute to delete them)",
+            files.len(),
+            human_size(total)
+        );
+        return Ok(());
+    }
+
+    let (mut deleted, mut freed, mut failed) = (0, 0, 0);
+    for (i, f) in files.iter().enumerate() {
+        if i > 0 {
+            std::thread::sleep(DELETE_PACING);
+        }
+        let id = f.get("id").and_then(|v| v.as_str()).unwrap_or("");
+        match slack_post_retrying(&client, &token, "files.delete", &[("file", id)]) {
+            Ok(_) => {
+                deleted += 1;
+                freed += size(f);
+            }
+            Err(e) => {
+                errln!("failed to delete {id}: {:#}", explain(e, id));
+                failed += 1;
+            }
+        }
+    }
+    outln!("deleted {deleted} files, {} freed", human_size(freed));
+    if failed > 0 {
+        return Err(anyhow!("{failed} file(s) could not be deleted"));
+    }
+    Ok(())
+}
+
+/// files.list with the given filters (unset ones left out), following pages until `limit`
+/// files are in hand or there are no more.
+fn fetch_files(
+    client: &Client,
+    token: &str,
+    filters: &[(&str, Option<&str>)],
+    limit: usize,
+) -> Result<Vec<Value>> {
+    let mut files = Vec::new();
+    each_file_page(client, token, filters, limit, |page| {
+        files.extend_from_slice(page);
+        Ok(())
+    })?;
+    Ok(files)
+}
+
+/// `fetch_files` a page at a time: each non-empty page goes to `on_page` as soon as it
+/// is parsed, and the last is cut short at `limit`. files.list pages by number, not by
+/// cursor, so this can't use `each_page`.
+fn each_file_page(
+    client: &Client,
+    token: &str,
+    filters: &[(&str, Option<&str>)],
+    limit: usize,
+    mut on_page: impl FnMut(&[Value]) -> Result<()>,
+) -> Result<()> {
+    let count = limit.min(PAGE_SIZE).to_string();
+    let mut seen = 0;
+    let mut page: u64 = 1;
+    while seen < limit {
+        let page_str = page.to_string();
+        let mut form = vec![("count", count.as_str()), ("page", page_str.as_str())];
+        form.extend(filters.iter().filter_map(|&(k, v)| Some((k, v?))));
+        let resp = slack_post_retrying(client, token, "files.list", &form)?;
+        let files = resp
+            .get("files")
+            .and_then(|v| v.as_array())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let files = &files[..files.len().min(limit - seen)];
+        if !files.is_empty() {
+            on_page(files)?;
+        }
+        seen += files.len();
+        let pages = resp
+            .pointer("/paging/pages")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        if page >= pages {
+            break;
+        }
+        page += 1;
+    }
+    Ok(())
+}
+
+pub fn download(args: DownloadArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let resp = slack_get(
+        &client,
+        "files.info",
+        &token,
+        &[("file", args.file.as_str())],
+    )?;
+    let file = resp.get("file").cloned().unwrap_or_default();
+    let name = file.get("name").and_then(|v| v.as_str()).unwrap_or("");
+    let path = output_path(args.out.as_deref(), name, &args.file);
+    if path.exists() && !args.force {
+        return Err(anyhow!(
+            "{} already exists; pass --force to overwrite it",
+            path.display()
+        ));
+    }
+    let written = fetch_to(&transfer_http(), &token, &file, &path)?;
+    outln!("{}\t{written} bytes", path.display());
+    Ok(())
+}
+
+pub fn download_all(args: DownloadAllArgs) -> Result<()> {
+    let since = args
+        .since
+        .as_deref()
+        .map(|d| parse_day(d, false))
+        .transpose()?
+        .map(|t| t.to_string());
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let filters = [
+        ("channel", Some(channel.as_str())),
+        ("ts_from", since.as_deref()),
+    ];
+    let files = fetch_files(&client, &token, &filters, usize::MAX)?;
+    if files.is_empty() {
+        outln!("no files to download");
+        return Ok(());
+    }
+    std::fs::create_dir_all(&args.out)
+        .with_context(|| format!("failed to create {}", args.out.display()))?;
+    let transfer = transfer_http();
+    let total = files.len();
+    let (mut saved, mut bytes) = (0, 0);
+    let mut failures = Vec::new();
+    for (i, file) in files.iter().enumerate() {
+        let id = file.get("id").and_then(|v| v.as_str()).unwrap_or("?");
+        let name = file.get("name").and_then(|v| v.as_str()).unwrap_or("");
+        let path = unused_path(&output_path(Some(&args.out), name, id));
+        match fetch_to(&transfer, &token, file, &path) {
+            Ok(n) => {
+                saved += 1;
+                bytes += n;
+                errln!("[{}/{total}] {} ({n} bytes)", i + 1, path.display());
+            }
+            Err(e) => {
+                errln!("[{}/{total}] {id} failed: {e:#}", i + 1);
+                failures.push(format!("{id} ({name}): {e:#}"));
+            }
+        }
+    }
+    outln!(
+        "downloaded {saved} of {total} files, {} to {}",
+        human_size(bytes),
+        args.out.display()
+    );
+    if !failures.is_empty() {
+        for f in &failures {
+            errln!("  {f}");
+        }
+        return Err(anyhow!(
+            "{} file(s) could not be downloaded",
+            failures.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Stream `file`'s private download to `path` with the token; returns the bytes written.
+fn fetch_to(client: &Client, token: &str, file: &Value, path: &Path) -> Result<u64> {
+    let id = file
+        .get("id")
+        .and_then(|v| v.as_str())
+        .unwrap_or("the file");
+    let name = file.get("name").and_then(|v| v.as_str()).unwrap_or("");
+    let url = ["url_private_download", "url_private"]
+        .iter()
+        .find_map(|k| file.get(*k).and_then(|v| v.as_str()))
+        .ok_or_else(|| anyhow!("{id} has no download URL (is it an external file?)"))?;
+    let mut body = client
+        .get(url)
+        .bearer_auth(token)
+        .send()
+        .with_context(|| format!("downloading {id} failed"))?;
+    let st = body.status();
+    if !st.is_success() {
+        return Err(anyhow!("HTTP {st} downloading {id}"));
+    }
+    // Without files:read Slack answers with its sign-in page rather than an error status.
+    let is_html = body
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|t| t.starts_with("text/html"));
+    if is_html && !name.ends_with(".html") {
+        return Err(anyhow!(
+            "Slack returned a web page instead of {id}; the token may lack files:read"
+        ));
+    }
+
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+    // Stream into a sibling temp file so a failed download never leaves half a file behind.
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".part-{}", std::process::id()));
+    let tmp = PathBuf::from(tmp);
+    let written = (|| -> Result<u64> {
+        let mut out = std::io::BufWriter::new(
+            std::fs::File::create(&tmp)
+                .with_context(|| format!("failed to create {}", tmp.display()))?,
+        );
+        let n = body
+            .copy_to(&mut out)
+            .with_context(|| format!("downloading {id} failed"))?;
+        out.flush()
+            .with_context(|| format!("failed to write {}", tmp.display()))?;
+        Ok(n)
+    })();
+    let written = match written {
+        Ok(n) => n,
+        Err(e) => {
+            std::fs::remove_file(&tmp).ok();
+            return Err(e);
+        }
+    };
+    std::fs::rename(&tmp, path).with_context(|| {
+        std::fs::remove_file(&tmp).ok();
+        format!("failed to write {}", path.display())
+    })?;
+    Ok(written)
+}
+
+/// `path`, or the first of `name-1.ext`, `name-2.ext`, ... beside it that doesn't exist yet.
+fn unused_path(path: &Path) -> PathBuf {
+    if !path.exists() {
+        return path.to_path_buf();
+    }
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let ext = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (1..)
+        .map(|n| path.with_file_name(format!("{stem}-{n}{ext}")))
+        .find(|p| !p.exists())
+        .expect("some suffix is free")
+}
+
+pub fn delete(args: FileDeleteArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    if !args.yes {
+        let resp = slack_get(
+            &client,
+            "files.info",
+            &token,
+            &[("file", args.file.as_str())],
+        )
+        .map_err(|e| explain(e, &args.file))?;
+        let file = resp.get("file").cloned().unwrap_or_default();
+        let name = file.get("name").and_then(|v| v.as_str()).unwrap_or("?");
+        let uid = file
+            .get("user")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown");
+        // The name is only for the prompt; an ID will do if users.list is out of reach.
+        let uploader = fetch_users_map(&client, &token)
+            .ok()
+            .and_then(|users| users.get(uid).map(|u| format!("@{}", u.display_name)))
+            .unwrap_or_else(|| uid.to_string());
+        if output::is_capturing() {
+            return Err(anyhow!("no terminal to confirm on; pass --yes"));
+        }
+        let go = dialoguer::Confirm::new()
+            .with_prompt(format!(
+                "Delete {name} ({}), uploaded by {uploader}?",
+                args.file
+            ))
+            .default(false)
+            .interact()
+            .context("failed to read confirmation")?;
+        if !go {
+            return Err(anyhow!("cancelled; nothing deleted"));
+        }
+    }
+    slack_post(
+        &client,
+        "files.delete",
+        &token,
+        Some(&[("file", args.file.as_str())]),
+    )
+    .map_err(|e| explain(e, &args.file))?;
+    outln!("deleted {}", args.file);
+    Ok(())
+}
+
+pub fn public(args: FilePublicArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let method = if args.enable {
+        "files.sharedPublicURL"
+    } else {
+        "files.revokePublicURL"
+    };
+    let resp = slack_post(
+        &client,
+        method,
+        &token,
+        Some(&[("file", args.file.as_str())]),
+    )
+    .map_err(|e| explain_public(e, &args.file))?;
+    let name = resp
+        .pointer("/file/name")
+        .and_then(|v| v.as_str())
+        .unwrap_or("?");
+    if args.enable {
+        outln!("public link for {name} ({})", args.file);
+        match resp
+            .pointer("/file/permalink_public")
+            .and_then(|v| v.as_str())
+        {
+            Some(url) => outln!("{url}"),
+            None => errln!("warning: Slack did not return the public link"),
+        }
+    } else {
+        outln!("public link revoked for {name} ({})", args.file);
+    }
+    Ok(())
+}
+
+/// `explain`, plus the reasons Slack refuses a public link.
+fn explain_public(e: anyhow::Error, id: &str) -> anyhow::Error {
+    match SlackError::code_of(&e) {
+        Some("missing_scope" | "public_url_scopes" | "not_allowed_token_type") => e.context(
+            "public links need a user token (xoxp-) with files:write; \
+             run `slack init --force` with one",
+        ),
+        Some("not_allowed") => e.context("public file sharing is turned off in this workspace"),
+        _ => explain(e, id),
+    }
+}
+
+/// Add a plain-language reason to the Slack errors people hit when deleting a file.
+fn explain(e: anyhow::Error, id: &str) -> anyhow::Error {
+    match SlackError::code_of(&e) {
+        Some("cant_delete_file") => e.context(format!(
+            "only {id}'s uploader or a workspace admin can delete it"
+        )),
+        Some("file_not_found" | "file_deleted") => e.context(format!("no file {id}")),
+        _ => e,
+    }
+}
+
+/// Where `download` saves: inside `out` when it is a directory (or written like one, with
+/// a trailing slash), else `out` itself. Slack's name is cut to its last path component.
+fn output_path(out: Option<&Path>, name: &str, id: &str) -> PathBuf {
+    let name = Path::new(name)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .filter(|n| !n.is_empty())
+        .unwrap_or(id);
+    match out {
+        None => PathBuf::from(name),
+        Some(out) if out.is_dir() || out.as_os_str().to_string_lossy().ends_with('/') => {
+            out.join(name)
+        }
+        Some(out) => out.to_path_buf(),
+    }
+}
+
+/// `id  name  size  mimetype  @uploader  created` for one file.
+fn file_line(f: &Value, names: &HashMap<String, String>) -> String {
+    let str_at = |k: &str| f.get(k).and_then(|v| v.as_str()).unwrap_or("");
+    let name = [str_at("name"), str_at("title")]
+        .into_iter()
+        .find(|s| !s.is_empty())
+        .unwrap_or("-");
+    let size = f
+        .get("size")
+        .and_then(|v| v.as_u64())
+        .map(human_size)
+        .unwrap_or_else(|| "-".into());
+    let uploader = match names.get(str_at("user")) {
+        Some(name) => format!("@{name}"),
+        None => str_at("user").to_string(),
+    };
+    let created = match f.get("created").and_then(|v| v.as_u64()) {
+        Some(secs) => {
+            let (day, time) = day_and_time(secs as f64);
+            format!("{day} {time}")
+        }
+        None => "-".into(),
+    };
+    format!(
+        "{}\t{name}\t{size}\t{}\t{uploader}\t{created}",
+        str_at("id"),
+        str_at("mimetype")
+    )
+}
+
+/// `512 B`, `1.5 KiB`, `12.0 MiB`, ...
+pub(crate) fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+/// `2024-04-05` (UTC; the start of the day, or its last second when `end_of_day`) or a
+/// unix time, as unix seconds.
+fn parse_day(s: &str, end_of_day: bool) -> Result<u64> {
+    let s = s.trim();
+    if let Ok(secs) = s.parse::<u64>() {
+        return Ok(secs);
+    }
+    let bad = || anyhow!("`{s}` is not a date; use YYYY-MM-DD or a unix time");
+    let mut parts = s.splitn(3, '-');
+    let mut field = || {
+        parts
+            .next()
+            .and_then(|p| p.parse::<u32>().ok())
+            .ok_or_else(bad)
+    };
+    let (y, m, d) = (field()?, field()?, field()?);
+    if !(1..=12).contains(&m) || !(1..=31).contains(&d) || y < 1970 {
+        return Err(bad());
+    }
+    let start = days_from_civil(i64::from(y), m, d) as u64 * 86_400;
+    Ok(if end_of_day { start + 86_399 } else { start })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn sizes_are_human_readable() {
+        assert_eq!(human_size(0), "0 B");
+        assert_eq!(human_size(1023), "1023 B");
+        assert_eq!(human_size(1536), "1.5 KiB");
+        assert_eq!(human_size(12 * 1024 * 1024), "12.0 MiB");
+        assert_eq!(human_size(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+
+    #[test]
+    fn days_parse_to_utc_bounds() {
+        assert_eq!(parse_day("1970-01-01", false).unwrap(), 0);
+        assert_eq!(parse_day("2024-04-05", false).unwrap(), 1_712_275_200);
+        assert_eq!(parse_day("2024-04-05", true).unwrap(), 1_712_361_599);
+        assert_eq!(parse_day("1712345678", true).unwrap(), 1_712_345_678);
+        assert!(parse_day("2024-13-01", false).is_err());
+        assert!(parse_day("April 5", false).is_err());
+    }
+
+    #[test]
+    fn output_path_keeps_slack_name_in_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            output_path(Some(dir.path()), "q3.pdf", "F1"),
+            dir.path().join("q3.pdf")
+        );
+        assert_eq!(
+            output_path(Some(Path::new("new/")), "q3.pdf", "F1"),
+            Path::new("new/q3.pdf")
+        );
+        assert_eq!(
+            output_path(Some(Path::new("x.pdf")), "q3.pdf", "F1"),
+            Path::new("x.pdf")
+        );
+        assert_eq!(
+            output_path(None, "../../etc/passwd", "F1"),
+            Path::new("passwd")
+        );
+        assert_eq!(output_path(None, "", "F1"), Path::new("F1"));
+    }
+
+    #[test]
+    fn unused_path_adds_a_numeric_suffix() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = dir.path().join("report.pdf");
+        assert_eq!(unused_path(&report), report);
+        std::fs::write(&report, b"").unwrap();
+        assert_eq!(unused_path(&report), dir.path().join("report-1.pdf"));
+        std::fs::write(dir.path().join("report-1.pdf"), b"").unwrap();
+        assert_eq!(unused_path(&report), dir.path().join("report-2.pdf"));
+        let notes = dir.path().join("NOTES");
+        std::fs::write(&notes, b"").unwrap();
+        assert_eq!(unused_path(&notes), dir.path().join("NOTES-1"));
+    }
+
+    #[test]
+    fn file_line_names_the_uploader() {
+        let names = HashMap::from([("U1".to_string(), "jane".to_string())]);
+        let f = json!({
+            "id": "F1",
+            "name": "q3.pdf",
+            "size": 1536,
+            "mimetype": "application/pdf",
+            "user": "U1",
+            "created": 1_712_275_200
+        });
+        assert_eq!(
+            file_line(&f, &names),
+            "F1\tq3.pdf\t1.5 KiB\tapplication/pdf\t@jane\t2024-04-05 00:00"
+        );
+    }
+}
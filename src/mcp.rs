@@ -0,0 +1,398 @@
+//! `slack mcp`: a Model Context Protocol server on stdio.
+//!
+//! Messages are JSON-RPC 2.0, one per line. Tools reuse the same helpers as the CLI
+//! commands and return structured JSON; failures (including Slack's own error codes)
+//! are reported as tool results with `isError: true` rather than protocol errors.
+
+use crate::{
+    ensure_token, find_people, http, list_conversations, open_conversation, recent_messages,
+    resolve_channel, slack_post, with_auth_retry, AccountKind, SlackError,
+};
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+use std::sync::atomic::Ordering;
+
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+pub fn serve(allow_write: bool) -> Result<()> {
+    crate::NO_PROMPT.store(true, Ordering::Relaxed);
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+    serve_on(stdin.lock(), &mut stdout, allow_write)
+}
+
+fn serve_on(reader: impl BufRead, writer: &mut impl Write, allow_write: bool) -> Result<()> {
+    for line in reader.lines() {
+        let line = line.context("failed to read from stdin")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let reply = match serde_json::from_str::<Value>(&line) {
+            Ok(msg) => handle(&msg, allow_write),
+            Err(e) => Some(rpc_error(Value::Null, -32700, &format!("parse error: {e}"))),
+        };
+        if let Some(reply) = reply {
+            writeln!(writer, "{reply}").context("failed to write to stdout")?;
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Answer one JSON-RPC message; notifications (no `id`) get no reply.
+fn handle(msg: &Value, allow_write: bool) -> Option<Value> {
+    let id = msg.get("id").cloned();
+    let method = msg.get("method").and_then(|v| v.as_str()).unwrap_or("");
+    let params = msg.get("params").cloned().unwrap_or(Value::Null);
+    let result = match method {
+        "initialize" => json!({
+            "protocolVersion": params
+                .get("protocolVersion")
+                .and_then(|v| v.as_str())
+                .unwrap_or(PROTOCOL_VERSION),
+            "capabilities": { "tools": {} },
+            "serverInfo": { "name": "mdslackcli", "version": env!("CARGO_PKG_VERSION") },
+        }),
+        "ping" => json!({}),
+        "tools/list" => json!({ "tools": tools(allow_write) }),
+        "tools/call" => call_tool(&params, allow_write),
+        _ if id.is_none() => return None,
+        _ => {
+            return Some(rpc_error(
+                id.unwrap_or(Value::Null),
+                -32601,
+                &format!("method not found: {method}"),
+            ))
+        }
+    };
+    let id = id?;
+    Some(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+}
+
+fn rpc_error(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+/// Tools that post to Slack and are only offered with `--allow-write`.
+const WRITE_TOOLS: &[&str] = &["send_message", "open_dm"];
+
+fn tools(allow_write: bool) -> Vec<Value> {
+    let all = vec![
+        json!({
+            "name": "list_channels",
+            "description": "List conversations visible to the user.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "types": {
+                        "type": "string",
+                        "description": "Comma-separated: public_channel, private_channel, mpim, im",
+                        "default": "public_channel,private_channel"
+                    },
+                    "limit": { "type": "integer", "minimum": 1, "maximum": 1000, "default": 100 }
+                }
+            }
+        }),
+        json!({
+            "name": "read_messages",
+            "description": "Recent messages in a channel or DM, oldest first.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "channel": { "type": "string", "description": "Channel ID or #name" },
+                    "limit": { "type": "integer", "minimum": 1, "maximum": 1000, "default": 20 }
+                },
+                "required": ["channel"]
+            }
+        }),
+        json!({
+            "name": "find_person",
+            "description": "Find users by display name, real name, email or user ID.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string" },
+                    "only": { "type": "string", "enum": ["admins", "guests", "bots"] },
+                    "limit": { "type": "integer", "minimum": 1, "default": 50 }
+                },
+                "required": ["query"]
+            }
+        }),
+        json!({
+            "name": "send_message",
+            "description": "Post a message to a channel or DM, optionally as a thread reply.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "channel": { "type": "string", "description": "Channel ID or #name" },
+                    "text": { "type": "string" },
+                    "thread_ts": { "type": "string" }
+                },
+                "required": ["channel", "text"]
+            }
+        }),
+        json!({
+            "name": "open_dm",
+            "description": "Open a DM or group DM with users, optionally posting a first message.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "users": { "type": "string", "description": "Comma-separated user IDs" },
+                    "text": { "type": "string" }
+                },
+                "required": ["users"]
+            }
+        }),
+    ];
+    all.into_iter()
+        .filter(|t| allow_write || !WRITE_TOOLS.contains(&t["name"].as_str().unwrap_or_default()))
+        .collect()
+}
+
+fn call_tool(params: &Value, allow_write: bool) -> Value {
+    let name = params.get("name").and_then(|v| v.as_str()).unwrap_or("");
+    let args = params
+        .get("arguments")
+        .cloned()
+        .unwrap_or_else(|| json!({}));
+    let outcome = if WRITE_TOOLS.contains(&name) && !allow_write {
+        Err(ToolError::new(
+            "write_disabled",
+            format!("`{name}` posts to Slack; restart the server with --allow-write"),
+        ))
+    } else {
+        with_auth_retry(|| run_tool(name, &args)).map_err(ToolError::from)
+    };
+    match outcome {
+        Ok(value) => json!({
+            "content": [{ "type": "text", "text": value.to_string() }],
+            "structuredContent": value,
+            "isError": false,
+        }),
+        Err(e) => {
+            let value = json!({ "error": { "code": e.code, "message": e.message } });
+            json!({
+                "content": [{ "type": "text", "text": e.message }],
+                "structuredContent": value,
+                "isError": true,
+            })
+        }
+    }
+}
+
+fn run_tool(name: &str, args: &Value) -> Result<Value> {
+    match name {
+        "list_channels" => {
+            let types = str_arg(args, "types")?.unwrap_or("public_channel,private_channel");
+            let limit = int_arg(args, "limit", 100)?;
+            let token = ensure_token()?;
+            let chans = list_conversations(&http(), &token, types, limit)?;
+            let channels: Vec<Value> = chans
+                .iter()
+                .map(|ch| {
+                    json!({
+                        "id": ch.get("id"),
+                        "name": ch.get("name"),
+                        "type": crate::conversation_type(ch),
+                        "user": ch.get("user"),
+                    })
+                })
+                .collect();
+            Ok(json!({ "channels": channels }))
+        }
+        "read_messages" => {
+            let channel = required(args, "channel")?;
+            let limit = int_arg(args, "limit", 20)?;
+            let token = ensure_token()?;
+            let client = http();
+            let channel = resolve_channel(&client, &token, channel)?;
+            let messages: Vec<Value> = recent_messages(&client, &token, &channel, limit)?
+                .iter()
+                .map(|m| {
+                    json!({
+                        "ts": m.get("ts"),
+                        "user": m.get("user").or_else(|| m.get("bot_id")),
+                        "text": m.get("text"),
+                        "thread_ts": m.get("thread_ts"),
+                        "reply_count": m.get("reply_count"),
+                    })
+                })
+                .collect();
+            Ok(json!({ "channel": channel, "messages": messages }))
+        }
+        "find_person" => {
+            let query = required(args, "query")?;
+            let only = match str_arg(args, "only")? {
+                Some(s) => Some(
+                    AccountKind::from_str(s, true)
+                        .map_err(|_| invalid("`only` must be admins, guests or bots".into()))?,
+                ),
+                None => None,
+            };
+            let limit = int_arg(args, "limit", 50)? as usize;
+            let token = ensure_token()?;
+            let users = crate::fetch_users_map(&http(), &token)?;
+            let people: Vec<Value> = find_people(&users, query, only, false)
+                .into_iter()
+                .take(limit)
+                .map(|(uid, info)| {
+                    json!({
+                        "id": uid,
+                        "display_name": info.display_name,
+                        "real_name": info.real_name,
+                        "email": info.email,
+                        "flags": info.flags(),
+                        "title": info.title,
+                        "phone": info.phone,
+                        "tz": info.tz,
+                        "tz_offset": info.tz_offset,
+                        "status_emoji": info.status_emoji,
+                        "status_text": info.status_text,
+                    })
+                })
+                .collect();
+            Ok(json!({ "people": people }))
+        }
+        "send_message" => {
+            let channel = required(args, "channel")?;
+            let text = required(args, "text")?;
+            let thread_ts = str_arg(args, "thread_ts")?;
+            let token = ensure_token()?;
+            let client = http();
+            let channel = resolve_channel(&client, &token, channel)?;
+            let mut form = vec![("channel", channel.as_str()), ("text", text)];
+            if let Some(ts) = thread_ts {
+                form.push(("thread_ts", ts));
+            }
+            let resp = slack_post(&client, "chat.postMessage", &token, Some(&form))?;
+            Ok(json!({ "channel": channel, "ts": resp.get("ts") }))
+        }
+        "open_dm" => {
+            let users = required(args, "users")?;
+            let text = str_arg(args, "text")?;
+            let token = ensure_token()?;
+            let client = http();
+            let channel = open_conversation(&client, &token, users)?;
+            let ts = match text {
+                Some(text) => slack_post(
+                    &client,
+                    "chat.postMessage",
+                    &token,
+                    Some(&[("channel", channel.as_str()), ("text", text)]),
+                )?
+                .get("ts")
+                .cloned(),
+                None => None,
+            };
+            Ok(json!({ "channel": channel, "ts": ts }))
+        }
+        _ => Err(ToolError::new("unknown_tool", format!("no tool named `{name}`")).into()),
+    }
+}
+
+/// A tool failure as reported to the client: a stable code plus a readable message.
+#[derive(Debug)]
+struct ToolError {
+    code: String,
+    message: String,
+}
+
+impl ToolError {
+    fn new(code: &str, message: String) -> Self {
+        ToolError {
+            code: code.to_string(),
+            message,
+        }
+    }
+}
+
+impl std::fmt::Display for ToolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ToolError {}
+
+impl From<anyhow::Error> for ToolError {
+    fn from(e: anyhow::Error) -> Self {
+        if let Some(te) = e.downcast_ref::<ToolError>() {
+            return ToolError::new(&te.code, te.message.clone());
+        }
+        let code = SlackError::code_of(&e).unwrap_or("error").to_string();
+        ToolError {
+            code,
+            message: format!("{e:#}"),
+        }
+    }
+}
+
+fn invalid(message: String) -> anyhow::Error {
+    ToolError::new("invalid_arguments", message).into()
+}
+
+fn str_arg<'a>(args: &'a Value, key: &str) -> Result<Option<&'a str>> {
+    match args.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s)),
+        Some(_) => Err(invalid(format!("`{key}` must be a string"))),
+    }
+}
+
+fn required<'a>(args: &'a Value, key: &str) -> Result<&'a str> {
+    str_arg(args, key)?.ok_or_else(|| invalid(format!("`{key}` is required")))
+}
+
+fn int_arg(args: &Value, key: &str, default: u32) -> Result<u32> {
+    match args.get(key) {
+        None | Some(Value::Null) => Ok(default),
+        Some(v) => v
+            .as_u64()
+            .and_then(|n| u32::try_from(n).ok())
+            .filter(|n| *n > 0)
+            .ok_or_else(|| invalid(format!("`{key}` must be a positive integer"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notifications_get_no_reply() {
+        let msg = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        assert!(handle(&msg, false).is_none());
+    }
+
+    #[test]
+    fn write_tools_are_hidden_and_refused_without_allow_write() {
+        let names = |allow| -> Vec<String> {
+            tools(allow)
+                .iter()
+                .map(|t| t["name"].as_str().unwrap().to_string())
+                .collect()
+        };
+        assert!(!names(false).contains(&"send_message".to_string()));
+        assert!(names(true).contains(&"send_message".to_string()));
+
+        let res = call_tool(
+            &json!({ "name": "send_message", "arguments": { "channel": "C1", "text": "hi" } }),
+            false,
+        );
+        assert_eq!(res["isError"], true);
+        assert_eq!(res["structuredContent"]["error"]["code"], "write_disabled");
+    }
+
+    #[test]
+    fn unknown_method_is_a_protocol_error() {
+        let reply = handle(
+            &json!({ "jsonrpc": "2.0", "id": 7, "method": "nope" }),
+            false,
+        )
+        .unwrap();
+        assert_eq!(reply["id"], 7);
+        assert_eq!(reply["error"]["code"], -32601);
+    }
+}
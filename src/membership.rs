@@ -0,0 +1,439 @@
+//! `slack members`, `invite`, `kick`, `leave` and `close`: who is in a channel, and
+//! adding and removing people (yourself included).
+
+use crate::output::{self, errln, out, outln};
+use crate::{
+    each_page, ensure_token, fetch_users_map, http, resolve_channel, resolve_users, slack_post,
+    table, user_info, CloseArgs, InviteArgs, KickArgs, LeaveArgs, MembersArgs, ReportFormat,
+    SlackError,
+};
+use anyhow::{anyhow, Context, Result};
+use reqwest::blocking::Client;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Most users conversations.invite takes in one call.
+const INVITE_BATCH: usize = 1000;
+
+/// Per-user invite errors that aren't failures: the person is in the channel either way.
+const SOFT_INVITE_ERRORS: &[&str] = &["already_in_channel", "cant_invite_self"];
+
+#[derive(Serialize)]
+struct Member {
+    id: String,
+    display_name: Option<String>,
+    real_name: Option<String>,
+    email: Option<String>,
+}
+
+/// `members --resolve` columns; the widths are set by the first page, as later ones are
+/// printed as they arrive.
+const MEMBER_HEADER: [&str; 4] = ["ID", "NAME", "REAL NAME", "EMAIL"];
+const MEMBER_WIDTHS: [Option<usize>; 4] = [None, Some(24), Some(28), None];
+
+pub fn members(args: MembersArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let users = if args.resolve {
+        fetch_users_map(&client, &token)?
+    } else {
+        Default::default()
+    };
+    // Each page is printed as it arrives; only a JSON array has to wait for all of them.
+    let mut count = 0;
+    let mut all = Vec::new();
+    let mut widths = None;
+    each_member_page(&client, &token, &channel, |ids| {
+        count += ids.len();
+        if args.count_only {
+            return;
+        }
+        let page = ids.iter().map(|id| {
+            let user = users.get(id);
+            Member {
+                display_name: user.map(|u| u.display_name.clone()),
+                real_name: user.and_then(|u| u.real_name.clone()),
+                email: user.and_then(|u| u.email.clone()),
+                id: id.clone(),
+            }
+        });
+        match args.format {
+            ReportFormat::Json => all.extend(page),
+            ReportFormat::Text if !args.resolve => {
+                for m in page {
+                    outln!("{}", m.id);
+                }
+            }
+            ReportFormat::Text => {
+                let rows: Vec<Vec<String>> = page.map(|m| member_row(&m)).collect();
+                let widths = widths.get_or_insert_with(|| {
+                    let widths = table::widths(&MEMBER_HEADER, &rows, &MEMBER_WIDTHS);
+                    let header = MEMBER_HEADER.map(str::to_string).to_vec();
+                    out!("{}", table::render_rows(&[header], &widths));
+                    widths
+                });
+                out!("{}", table::render_rows(&rows, widths));
+            }
+        }
+    })
+    .map_err(|e| explain(e, &channel))?;
+    if args.count_only {
+        outln!("{count}");
+        return Ok(());
+    }
+    match args.format {
+        ReportFormat::Json => outln!("{}", serde_json::to_string_pretty(&all)?),
+        ReportFormat::Text if count == 0 => outln!("{} has no members", args.channel),
+        ReportFormat::Text => {}
+    }
+    Ok(())
+}
+
+/// One `members --resolve` row: ID, @display name, real name, email (`-` when unknown).
+fn member_row(m: &Member) -> Vec<String> {
+    let or_dash = |s: &Option<String>| s.clone().unwrap_or_else(|| "-".to_string());
+    let name = match &m.display_name {
+        Some(name) => format!("@{name}"),
+        None => "-".to_string(),
+    };
+    vec![m.id.clone(), name, or_dash(&m.real_name), or_dash(&m.email)]
+}
+
+pub fn invite(args: InviteArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let given: Vec<String> = args
+        .users
+        .iter()
+        .map(|u| u.trim().to_string())
+        .filter(|u| !u.is_empty())
+        .collect();
+    let ids = resolve_users(&client, &token, &given)?;
+    let mut failed_users: HashMap<String, String> = HashMap::new();
+    for batch in ids.chunks(INVITE_BATCH) {
+        let users = batch.join(",");
+        let form = [
+            ("channel", channel.as_str()),
+            ("users", users.as_str()),
+            ("force", "true"),
+        ];
+        let errors = match slack_post(&client, "conversations.invite", &token, Some(&form)) {
+            Ok(resp) => resp
+                .get("errors")
+                .and_then(|v| v.as_array())
+                .cloned()
+                .unwrap_or_default(),
+            Err(e) => match per_user_errors(&e, batch) {
+                Some(errors) => errors,
+                None => return Err(explain(e, &channel)),
+            },
+        };
+        for err in &errors {
+            let user = err.get("user").and_then(|v| v.as_str());
+            let code = err.get("error").and_then(|v| v.as_str());
+            if let (Some(user), Some(code)) = (user, code) {
+                failed_users.insert(user.to_string(), code.to_string());
+            }
+        }
+    }
+    let mut failed = 0;
+    let rows: Vec<Vec<String>> = given
+        .iter()
+        .zip(&ids)
+        .map(|(given, id)| {
+            let user = if given == id {
+                id.clone()
+            } else {
+                format!("{given} ({id})")
+            };
+            let result = match failed_users.get(id).map(String::as_str) {
+                None => "invited".to_string(),
+                Some("already_in_channel") => "already in channel".to_string(),
+                Some("cant_invite_self") => "that's you".to_string(),
+                Some(code) => {
+                    failed += 1;
+                    format!("error: {code}")
+                }
+            };
+            vec![user, result]
+        })
+        .collect();
+    out!(
+        "{}",
+        table::render(&["USER", "RESULT"], &rows, &[None, None])
+    );
+    if failed > 0 {
+        return Err(anyhow!(
+            "{failed} of {} users could not be invited",
+            ids.len()
+        ));
+    }
+    Ok(())
+}
+
+/// The `{user, error}` list behind a failed conversations.invite, when the failure was
+/// about particular users rather than the channel. With one user Slack may only give
+/// the top-level code, which is then theirs.
+fn per_user_errors(e: &anyhow::Error, batch: &[String]) -> Option<Vec<Value>> {
+    let slack = e.downcast_ref::<SlackError>()?;
+    if !slack.errors.is_empty() {
+        return Some(slack.errors.clone());
+    }
+    match batch {
+        [user] if is_user_error(&slack.code) => Some(vec![serde_json::json!({
+            "user": user,
+            "error": slack.code,
+        })]),
+        _ => None,
+    }
+}
+
+/// Codes conversations.invite gives for a user it couldn't add, as opposed to a
+/// problem with the channel or token.
+fn is_user_error(code: &str) -> bool {
+    SOFT_INVITE_ERRORS.contains(&code)
+        || matches!(
+            code,
+            "user_not_found"
+                | "cant_invite"
+                | "ura_max_channels"
+                | "user_is_restricted"
+                | "user_is_ultra_restricted"
+                | "not_allowed_for_enterprise_user"
+        )
+}
+
+pub fn kick(args: KickArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let ids = resolve_users(&client, &token, &args.users)?;
+    if !args.yes {
+        let names: Vec<String> = ids
+            .iter()
+            .map(|id| user_label(&client, &token, id))
+            .collect();
+        let info = slack_post(
+            &client,
+            "conversations.info",
+            &token,
+            Some(&[("channel", channel.as_str())]),
+        )
+        .map_err(|e| explain(e, &channel))?;
+        let place = match info.pointer("/channel/name").and_then(|v| v.as_str()) {
+            Some(name) => format!("#{name} ({channel})"),
+            None => channel.clone(),
+        };
+        if output::is_capturing() {
+            return Err(anyhow!("no terminal to confirm on; pass --yes"));
+        }
+        let go = dialoguer::Confirm::new()
+            .with_prompt(format!("Remove {} from {place}?", names.join(", ")))
+            .default(false)
+            .interact()
+            .context("failed to read confirmation")?;
+        if !go {
+            return Err(anyhow!("cancelled; nobody removed"));
+        }
+    }
+    let mut failed = 0;
+    let mut rows = Vec::new();
+    for (given, id) in args.users.iter().zip(&ids) {
+        let form = [("channel", channel.as_str()), ("user", id.as_str())];
+        let result = match slack_post(&client, "conversations.kick", &token, Some(&form)) {
+            Ok(_) => "removed".to_string(),
+            Err(e) => {
+                match SlackError::code_of(&e) {
+                    Some("not_in_channel") => "error: isn't in the channel".to_string(),
+                    Some("cant_kick_self") => {
+                        "error: that's you; use `slack leave` instead".to_string()
+                    }
+                    Some("user_not_found") => "error: no such user".to_string(),
+                    // Anything else is about the channel or the token, so it would
+                    // fail the same way for everyone left.
+                    _ => return Err(explain(e, &channel)),
+                }
+            }
+        };
+        if result.starts_with("error") {
+            failed += 1;
+        }
+        let user = if given == id {
+            id.clone()
+        } else {
+            format!("{given} ({id})")
+        };
+        rows.push(vec![user, result]);
+    }
+    out!(
+        "{}",
+        table::render(&["USER", "RESULT"], &rows, &[None, None])
+    );
+    if failed > 0 {
+        return Err(anyhow!(
+            "{failed} of {} users could not be removed",
+            ids.len()
+        ));
+    }
+    Ok(())
+}
+
+pub fn leave(args: LeaveArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let mut failed = 0;
+    for given in &args.channels {
+        match leave_one(&client, &token, given) {
+            Ok(line) => outln!("{line}"),
+            Err(e) => {
+                failed += 1;
+                errln!("{given}: {e:#}");
+            }
+        }
+    }
+    if failed > 0 {
+        return Err(anyhow!(
+            "{failed} of {} channels could not be left",
+            args.channels.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Leave one channel; `left #name (C123)`, or `not in …` when you weren't a member.
+fn leave_one(client: &Client, token: &str, given: &str) -> Result<String> {
+    let channel = resolve_channel(client, token, given)?;
+    let info = slack_post(
+        client,
+        "conversations.info",
+        token,
+        Some(&[("channel", channel.as_str())]),
+    )
+    .map_err(|e| explain(e, &channel))?;
+    let label = match info.pointer("/channel/name").and_then(|v| v.as_str()) {
+        Some(name) => format!("#{name} ({channel})"),
+        None => channel.clone(),
+    };
+    let resp = match slack_post(
+        client,
+        "conversations.leave",
+        token,
+        Some(&[("channel", channel.as_str())]),
+    ) {
+        Ok(resp) => resp,
+        Err(e) if SlackError::code_of(&e) == Some("not_in_channel") => {
+            return Ok(format!("not in {label}"));
+        }
+        Err(e) => return Err(explain(e, &label)),
+    };
+    // Slack answers ok for a channel you weren't in, with a flag saying so.
+    if resp.get("not_in_channel").and_then(|v| v.as_bool()) == Some(true) {
+        return Ok(format!("not in {label}"));
+    }
+    Ok(format!("left {label}"))
+}
+
+pub fn close(args: CloseArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let mut failed = 0;
+    for given in &args.channels {
+        match close_one(&client, &token, given) {
+            Ok(line) => outln!("{line}"),
+            Err(e) => {
+                failed += 1;
+                errln!("{given}: {e:#}");
+            }
+        }
+    }
+    if failed > 0 {
+        return Err(anyhow!(
+            "{failed} of {} conversations could not be closed",
+            args.channels.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Close one DM or group DM; `closed D123`, or `already closed D123` when Slack
+/// answers with `no_op`.
+fn close_one(client: &Client, token: &str, given: &str) -> Result<String> {
+    let channel = resolve_channel(client, token, given)?;
+    let resp = slack_post(
+        client,
+        "conversations.close",
+        token,
+        Some(&[("channel", channel.as_str())]),
+    )
+    .map_err(|e| explain(e, &channel))?;
+    let flag = |k: &str| resp.get(k).and_then(|v| v.as_bool()) == Some(true);
+    if flag("no_op") || flag("already_closed") {
+        return Ok(format!("already closed {channel}"));
+    }
+    Ok(format!("closed {channel}"))
+}
+
+/// `@name (U123)` from users.info, or just the ID if the lookup fails; for display
+/// only.
+pub(crate) fn user_label(client: &Client, token: &str, id: &str) -> String {
+    slack_post(client, "users.info", token, Some(&[("user", id)]))
+        .ok()
+        .and_then(|resp| resp.get("user").and_then(user_info))
+        .map(|(_, info)| format!("@{} ({id})", info.display_name))
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// conversations.members for `channel`: every page of member IDs, each handed to
+/// `on_page` as soon as it is parsed.
+fn each_member_page(
+    client: &Client,
+    token: &str,
+    channel: &str,
+    mut on_page: impl FnMut(&[String]),
+) -> Result<()> {
+    let form = [("channel", channel), ("limit", "1000")];
+    each_page(
+        client,
+        token,
+        "conversations.members",
+        &form,
+        "members",
+        |page| {
+            let ids: Vec<String> = page
+                .iter()
+                .filter_map(|v| v.as_str())
+                .map(str::to_string)
+                .collect();
+            on_page(&ids);
+            true
+        },
+    )
+}
+
+fn explain(e: anyhow::Error, channel: &str) -> anyhow::Error {
+    match SlackError::code_of(&e) {
+        Some("channel_not_found") => e.context(format!(
+            "no channel {channel} visible to you; private channels need you to be a member"
+        )),
+        Some("not_in_channel") => e.context(format!(
+            "you're not in {channel}; join it first (`slack join --channel {channel}`)"
+        )),
+        Some("is_archived") => e.context(format!("{channel} is archived")),
+        Some("cant_leave_general") => {
+            e.context("nobody can leave the workspace's #general; it's where everyone is")
+        }
+        Some("cant_kick_from_general") => {
+            e.context("nobody can be removed from the workspace's #general")
+        }
+        Some("method_not_supported_for_channel_type") => e.context(format!(
+            "{channel} isn't a DM; use `slack leave` for channels"
+        )),
+        Some("restricted_action") => e.context(
+            "your workspace only lets admins (or channel managers) remove people; ask one",
+        ),
+        _ => e,
+    }
+}
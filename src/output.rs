@@ -0,0 +1,76 @@
+//! Command output that can be captured instead of written to the terminal.
+//!
+//! Handlers print through `outln!`/`errln!` (and `out!`); normally that is plain
+//! stdout/stderr, but the daemon captures both per request and sends them back.
+
+use std::cell::RefCell;
+use std::io::Write;
+
+thread_local! {
+    static CAPTURE: RefCell<Option<(String, String)>> = const { RefCell::new(None) };
+}
+
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::output::write_out(&format!($($arg)*))
+    };
+}
+
+macro_rules! outln {
+    () => {
+        $crate::output::write_out("\n")
+    };
+    ($($arg:tt)*) => {
+        $crate::output::write_out(&format!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+macro_rules! errln {
+    ($($arg:tt)*) => {
+        $crate::output::write_err(&format!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+pub(crate) use {errln, out, outln};
+
+pub fn write_out(s: &str) {
+    let captured = CAPTURE.with(|c| match c.borrow_mut().as_mut() {
+        Some((out, _)) => {
+            out.push_str(s);
+            true
+        }
+        None => false,
+    });
+    if !captured {
+        let mut stdout = std::io::stdout().lock();
+        let _ = stdout.write_all(s.as_bytes());
+        let _ = stdout.flush();
+    }
+}
+
+pub fn write_err(s: &str) {
+    let captured = CAPTURE.with(|c| match c.borrow_mut().as_mut() {
+        Some((_, err)) => {
+            err.push_str(s);
+            true
+        }
+        None => false,
+    });
+    if !captured {
+        let _ = std::io::stderr().write_all(s.as_bytes());
+    }
+}
+
+/// True while output is being captured (e.g. inside the daemon), where there is no
+/// terminal to prompt on.
+pub fn is_capturing() -> bool {
+    CAPTURE.with(|c| c.borrow().is_some())
+}
+
+/// Run `f` with output captured; returns its result plus (stdout, stderr).
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, String, String) {
+    CAPTURE.with(|c| *c.borrow_mut() = Some((String::new(), String::new())));
+    let result = f();
+    let (out, err) = CAPTURE.with(|c| c.borrow_mut().take()).unwrap_or_default();
+    (result, out, err)
+}
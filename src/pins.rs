@@ -0,0 +1,114 @@
+//! `slack pin`, `pins` and `unpin`: a channel's pinned messages.
+
+use crate::output::outln;
+use crate::remindme::excerpt;
+use crate::{
+    ensure_token, fetch_users_map, http, resolve_channel, resolve_ts, slack_post, PinArgs,
+    PinsArgs, SlackError, UserInfo,
+};
+use anyhow::Result;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Characters of each pinned message shown by `pins`.
+const PREVIEW_CHARS: usize = 80;
+
+pub fn pin(args: PinArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let ts = resolve_ts(&client, &token, &channel, args.ts.as_deref(), args.last)?;
+    let form = [("channel", channel.as_str()), ("timestamp", ts.as_str())];
+    match slack_post(&client, "pins.add", &token, Some(&form)) {
+        Ok(_) => outln!("pinned {ts}"),
+        Err(e) => match SlackError::code_of(&e) {
+            Some("already_pinned") if !args.strict => outln!("{ts} was already pinned"),
+            _ => return Err(e),
+        },
+    }
+    Ok(())
+}
+
+pub fn unpin(args: PinArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let ts = resolve_ts(&client, &token, &channel, args.ts.as_deref(), args.last)?;
+    let form = [("channel", channel.as_str()), ("timestamp", ts.as_str())];
+    match slack_post(&client, "pins.remove", &token, Some(&form)) {
+        Ok(_) => outln!("unpinned {ts}"),
+        Err(e) => match SlackError::code_of(&e) {
+            Some("no_pin") if !args.strict => outln!("{ts} was not pinned"),
+            _ => return Err(e),
+        },
+    }
+    Ok(())
+}
+
+pub fn list(args: PinsArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let resp = slack_post(
+        &client,
+        "pins.list",
+        &token,
+        Some(&[("channel", channel.as_str())]),
+    )?;
+    // Only messages are listed; pinned files are a thing of the past.
+    let messages: Vec<&Value> = resp
+        .get("items")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|item| item.get("message"))
+        .collect();
+    if messages.is_empty() {
+        outln!("no pins");
+        return Ok(());
+    }
+    let users = fetch_users_map(&client, &token)?;
+    for m in messages {
+        outln!("{}", pin_line(m, &users));
+    }
+    Ok(())
+}
+
+/// `ts  @author  preview` for one pinned message.
+fn pin_line(m: &Value, users: &HashMap<String, UserInfo>) -> String {
+    let ts = m.get("ts").and_then(|v| v.as_str()).unwrap_or("-");
+    let author = match m.get("user").and_then(|v| v.as_str()) {
+        Some(id) => match users.get(id) {
+            Some(info) => format!("@{}", info.display_name),
+            None => id.to_string(),
+        },
+        None => m
+            .get("bot_id")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown")
+            .to_string(),
+    };
+    format!("{ts}\t{author}\t{}", excerpt(m, PREVIEW_CHARS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn pin_line_names_the_author() {
+        let mut users = HashMap::new();
+        users.insert(
+            "U1".to_string(),
+            UserInfo {
+                display_name: "jane".into(),
+                ..Default::default()
+            },
+        );
+        let m = json!({ "ts": "1.0", "user": "U1", "text": "Runbook:\nstep one" });
+        assert_eq!(pin_line(&m, &users), "1.0\t@jane\tRunbook:…");
+        let bot = json!({ "ts": "2.0", "bot_id": "B1", "text": "deploys here" });
+        assert_eq!(pin_line(&bot, &users), "2.0\tB1\tdeploys here");
+    }
+}
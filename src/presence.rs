@@ -0,0 +1,155 @@
+//! `slack presence`: whether someone is active or away, and setting your own.
+
+use crate::export_html::day_and_time;
+use crate::output::{errln, out, outln};
+use crate::usergroups::members;
+use crate::{
+    auth_test, ensure_token, fetch_users_map, http, parallel_map, slack_get, slack_post,
+    slack_post_retrying_with, table, PresenceArgs, PresenceBatchArgs, PresenceCommand,
+    PresenceGetArgs,
+};
+use anyhow::{anyhow, Result};
+use reqwest::blocking::Client;
+use serde_json::Value;
+
+/// users.getPresence calls in flight at once for `presence-batch`; it's a Tier 3 method,
+/// so a few at a time stays clear of the limit for a team-sized list.
+const BATCH_WORKERS: usize = 4;
+
+pub fn run(args: PresenceArgs) -> Result<()> {
+    match args.command {
+        PresenceCommand::Get(args) => get(args),
+        PresenceCommand::Set(args) => {
+            let token = ensure_token()?;
+            let presence = args.state.as_str();
+            slack_post(
+                &http(),
+                "users.setPresence",
+                &token,
+                Some(&[("presence", presence)]),
+            )?;
+            outln!("presence set to {presence}");
+            Ok(())
+        }
+    }
+}
+
+fn get(args: PresenceGetArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let user = match args.user {
+        Some(user) => user,
+        None => auth_test(&client, &token)?
+            .user_id
+            .ok_or_else(|| anyhow!("auth.test did not say who you are; pass --user"))?,
+    };
+    let resp = slack_get(&client, "users.getPresence", &token, &[("user", &user)])?;
+    outln!("{user}\t{}", describe(&resp));
+    Ok(())
+}
+
+pub fn batch(args: PresenceBatchArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let users = match args.usergroup.as_deref() {
+        Some(group) => members(&client, &token, group)?,
+        None => args.users.clone(),
+    };
+    if users.is_empty() {
+        outln!("no users");
+        return Ok(());
+    }
+    let names = match fetch_users_map(&client, &token) {
+        Ok(map) => map,
+        Err(e) => {
+            errln!("warning: could not look up names, showing IDs: {e:#}");
+            Default::default()
+        }
+    };
+    let results = presences(&client, &token, &users);
+    let mut failed = 0;
+    let rows: Vec<Vec<String>> = users
+        .iter()
+        .zip(&results)
+        .map(|(user, result)| {
+            let name = names
+                .get(user)
+                .map(|u| u.display_name.clone())
+                .unwrap_or_else(|| user.clone());
+            let (presence, last) = match result {
+                Ok(resp) => (describe(resp), last_activity(resp)),
+                Err(e) => {
+                    failed += 1;
+                    (format!("error: {e:#}"), "-".to_string())
+                }
+            };
+            vec![name, presence, last]
+        })
+        .collect();
+    out!(
+        "{}",
+        table::render(
+            &["NAME", "PRESENCE", "LAST ACTIVE (UTC)"],
+            &rows,
+            &[Some(32), None, None]
+        )
+    );
+    if failed > 0 {
+        return Err(anyhow!(
+            "{failed} of {} users could not be checked",
+            users.len()
+        ));
+    }
+    Ok(())
+}
+
+/// users.getPresence for each of `users`, `BATCH_WORKERS` at a time, in the same order.
+/// Each call backs off on its own when rate limited.
+fn presences(client: &Client, token: &str, users: &[String]) -> Vec<Result<Value>> {
+    parallel_map(users, BATCH_WORKERS, |user, report| {
+        let form = [("user", user.as_str())];
+        slack_post_retrying_with(client, token, "users.getPresence", &form, report)
+    })
+}
+
+/// When the user was last active, where Slack says (it mostly does only for you).
+fn last_activity(resp: &Value) -> String {
+    match resp.get("last_activity").and_then(|v| v.as_i64()) {
+        Some(t) if t > 0 => {
+            let (day, time) = day_and_time(t as f64);
+            format!("{day} {time}")
+        }
+        _ => "-".to_string(),
+    }
+}
+
+/// `active`, `away (set manually)` or `away (idle)`; Slack only says which for your own
+/// presence, so for others it's just active or away.
+fn describe(resp: &Value) -> String {
+    let presence = resp
+        .get("presence")
+        .and_then(|v| v.as_str())
+        .unwrap_or("unknown");
+    let flag = |k: &str| resp.get(k).and_then(|v| v.as_bool());
+    match (presence, flag("manual_away"), flag("auto_away")) {
+        ("away", Some(true), _) => "away (set manually)".to_string(),
+        ("away", _, Some(true)) => "away (idle)".to_string(),
+        _ => presence.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn describe_says_why_you_are_away() {
+        let manual = json!({ "presence": "away", "manual_away": true, "auto_away": false });
+        assert_eq!(describe(&manual), "away (set manually)");
+        let idle = json!({ "presence": "away", "manual_away": false, "auto_away": true });
+        assert_eq!(describe(&idle), "away (idle)");
+        assert_eq!(describe(&json!({ "presence": "away" })), "away");
+        assert_eq!(describe(&json!({ "presence": "active" })), "active");
+    }
+}
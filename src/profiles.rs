@@ -0,0 +1,184 @@
+//! `slack profile`: the named token profiles that `init --profile` has saved. The one
+//! exception, `profile set`, edits the Slack user profile and lives in `user_profile`.
+//!
+//! Keyrings can't be enumerated portably, so the names are recorded in `profiles.json`
+//! in the config dir; the tokens themselves stay in the token store.
+
+use crate::output::outln;
+use crate::token_store::{self, DEFAULT_PROFILE};
+use crate::{auth_test, config_dir, http, invocation, ProfileArgs, ProfileCommand, TokenType};
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Pause between auth.test calls when checking several profiles.
+const CHECK_PACING: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Registry {
+    profiles: Vec<String>,
+}
+
+fn registry_path() -> Option<PathBuf> {
+    config_dir().map(|d| d.join("profiles.json"))
+}
+
+/// Known profile names, sorted.
+pub fn list() -> Result<Vec<String>> {
+    let Some(path) = registry_path() else {
+        return Ok(Vec::new());
+    };
+    match std::fs::read(&path) {
+        Ok(data) => Ok(serde_json::from_slice::<Registry>(&data)
+            .with_context(|| format!("invalid {}", path.display()))?
+            .profiles),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e).with_context(|| format!("failed to read {}", path.display())),
+    }
+}
+
+fn save(mut profiles: Vec<String>) -> Result<()> {
+    let path = registry_path().ok_or_else(|| anyhow!("no config directory for profiles"))?;
+    profiles.sort();
+    profiles.dedup();
+    crate::write_atomic(&path, &serde_json::to_vec_pretty(&Registry { profiles })?)
+}
+
+/// Record that `profile` has a stored token.
+pub fn register(profile: &str) -> Result<()> {
+    let mut profiles = list()?;
+    if profiles.iter().any(|p| p == profile) {
+        return Ok(());
+    }
+    profiles.push(profile.to_string());
+    save(profiles)
+}
+
+pub fn unregister(profile: &str) -> Result<()> {
+    let mut profiles = list()?;
+    profiles.retain(|p| p != profile);
+    save(profiles)
+}
+
+pub fn run(args: ProfileArgs) -> Result<()> {
+    match args.command {
+        ProfileCommand::List => {
+            let current = invocation().profile;
+            for p in list()? {
+                let mark = if p == current { "*" } else { " " };
+                outln!("{mark} {p}");
+            }
+            Ok(())
+        }
+        ProfileCommand::Check => check(),
+        ProfileCommand::Set(args) => crate::user_profile::set(args),
+    }
+}
+
+/// Outcome of validating one profile's stored token.
+#[derive(Debug, PartialEq)]
+enum Status {
+    Valid,
+    /// Slack rejected the token; the code says how (revoked, expired, …).
+    Rejected(String),
+    Missing,
+    /// The token store couldn't be read (e.g. wrong passphrase).
+    Unreadable(String),
+    /// auth.test didn't get an answer; says nothing about the token.
+    Network(String),
+}
+
+impl Status {
+    fn label(&self) -> String {
+        match self {
+            Status::Valid => "valid".into(),
+            Status::Rejected(code) if code == "token_expired" => "expired".into(),
+            Status::Rejected(code) if code == "token_revoked" => "revoked".into(),
+            Status::Rejected(code) => format!("invalid ({code})"),
+            Status::Missing => "missing (no stored token)".into(),
+            Status::Unreadable(e) => format!("unreadable ({e})"),
+            Status::Network(e) => format!("network error ({e})"),
+        }
+    }
+
+    /// Whether re-running `init` for the profile is the fix.
+    fn needs_init(&self) -> bool {
+        matches!(
+            self,
+            Status::Rejected(_) | Status::Missing | Status::Unreadable(_)
+        )
+    }
+}
+
+fn check() -> Result<()> {
+    let mut profiles = list()?;
+    if profiles.is_empty() {
+        // Tokens saved before profiles existed live under the default profile.
+        profiles.push(DEFAULT_PROFILE.to_string());
+    }
+    let store = invocation().store;
+    let client = http();
+    let mut broken = Vec::new();
+    for (i, name) in profiles.iter().enumerate() {
+        if i > 0 {
+            std::thread::sleep(CHECK_PACING);
+        }
+        let (mut team, mut user, mut kind) = ("-".to_string(), "-".to_string(), "-");
+        let status = match token_store::open(store, name).and_then(|s| s.read()) {
+            Err(e) => Status::Unreadable(format!("{e:#}")),
+            Ok(None) => Status::Missing,
+            Ok(Some(token)) => {
+                kind = TokenType::detect(&token).describe();
+                match auth_test(&client, &token) {
+                    Err(e) => Status::Network(format!("{e:#}")),
+                    Ok(info) if info.ok => {
+                        team = info.team.unwrap_or_else(|| "-".into());
+                        user = info.user_id.unwrap_or_else(|| "-".into());
+                        Status::Valid
+                    }
+                    Ok(info) => {
+                        Status::Rejected(info.error.unwrap_or_else(|| "invalid_auth".into()))
+                    }
+                }
+            }
+        };
+        outln!("{name}\t{team}\t{user}\t{kind}\t{}", status.label());
+        if status != Status::Valid {
+            broken.push((name, status));
+        }
+    }
+    if broken.is_empty() {
+        return Ok(());
+    }
+    for (name, status) in &broken {
+        if status.needs_init() {
+            outln!("fix {name}: slack init --profile {name} --force");
+        }
+    }
+    Err(anyhow!(
+        "{} of {} profile(s) failed the check",
+        broken.len(),
+        profiles.len()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_labels_separate_auth_from_network_failures() {
+        assert_eq!(Status::Rejected("token_revoked".into()).label(), "revoked");
+        assert_eq!(Status::Rejected("token_expired".into()).label(), "expired");
+        assert_eq!(
+            Status::Rejected("invalid_auth".into()).label(),
+            "invalid (invalid_auth)"
+        );
+        assert!(Status::Rejected("invalid_auth".into()).needs_init());
+        assert!(!Status::Network("timeout".into()).needs_init());
+        assert!(Status::Network("timeout".into())
+            .label()
+            .starts_with("network error"));
+    }
+}
@@ -0,0 +1,191 @@
+//! `slack react`, `unreact` and `reactions-get`: emoji reactions on one message.
+
+use crate::output::outln;
+use crate::{
+    ensure_token, fetch_users_map, http, resolve_channel, resolve_ts, slack_post, ReactArgs,
+    ReactionsGetArgs, ReportFormat, SlackError, UnreactArgs, UserInfo,
+};
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+pub fn react(args: ReactArgs) -> Result<()> {
+    let name = normalize_emoji(&args.emoji)?;
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let ts = resolve_ts(&client, &token, &channel, args.ts.as_deref(), args.last)?;
+    let form = [
+        ("channel", channel.as_str()),
+        ("timestamp", ts.as_str()),
+        ("name", name.as_str()),
+    ];
+    match slack_post(&client, "reactions.add", &token, Some(&form)) {
+        Ok(_) => outln!("reacted :{name}: to {ts}"),
+        Err(e) => match SlackError::code_of(&e) {
+            Some("already_reacted") => outln!("already reacted :{name}: to {ts}"),
+            Some("invalid_name") => return Err(e.context(unknown_emoji(&name))),
+            _ => return Err(e),
+        },
+    }
+    Ok(())
+}
+
+pub fn unreact(args: UnreactArgs) -> Result<()> {
+    let name = normalize_emoji(&args.emoji)?;
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let form = [
+        ("channel", channel.as_str()),
+        ("timestamp", args.ts.as_str()),
+        ("name", name.as_str()),
+    ];
+    match slack_post(&client, "reactions.remove", &token, Some(&form)) {
+        Ok(_) => outln!("removed :{name}: from {}", args.ts),
+        Err(e) => match SlackError::code_of(&e) {
+            Some("no_reaction") if !args.strict => outln!("you had no such reaction (:{name}:)"),
+            Some("no_reaction") => {
+                return Err(e.context(format!("you had no :{name}: reaction on {}", args.ts)))
+            }
+            Some("invalid_name") => return Err(e.context(unknown_emoji(&name))),
+            _ => return Err(e),
+        },
+    }
+    Ok(())
+}
+
+fn unknown_emoji(name: &str) -> String {
+    format!("`{name}` is not an emoji in this workspace (use its short name, e.g. thumbsup)")
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct Reaction {
+    name: String,
+    count: u64,
+    users: Vec<Reactor>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct Reactor {
+    id: String,
+    name: String,
+}
+
+pub fn get(args: ReactionsGetArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let resp = slack_post(
+        &client,
+        "reactions.get",
+        &token,
+        Some(&[
+            ("channel", channel.as_str()),
+            ("timestamp", args.ts.as_str()),
+            ("full", "true"),
+        ]),
+    )?;
+    // Only look names up when there is someone to name.
+    let item = resp.get("message").or_else(|| resp.get("file"));
+    let has_reactions = item
+        .and_then(|m| m.get("reactions"))
+        .and_then(|v| v.as_array())
+        .is_some_and(|r| !r.is_empty());
+    let users = if has_reactions {
+        fetch_users_map(&client, &token)?
+    } else {
+        HashMap::new()
+    };
+    let reactions = item.map(|m| collect(m, &users)).unwrap_or_default();
+    match args.format {
+        ReportFormat::Json => outln!("{}", serde_json::to_string_pretty(&reactions)?),
+        ReportFormat::Text if reactions.is_empty() => outln!("no reactions"),
+        ReportFormat::Text => {
+            for r in &reactions {
+                let names: Vec<&str> = r.users.iter().map(|u| u.name.as_str()).collect();
+                outln!(":{}:\t{}\t{}", r.name, r.count, names.join(" "));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The message's reactions in Slack's order, with user IDs resolved to `@display_name`.
+fn collect(message: &Value, users: &HashMap<String, UserInfo>) -> Vec<Reaction> {
+    message
+        .get("reactions")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .map(|r| Reaction {
+            name: r
+                .get("name")
+                .and_then(|v| v.as_str())
+                .unwrap_or("?")
+                .to_string(),
+            count: r.get("count").and_then(|v| v.as_u64()).unwrap_or(0),
+            users: r
+                .get("users")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|u| u.as_str())
+                .map(|id| Reactor {
+                    id: id.to_string(),
+                    name: match users.get(id) {
+                        Some(info) => format!("@{}", info.display_name),
+                        None => id.to_string(),
+                    },
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// `:thumbsup:` → `thumbsup`; skin tones (`+1::skin-tone-2`) are kept as Slack expects.
+pub(crate) fn normalize_emoji(emoji: &str) -> Result<String> {
+    let name = emoji.trim().trim_start_matches(':').trim_end_matches(':');
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return Err(anyhow!("`{emoji}` is not an emoji name"));
+    }
+    Ok(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn colons_are_stripped() {
+        assert_eq!(normalize_emoji(":thumbsup:").unwrap(), "thumbsup");
+        assert_eq!(normalize_emoji(" eyes ").unwrap(), "eyes");
+        assert_eq!(
+            normalize_emoji(":+1::skin-tone-2:").unwrap(),
+            "+1::skin-tone-2"
+        );
+        assert!(normalize_emoji("::").is_err());
+        assert!(normalize_emoji("thumbs up").is_err());
+    }
+
+    #[test]
+    fn reactors_are_named_when_known() {
+        let mut users = HashMap::new();
+        users.insert(
+            "U1".to_string(),
+            UserInfo {
+                display_name: "jane".into(),
+                ..Default::default()
+            },
+        );
+        let m = json!({ "reactions": [{ "name": "tada", "count": 2, "users": ["U1", "U9"] }] });
+        let got = collect(&m, &users);
+        assert_eq!(got.len(), 1);
+        assert_eq!(got[0].count, 2);
+        let names: Vec<&str> = got[0].users.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, ["@jane", "U9"]);
+        assert!(collect(&json!({}), &users).is_empty());
+    }
+}
@@ -0,0 +1,214 @@
+//! `slack mark` and `unreads`: where your read markers sit, and what's past them.
+
+use crate::drafts::preview;
+use crate::output::{errln, out, outln};
+use crate::{
+    conversation_type, each_member_conversation_page, ensure_token, fetch_users_map, http,
+    parallel_map, recent_messages, resolve_channel, slack_post, slack_post_retrying_with, table,
+    MarkArgs, SlackError, UnreadsArgs,
+};
+use anyhow::{anyhow, Result};
+use reqwest::blocking::Client;
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// conversations.info calls in flight at once for `unreads`; it's a Tier 3 method, like
+/// users.getPresence for `presence-batch`.
+const UNREAD_WORKERS: usize = 4;
+
+/// Characters of the newest message shown by `unreads`.
+const PREVIEW_CHARS: usize = 60;
+
+pub fn mark(args: MarkArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let ts = match args.ts {
+        Some(ts) => ts,
+        None => recent_messages(&client, &token, &channel, 1)
+            .map_err(|e| explain(e, &channel))?
+            .last()
+            .and_then(|m| m.get("ts"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| anyhow!("{channel} has no messages to mark"))?,
+    };
+    slack_post(
+        &client,
+        "conversations.mark",
+        &token,
+        Some(&[("channel", channel.as_str()), ("ts", ts.as_str())]),
+    )
+    .map_err(|e| explain(e, &channel))?;
+    outln!("marked {channel} read up to {ts}");
+    Ok(())
+}
+
+fn explain(e: anyhow::Error, channel: &str) -> anyhow::Error {
+    match SlackError::code_of(&e) {
+        Some("not_in_channel") => e.context(format!(
+            "you're not in {channel}, so it has no read marker; `slack join` first"
+        )),
+        Some("invalid_timestamp") => {
+            e.context("--ts must be a message timestamp like 1712345678.000100")
+        }
+        _ => e,
+    }
+}
+
+/// One conversation with something unread.
+struct Unread {
+    name: String,
+    count: u64,
+    latest: String,
+}
+
+pub fn unreads(args: UnreadsArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let types = if args.include_dms {
+        "public_channel,private_channel,mpim,im"
+    } else {
+        "public_channel,private_channel"
+    };
+    let mut candidates = Vec::new();
+    each_member_conversation_page(&client, &token, types, true, |page| {
+        candidates.extend(page.iter().cloned())
+    })?;
+    let muted = match muted_channels(&client, &token) {
+        Ok(muted) => muted,
+        Err(e) => {
+            errln!("warning: could not read muted channels, including them: {e:#}");
+            Default::default()
+        }
+    };
+    candidates.retain(|ch| {
+        let id = ch.get("id").and_then(|v| v.as_str()).unwrap_or("");
+        !id.is_empty() && !muted.contains(id)
+    });
+    let names = if candidates.iter().any(|ch| conversation_type(ch) == "im") {
+        match fetch_users_map(&client, &token) {
+            Ok(map) => map,
+            Err(e) => {
+                errln!("warning: could not look up names, showing IDs: {e:#}");
+                Default::default()
+            }
+        }
+    } else {
+        Default::default()
+    };
+    let results = unread_states(&client, &token, &candidates);
+    let mut failed = 0;
+    let mut unread = Vec::new();
+    for (ch, result) in candidates.iter().zip(results) {
+        let id = ch.get("id").and_then(|v| v.as_str()).unwrap_or("-");
+        let info = match result {
+            Ok(info) => info,
+            Err(e) => {
+                failed += 1;
+                errln!("{id}: {e:#}");
+                continue;
+            }
+        };
+        let count = info
+            .get("unread_count_display")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        if count == 0 || info.get("is_muted").and_then(|v| v.as_bool()) == Some(true) {
+            continue;
+        }
+        let name = match ch.get("user").and_then(|v| v.as_str()) {
+            Some(user) if conversation_type(ch) == "im" => match names.get(user) {
+                Some(u) => format!("@{}", u.display_name),
+                None => user.to_string(),
+            },
+            _ => match ch.get("name").and_then(|v| v.as_str()) {
+                Some(name) => format!("#{name}"),
+                None => id.to_string(),
+            },
+        };
+        let latest = info
+            .pointer("/latest/text")
+            .and_then(|v| v.as_str())
+            .map(|text| preview(text, PREVIEW_CHARS))
+            .unwrap_or_else(|| "-".to_string());
+        unread.push(Unread {
+            name,
+            count,
+            latest,
+        });
+    }
+    // Stable, so ties keep users.conversations' order.
+    unread.sort_by_key(|u| std::cmp::Reverse(u.count));
+    if unread.is_empty() {
+        outln!("nothing unread");
+    } else {
+        let rows: Vec<Vec<String>> = unread
+            .into_iter()
+            .map(|u| vec![u.name, u.count.to_string(), u.latest])
+            .collect();
+        out!(
+            "{}",
+            table::render(
+                &["CONVERSATION", "UNREAD", "LATEST"],
+                &rows,
+                &[Some(32), None, None]
+            )
+        );
+    }
+    if failed > 0 {
+        return Err(anyhow!(
+            "{failed} of {} conversations could not be checked",
+            candidates.len()
+        ));
+    }
+    Ok(())
+}
+
+/// IDs of the channels you've muted, from users.prefs.get.
+fn muted_channels(client: &Client, token: &str) -> Result<HashSet<String>> {
+    let resp = slack_post(client, "users.prefs.get", token, None)?;
+    Ok(resp
+        .pointer("/prefs/muted_channels")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// conversations.info's `channel` for each of `conversations`, `UNREAD_WORKERS` at a
+/// time, in the same order. Where there's something unread and Slack left out `latest`
+/// (it mostly only sends it for DMs), the newest message is fetched for the preview.
+fn unread_states(client: &Client, token: &str, conversations: &[Value]) -> Vec<Result<Value>> {
+    parallel_map(conversations, UNREAD_WORKERS, |ch, report| {
+        let id = ch.get("id").and_then(|v| v.as_str()).unwrap_or("");
+        unread_state(client, token, id, report)
+    })
+}
+
+fn unread_state(
+    client: &Client,
+    token: &str,
+    channel: &str,
+    report: &dyn Fn(String),
+) -> Result<Value> {
+    let form = [("channel", channel)];
+    let resp = slack_post_retrying_with(client, token, "conversations.info", &form, report)?;
+    let mut info = resp.get("channel").cloned().unwrap_or_default();
+    let count = info
+        .get("unread_count_display")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+    if count > 0 && info.pointer("/latest/text").is_none() {
+        let form = [("channel", channel), ("limit", "1")];
+        let history =
+            slack_post_retrying_with(client, token, "conversations.history", &form, report)?;
+        if let Some(latest) = history.pointer("/messages/0") {
+            info["latest"] = latest.clone();
+        }
+    }
+    Ok(info)
+}
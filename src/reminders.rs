@@ -0,0 +1,180 @@
+//! `slack remind`, `reminders`, `remind-complete` and `remind-delete`: the reminders API.
+
+use crate::export_html::day_and_time;
+use crate::output::{errln, outln};
+use crate::{
+    auth_test, ensure_token, http, slack_post, ReminderAddArgs, ReminderIdArgs, SlackError,
+};
+use anyhow::Result;
+use reqwest::blocking::Client;
+use serde_json::Value;
+
+pub fn add(args: ReminderAddArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let form = [("text", args.text.as_str()), ("time", args.time.as_str())];
+    let resp = slack_post(&client, "reminders.add", &token, Some(&form))
+        .map_err(|e| explain_time(e, &args.time))?;
+    let reminder = resp.get("reminder").cloned().unwrap_or_default();
+    let id = reminder.get("id").and_then(|v| v.as_str()).unwrap_or("-");
+    match reminder.get("time").and_then(|v| v.as_i64()) {
+        Some(due) => {
+            let offset = tz_offset(&client, &token);
+            outln!("reminder {id} due {}", local_time(due, offset));
+        }
+        None => outln!("reminder {id} created"),
+    }
+    Ok(())
+}
+
+pub fn list() -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let resp = slack_post(&client, "reminders.list", &token, None).map_err(explain)?;
+    let reminders = resp
+        .get("reminders")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    if reminders.is_empty() {
+        outln!("no reminders");
+        return Ok(());
+    }
+    let offset = tz_offset(&client, &token);
+    for r in &reminders {
+        outln!("{}", reminder_line(r, offset));
+    }
+    Ok(())
+}
+
+pub fn complete(args: ReminderIdArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    slack_post(
+        &client,
+        "reminders.complete",
+        &token,
+        Some(&[("reminder", args.id.as_str())]),
+    )
+    .map_err(explain)?;
+    outln!("completed reminder {}", args.id);
+    Ok(())
+}
+
+pub fn delete(args: ReminderIdArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    slack_post(
+        &client,
+        "reminders.delete",
+        &token,
+        Some(&[("reminder", args.id.as_str())]),
+    )
+    .map_err(explain)?;
+    outln!("deleted reminder {}", args.id);
+    Ok(())
+}
+
+/// Seconds east of UTC for the token's user, from their Slack profile; Slack reads
+/// reminder and status times in that zone too.
+pub(crate) fn profile_tz_offset(client: &Client, token: &str) -> Result<i64> {
+    let user = auth_test(client, token)?.user_id.unwrap_or_default();
+    let resp = slack_post(
+        client,
+        "users.info",
+        token,
+        Some(&[("user", user.as_str())]),
+    )?;
+    Ok(resp
+        .pointer("/user/tz_offset")
+        .and_then(|v| v.as_i64())
+        .unwrap_or(0))
+}
+
+/// `profile_tz_offset`, falling back to UTC with a warning; for display only.
+pub(crate) fn tz_offset(client: &Client, token: &str) -> i64 {
+    profile_tz_offset(client, token).unwrap_or_else(|e| {
+        errln!("warning: could not look up your time zone, showing UTC: {e:#}");
+        0
+    })
+}
+
+/// `2024-04-05 09:00 -07:00`: `epoch` shifted into the zone `offset` seconds east of UTC.
+pub(crate) fn local_time(epoch: i64, offset: i64) -> String {
+    let (day, time) = day_and_time((epoch + offset) as f64);
+    let sign = if offset < 0 { '-' } else { '+' };
+    let abs = offset.abs();
+    format!(
+        "{day} {time} {sign}{:02}:{:02}",
+        abs / 3600,
+        (abs % 3600) / 60
+    )
+}
+
+/// `id  due  kind  text` for one reminder; kind is once, recurring or done.
+fn reminder_line(r: &Value, offset: i64) -> String {
+    let str_at = |k: &str| r.get(k).and_then(|v| v.as_str()).unwrap_or("");
+    let recurring = r.get("recurring").and_then(|v| v.as_bool()) == Some(true);
+    let done = r.get("complete_ts").and_then(|v| v.as_i64()).unwrap_or(0) != 0;
+    let kind = match (done, recurring) {
+        (true, _) => "done",
+        (false, true) => "recurring",
+        (false, false) => "once",
+    };
+    // Recurring reminders carry their schedule in the text rather than a time.
+    let due = match r.get("time").and_then(|v| v.as_i64()) {
+        Some(t) if t > 0 => local_time(t, offset),
+        _ => "-".into(),
+    };
+    format!("{}\t{due}\t{kind}\t{}", str_at("id"), str_at("text"))
+}
+
+/// `explain`, plus an echo of --time when Slack couldn't make sense of it.
+pub(crate) fn explain_time(e: anyhow::Error, time: &str) -> anyhow::Error {
+    if SlackError::code_of(&e) == Some("cannot_parse") {
+        return e.context(format!(
+            "Slack couldn't understand --time \"{time}\"; try something like \"in 2 hours\", \
+             \"tomorrow at 9am\" or a unix time"
+        ));
+    }
+    explain(e)
+}
+
+fn explain(e: anyhow::Error) -> anyhow::Error {
+    match SlackError::code_of(&e) {
+        Some("not_found") => e.context("no such reminder; `slack reminders` lists their IDs"),
+        Some("not_allowed_token_type") => e.context(
+            "reminders need a user token (xoxp-) with reminders:write; bot tokens can't set them",
+        ),
+        _ => e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn local_time_applies_the_offset() {
+        assert_eq!(local_time(1_712_345_678, 0), "2024-04-05 19:34 +00:00");
+        assert_eq!(
+            local_time(1_712_345_678, -7 * 3600),
+            "2024-04-05 12:34 -07:00"
+        );
+        assert_eq!(local_time(1_712_345_678, 19_800), "2024-04-06 01:04 +05:30");
+    }
+
+    #[test]
+    fn reminder_line_marks_kind() {
+        let once = json!({ "id": "Rm1", "text": "rotate certs", "time": 1_712_345_678, "complete_ts": 0, "recurring": false });
+        assert_eq!(
+            reminder_line(&once, 0),
+            "Rm1\t2024-04-05 19:34 +00:00\tonce\trotate certs"
+        );
+        let weekly = json!({ "id": "Rm2", "text": "standup", "recurring": true });
+        assert_eq!(reminder_line(&weekly, 0), "Rm2\t-\trecurring\tstandup");
+        let done = json!({ "id": "Rm3", "text": "x", "time": 1, "complete_ts": 2 });
+        assert!(reminder_line(&done, 0).contains("\tdone\t"));
+    }
+}
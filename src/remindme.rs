@@ -0,0 +1,157 @@
+//! `slack remindme`: a reminders.add reminder that quotes and links a message.
+
+use crate::output::outln;
+use crate::reminders::explain_time;
+use crate::{
+    ensure_token, get_permalink, http, parse_permalink, resolve_channel, slack_post, RemindArgs,
+};
+use anyhow::{anyhow, Context, Result};
+use reqwest::blocking::Client;
+use serde_json::Value;
+
+/// Longest excerpt quoted in the reminder, in characters.
+const EXCERPT_CHARS: usize = 80;
+
+pub fn run(args: RemindArgs) -> Result<()> {
+    let (channel, ts, thread_ts) = match parse_permalink(&args.ts) {
+        Some((channel, ts, thread_ts)) => (channel, ts, thread_ts),
+        None => {
+            let channel = args.channel.clone().ok_or_else(|| {
+                anyhow!("--channel is required unless --ts is a message permalink")
+            })?;
+            (channel, args.ts.clone(), None)
+        }
+    };
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &channel)?;
+    // Two lookups, then the reminder itself; say which of the three failed.
+    let message = fetch_message(&client, &token, &channel, &ts, thread_ts.as_deref())
+        .with_context(|| format!("could not read message {ts} in {channel}"))?;
+    let permalink = get_permalink(&client, &token, &channel, &ts)
+        .with_context(|| format!("could not get a permalink for {ts} in {channel}"))?;
+
+    let text = compose(&excerpt(&message, EXCERPT_CHARS), &permalink);
+    let resp = slack_post(
+        &client,
+        "reminders.add",
+        &token,
+        Some(&[("text", text.as_str()), ("time", args.time.as_str())]),
+    )
+    .map_err(|e| explain_time(e, &args.time))
+    .context("found the message, but reminders.add failed, so no reminder was set")?;
+    let reminder = resp.get("reminder").cloned().unwrap_or_default();
+    let id = reminder.get("id").and_then(|v| v.as_str()).unwrap_or("-");
+    match reminder.get("time").and_then(|v| v.as_u64()) {
+        Some(due) => {
+            let (day, time) = crate::export_html::day_and_time(due as f64);
+            outln!("reminder {id} due {day} {time} UTC");
+        }
+        None => outln!("reminder {id} created"),
+    }
+    Ok(())
+}
+
+/// Look the message up by ts; thread replies need their parent's ts to be found.
+pub(crate) fn fetch_message(
+    client: &Client,
+    token: &str,
+    channel: &str,
+    ts: &str,
+    thread_ts: Option<&str>,
+) -> Result<Value> {
+    let resp = match thread_ts.filter(|t| *t != ts) {
+        Some(parent) => slack_post(
+            client,
+            "conversations.replies",
+            token,
+            Some(&[("channel", channel), ("ts", parent), ("limit", "1000")]),
+        )?,
+        None => slack_post(
+            client,
+            "conversations.history",
+            token,
+            Some(&[
+                ("channel", channel),
+                ("latest", ts),
+                ("oldest", ts),
+                ("inclusive", "true"),
+                ("limit", "1"),
+            ]),
+        )?,
+    };
+    resp.get("messages")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .find(|m| m.get("ts").and_then(|v| v.as_str()) == Some(ts))
+        .cloned()
+        .ok_or_else(|| anyhow!("no message with ts {ts} in {channel}"))
+}
+
+/// First line of the message (or its first file's name), whitespace-collapsed and cut
+/// to `max` characters.
+pub(crate) fn excerpt(message: &Value, max: usize) -> String {
+    let text = message.get("text").and_then(|v| v.as_str()).unwrap_or("");
+    let line = text.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+    let mut source = line.split_whitespace().collect::<Vec<_>>().join(" ");
+    if source.is_empty() {
+        source = message
+            .get("files")
+            .and_then(|v| v.as_array())
+            .and_then(|files| files.first())
+            .and_then(|f| {
+                f.get("name")
+                    .or_else(|| f.get("title"))
+                    .and_then(|v| v.as_str())
+            })
+            .unwrap_or("a message")
+            .to_string();
+    }
+    let more_lines = text.trim().lines().count() > 1;
+    if source.chars().count() > max {
+        let cut: String = source.chars().take(max.saturating_sub(1)).collect();
+        format!("{}…", cut.trim_end())
+    } else if more_lines {
+        format!("{source}…")
+    } else {
+        source
+    }
+}
+
+fn compose(excerpt: &str, permalink: &str) -> String {
+    format!("\u{201c}{excerpt}\u{201d} {permalink}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn excerpt_uses_first_line_and_truncates() {
+        let m = json!({ "text": "deploy   is\tdone\nsecond line" });
+        assert_eq!(excerpt(&m, 80), "deploy is done…");
+        let m = json!({ "text": "abcdefghijkl" });
+        assert_eq!(excerpt(&m, 6), "abcde…");
+        assert_eq!(excerpt(&m, 12), "abcdefghijkl");
+        let m = json!({ "text": "héllo wörld" });
+        assert_eq!(excerpt(&m, 7), "héllo…");
+    }
+
+    #[test]
+    fn excerpt_of_file_only_message_is_the_file_name() {
+        let m = json!({ "text": "", "files": [{ "name": "report.pdf", "title": "Q3" }] });
+        assert_eq!(excerpt(&m, 80), "report.pdf");
+        let m = json!({ "text": "" });
+        assert_eq!(excerpt(&m, 80), "a message");
+    }
+
+    #[test]
+    fn reminder_text_quotes_excerpt_then_link() {
+        assert_eq!(
+            compose("ship it", "https://x.slack.com/archives/C1/p1"),
+            "\u{201c}ship it\u{201d} https://x.slack.com/archives/C1/p1"
+        );
+    }
+}
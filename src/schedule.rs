@@ -0,0 +1,82 @@
+//! `slack schedule`: queue a message with chat.scheduleMessage.
+
+use crate::output::outln;
+use crate::{
+    ensure_token, http, read_text_arg, resolve_channel, slack_post, unix_now, ScheduleArgs,
+    SlackError,
+};
+use anyhow::{anyhow, Result};
+
+/// How far ahead Slack accepts a scheduled message.
+const MAX_AHEAD_SECS: u64 = 120 * 86_400;
+
+pub fn run(args: ScheduleArgs) -> Result<()> {
+    check_post_at(args.post_at, unix_now())?;
+    let text = read_text_arg(&args.text)?;
+    // Slack rejects it with `no_text` anyway; fail before making any calls.
+    if text.trim().is_empty() {
+        return Err(anyhow!("--text is empty; nothing to schedule"));
+    }
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let post_at = args.post_at.to_string();
+    let mut form = vec![
+        ("channel", channel.as_str()),
+        ("text", text.as_str()),
+        ("post_at", post_at.as_str()),
+    ];
+    if let Some(ts) = args.thread_ts.as_deref() {
+        form.push(("thread_ts", ts));
+    }
+    let resp = slack_post(&client, "chat.scheduleMessage", &token, Some(&form)).map_err(|e| {
+        match SlackError::code_of(&e) {
+            Some("time_in_past") => {
+                e.context("--post-at had passed by the time Slack got it; pick a later time")
+            }
+            Some("time_too_far") => e.context("--post-at is more than 120 days away"),
+            _ => e,
+        }
+    })?;
+    let id = resp
+        .get("scheduled_message_id")
+        .and_then(|v| v.as_str())
+        .unwrap_or("-");
+    let at = resp
+        .get("post_at")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(args.post_at);
+    let (day, time) = crate::export_html::day_and_time(at as f64);
+    outln!("scheduled {id} for {day} {time} UTC");
+    Ok(())
+}
+
+/// `post_at` must be in the future and within Slack's 120-day window.
+fn check_post_at(post_at: u64, now: u64) -> Result<()> {
+    if post_at <= now {
+        return Err(anyhow!(
+            "--post-at {post_at} is in the past (now is {now}); give a future unix time"
+        ));
+    }
+    if post_at - now > MAX_AHEAD_SECS {
+        return Err(anyhow!(
+            "--post-at {post_at} is more than 120 days away; Slack won't schedule that far ahead"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn post_at_must_be_soon_but_not_past() {
+        let now = 1_700_000_000;
+        assert!(check_post_at(now - 1, now).is_err());
+        assert!(check_post_at(now, now).is_err());
+        assert!(check_post_at(now + 60, now).is_ok());
+        assert!(check_post_at(now + MAX_AHEAD_SECS, now).is_ok());
+        assert!(check_post_at(now + MAX_AHEAD_SECS + 1, now).is_err());
+    }
+}
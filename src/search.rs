@@ -0,0 +1,209 @@
+//! `slack search` and `search-files`: Slack's search, which pages by number rather than by cursor.
+
+use crate::files::human_size;
+use crate::output::{errln, outln};
+use crate::remindme::excerpt;
+use crate::{ensure_token, http, slack_post_retrying, SearchArgs, SlackError};
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+/// The most matches search.messages and search.files return per page.
+const MAX_PAGE_SIZE: u32 = 100;
+/// Pages --all walks before stopping; Slack serves no further than this anyway.
+const MAX_PAGES: u32 = 100;
+/// Characters of each match's text shown in the listing.
+const PREVIEW_CHARS: usize = 100;
+
+pub fn messages(args: SearchArgs) -> Result<()> {
+    run(&args, "search.messages", "messages", match_line)
+}
+
+pub fn files(args: SearchArgs) -> Result<()> {
+    run(&args, "search.files", "files", file_line)
+}
+
+/// Print up to --limit results of one search.* method, one `line` each, starting at --page.
+fn run(args: &SearchArgs, method: &str, key: &str, line: fn(&Value) -> String) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let (count, limit) = if args.count_only {
+        (1, 1)
+    } else if args.all {
+        (MAX_PAGE_SIZE, MAX_PAGE_SIZE * MAX_PAGES)
+    } else {
+        (page_size(args.limit), args.limit)
+    };
+    let count = count.to_string();
+    let mut page = args.page;
+    let mut printed = 0;
+    loop {
+        let page_str = page.to_string();
+        let mut form = vec![
+            ("query", args.query.as_str()),
+            ("count", count.as_str()),
+            ("page", page_str.as_str()),
+        ];
+        if let Some(sort) = args.sort {
+            form.push(("sort", sort.as_str()));
+        }
+        if let Some(dir) = args.sort_dir {
+            form.push(("sort_dir", dir.as_str()));
+        }
+        let resp = slack_post_retrying(&client, &token, method, &form).map_err(explain)?;
+        // Workspaces with file search turned off answer ok but leave the results out.
+        let Some(results) = resp.get(key) else {
+            return Err(anyhow!(
+                "{method} returned no {key}; search may be turned off for this workspace"
+            ));
+        };
+        if args.count_only {
+            outln!("{}", total(results));
+            return Ok(());
+        }
+        let matches = results
+            .get("matches")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+        let shown = matches.len().min((limit - printed) as usize);
+        for m in &matches[..shown] {
+            outln!("{}", line(m));
+        }
+        printed += shown as u32;
+        let pages = pages(results);
+        if printed == 0 && page == args.page {
+            outln!("no matches");
+            return Ok(());
+        }
+        if page >= pages {
+            return Ok(());
+        }
+        if printed >= limit {
+            // A partly shown page is shown again rather than skipping its tail.
+            let next = if shown == matches.len() {
+                page + 1
+            } else {
+                page
+            };
+            errln!(
+                "({} matches in all; continue with --page {next})",
+                total(results)
+            );
+            return Ok(());
+        }
+        page += 1;
+    }
+}
+
+/// Matches per page: --limit itself up to Slack's cap, otherwise split evenly over the
+/// fewest pages so that --page steps stay aligned with what was printed.
+fn page_size(limit: u32) -> u32 {
+    let pages = limit.div_ceil(MAX_PAGE_SIZE);
+    limit.div_ceil(pages)
+}
+
+/// `ts  #channel  user  preview  permalink` for one search match.
+fn match_line(m: &Value) -> String {
+    let str_at = |ptr: &str| m.pointer(ptr).and_then(|v| v.as_str()).unwrap_or("");
+    let channel = match str_at("/channel/name") {
+        "" => str_at("/channel/id").to_string(),
+        name => format!("#{name}"),
+    };
+    let user = [str_at("/username"), str_at("/user")]
+        .into_iter()
+        .find(|s| !s.is_empty())
+        .unwrap_or("unknown");
+    format!(
+        "{}\t{channel}\t{user}\t{}\t{}",
+        str_at("/ts"),
+        excerpt(m, PREVIEW_CHARS),
+        str_at("/permalink")
+    )
+}
+
+/// `id  name  type  size  uploader  permalink` for one file match.
+fn file_line(f: &Value) -> String {
+    let str_at = |ptr: &str| f.pointer(ptr).and_then(|v| v.as_str()).unwrap_or("");
+    let name = [str_at("/name"), str_at("/title")]
+        .into_iter()
+        .find(|s| !s.is_empty())
+        .unwrap_or("-");
+    let uploader = [str_at("/username"), str_at("/user")]
+        .into_iter()
+        .find(|s| !s.is_empty())
+        .unwrap_or("unknown");
+    let size = f
+        .get("size")
+        .and_then(|v| v.as_u64())
+        .map(human_size)
+        .unwrap_or_else(|| "-".into());
+    format!(
+        "{}\t{name}\t{}\t{size}\t{uploader}\t{}",
+        str_at("/id"),
+        str_at("/filetype"),
+        str_at("/permalink")
+    )
+}
+
+/// Total matches, from `paging` or, when Slack sends that instead, `pagination`.
+fn total(results: &Value) -> u64 {
+    results
+        .pointer("/paging/total")
+        .or_else(|| results.pointer("/pagination/total_count"))
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0)
+}
+
+/// How many pages of results there are, from the same two places.
+fn pages(results: &Value) -> u32 {
+    results
+        .pointer("/paging/pages")
+        .or_else(|| results.pointer("/pagination/page_count"))
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0) as u32
+}
+
+fn explain(e: anyhow::Error) -> anyhow::Error {
+    match SlackError::code_of(&e) {
+        Some("feature_not_enabled" | "not_allowed" | "restricted_action") => {
+            e.context("search is turned off for this workspace or token")
+        }
+        Some("not_allowed_token_type") => e.context(
+            "search needs a user token (xoxp-) with search:read; bot tokens can't search. \
+             Run `slack init --force` with a user token.",
+        ),
+        _ => e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn page_size_splits_large_limits_evenly() {
+        assert_eq!(page_size(20), 20);
+        assert_eq!(page_size(100), 100);
+        assert_eq!(page_size(150), 75);
+        assert_eq!(page_size(250), 84);
+    }
+
+    #[test]
+    fn match_line_names_channel_and_user() {
+        let m = json!({
+            "ts": "1.0",
+            "channel": { "id": "C1", "name": "ops" },
+            "user": "U1",
+            "username": "jane",
+            "text": "deploy failed\nstack trace follows",
+            "permalink": "https://acme.slack.com/archives/C1/p10"
+        });
+        assert_eq!(
+            match_line(&m),
+            "1.0\t#ops\tjane\tdeploy failed…\thttps://acme.slack.com/archives/C1/p10"
+        );
+        let dm = json!({ "ts": "2.0", "channel": { "id": "D1" }, "user": "U2", "text": "hi" });
+        assert_eq!(match_line(&dm), "2.0\tD1\tU2\thi\t");
+    }
+}
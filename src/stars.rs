@@ -0,0 +1,170 @@
+//! `slack saved`, `save` and `unsave`: your saved items, which the API still calls stars.
+
+use crate::output::{errln, outln};
+use crate::remindme::excerpt;
+use crate::{
+    each_conversation_page, each_page, ensure_token, http, resolve_channel, slack_post, SaveArgs,
+    SavedArgs, SlackError,
+};
+use anyhow::Result;
+use reqwest::blocking::Client;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Characters of each saved message shown by `saved`.
+const PREVIEW_CHARS: usize = 80;
+
+pub fn save(args: SaveArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let form = [
+        ("channel", channel.as_str()),
+        ("timestamp", args.ts.as_str()),
+    ];
+    match slack_post(&client, "stars.add", &token, Some(&form)) {
+        Ok(_) => outln!("saved {}", args.ts),
+        Err(e) => match SlackError::code_of(&e) {
+            Some("already_starred") => outln!("{} was already saved", args.ts),
+            _ => return Err(e),
+        },
+    }
+    Ok(())
+}
+
+pub fn unsave(args: SaveArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let form = [
+        ("channel", channel.as_str()),
+        ("timestamp", args.ts.as_str()),
+    ];
+    match slack_post(&client, "stars.remove", &token, Some(&form)) {
+        Ok(_) => outln!("unsaved {}", args.ts),
+        Err(e) => match SlackError::code_of(&e) {
+            Some("not_starred") => outln!("{} was not saved", args.ts),
+            _ => return Err(e),
+        },
+    }
+    Ok(())
+}
+
+pub fn list(args: SavedArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let items = fetch_saved(&client, &token, args.limit)?;
+    if items.is_empty() {
+        outln!("no saved items");
+        return Ok(());
+    }
+    let names = if items.iter().any(|i| i.get("channel").is_some()) {
+        channel_names(&client, &token)
+    } else {
+        HashMap::new()
+    };
+    for item in &items {
+        outln!("{}", saved_line(item, &names));
+    }
+    Ok(())
+}
+
+/// Up to `limit` saved items, following stars.list's cursor.
+fn fetch_saved(client: &Client, token: &str, limit: usize) -> Result<Vec<Value>> {
+    let mut items = Vec::new();
+    each_page(
+        client,
+        token,
+        "stars.list",
+        &[("limit", "100")],
+        "items",
+        |page| {
+            items.extend_from_slice(page);
+            items.len() < limit
+        },
+    )?;
+    items.truncate(limit);
+    Ok(items)
+}
+
+/// Channel ID to name for the conversations you can see. Saved items still list fine
+/// without it, so a failure only costs the names.
+fn channel_names(client: &Client, token: &str) -> HashMap<String, String> {
+    let mut names = HashMap::new();
+    let result = each_conversation_page(
+        client,
+        token,
+        "public_channel,private_channel",
+        1000,
+        true,
+        |page| {
+            for c in page {
+                let id = c.get("id").and_then(|v| v.as_str());
+                let name = c.get("name").and_then(|v| v.as_str());
+                if let (Some(id), Some(name)) = (id, name) {
+                    names.insert(id.to_string(), name.to_string());
+                }
+            }
+        },
+    );
+    if let Err(e) = result {
+        errln!("warning: could not list channels, showing IDs: {e:#}");
+    }
+    names
+}
+
+/// `type  channel  ts-or-file-id  preview` for one saved item.
+fn saved_line(item: &Value, names: &HashMap<String, String>) -> String {
+    let str_at = |ptr: &str| item.pointer(ptr).and_then(|v| v.as_str()).unwrap_or("");
+    let kind = str_at("/type");
+    let channel = match str_at("/channel") {
+        "" => "-".to_string(),
+        id => names
+            .get(id)
+            .map(|name| format!("#{name}"))
+            .unwrap_or_else(|| id.to_string()),
+    };
+    let (what, preview) = match kind {
+        "message" => (
+            str_at("/message/ts").to_string(),
+            item.get("message")
+                .map(|m| excerpt(m, PREVIEW_CHARS))
+                .unwrap_or_default(),
+        ),
+        "file" | "file_comment" => {
+            let name = [str_at("/file/name"), str_at("/file/title")]
+                .into_iter()
+                .find(|s| !s.is_empty())
+                .unwrap_or("-");
+            (str_at("/file/id").to_string(), name.to_string())
+        }
+        _ => ("-".to_string(), "-".to_string()),
+    };
+    format!("{kind}\t{channel}\t{what}\t{preview}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn saved_line_tells_messages_from_files() {
+        let mut names = HashMap::new();
+        names.insert("C1".to_string(), "ops".to_string());
+        let message = json!({
+            "type": "message",
+            "channel": "C1",
+            "message": { "ts": "1.0", "text": "follow up on the outage\ndetails" }
+        });
+        assert_eq!(
+            saved_line(&message, &names),
+            "message\t#ops\t1.0\tfollow up on the outage…"
+        );
+        let file = json!({ "type": "file", "file": { "id": "F1", "name": "plan.pdf" } });
+        assert_eq!(saved_line(&file, &names), "file\t-\tF1\tplan.pdf");
+        let unknown =
+            json!({ "type": "message", "channel": "C9", "message": { "ts": "2.0", "text": "hi" } });
+        assert_eq!(saved_line(&unknown, &names), "message\tC9\t2.0\thi");
+    }
+}
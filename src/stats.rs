@@ -0,0 +1,350 @@
+//! `slack stats`: small engagement reports computed from channel history.
+
+use crate::output::{errln, out, outln};
+use crate::{
+    each_page, ensure_token, get_permalink, http, resolve_channel, slack_post, unix_now,
+    ReactionStatsArgs, ReportFormat, StatsArgs, StatsCommand,
+};
+use anyhow::{anyhow, Result};
+use reqwest::blocking::Client;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+pub fn run(args: StatsArgs) -> Result<()> {
+    match args.command {
+        StatsCommand::Reactions(args) => reactions(args),
+    }
+}
+
+fn reactions(args: ReactionStatsArgs) -> Result<()> {
+    let oldest = parse_since(&args.since, unix_now())?;
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let messages = history_since(&client, &token, &channel, oldest)?;
+    let mut stats = aggregate(&messages, args.top);
+
+    // Message payloads cap each reaction's `users` list; ask for the full list on the leaders.
+    let mut leaders = Vec::new();
+    for top in stats.top_messages.iter_mut() {
+        match get_permalink(&client, &token, &channel, &top.ts) {
+            Ok(url) => top.permalink = Some(url),
+            Err(e) => errln!("warning: no permalink for {}: {e:#}", top.ts),
+        }
+        let form = [
+            ("channel", channel.as_str()),
+            ("timestamp", top.ts.as_str()),
+            ("full", "true"),
+        ];
+        match slack_post(&client, "reactions.get", &token, Some(&form)) {
+            Ok(resp) => leaders.extend(resp.get("message").cloned()),
+            Err(e) => errln!("warning: could not fetch reactions for {}: {e:#}", top.ts),
+        }
+    }
+    stats.top_reactors = top_reactors(&leaders, args.top);
+
+    match args.format {
+        ReportFormat::Json => outln!("{}", serde_json::to_string_pretty(&stats)?),
+        ReportFormat::Text => out!("{}", render_text(&channel, &args.since, &stats)),
+    }
+    Ok(())
+}
+
+/// Every top-level message in `channel` posted after `oldest` (unix seconds).
+fn history_since(client: &Client, token: &str, channel: &str, oldest: u64) -> Result<Vec<Value>> {
+    let oldest = oldest.to_string();
+    let form = [
+        ("channel", channel),
+        ("oldest", oldest.as_str()),
+        ("limit", "200"),
+    ];
+    let mut messages = Vec::new();
+    each_page(
+        client,
+        token,
+        "conversations.history",
+        &form,
+        "messages",
+        |page| {
+            messages.extend_from_slice(page);
+            true
+        },
+    )?;
+    Ok(messages)
+}
+
+/// `30d`, `12h`, `90m`, `2w` ago, or an absolute unix time / Slack ts.
+pub(crate) fn parse_since(since: &str, now: u64) -> Result<u64> {
+    let s = since.trim();
+    if let Some(secs) = parse_duration(s)? {
+        return Ok(now.saturating_sub(secs));
+    }
+    let bad = || anyhow!("--since must look like 30d, 12h, 2w or a unix time");
+    if s.ends_with(['m', 'h', 'd', 'w']) {
+        return Err(bad());
+    }
+    let secs = s.split('.').next().unwrap_or_default();
+    secs.parse().map_err(|_| bad())
+}
+
+/// `90m`, `12h`, `30d` or `2w` in seconds; `None` if `s` isn't shaped like one, an
+/// error if it is but doesn't fit in a u64.
+pub(crate) fn parse_duration(s: &str) -> Result<Option<u64>> {
+    let s = s.trim();
+    let unit: u64 = match s.chars().last() {
+        Some('m') => 60,
+        Some('h') => 3600,
+        Some('d') => 86_400,
+        Some('w') => 7 * 86_400,
+        _ => return Ok(None),
+    };
+    let digits = &s[..s.len() - 1];
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Ok(None);
+    }
+    digits
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(unit))
+        .map(Some)
+        .ok_or_else(|| anyhow!("duration `{s}` is too long"))
+}
+
+#[derive(Debug, Default, Serialize)]
+struct ReactionStats {
+    total: u64,
+    messages: usize,
+    messages_with_reactions: usize,
+    top_emojis: Vec<Counted>,
+    top_messages: Vec<TopMessage>,
+    top_reactors: Vec<Counted>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct Counted {
+    name: String,
+    count: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct TopMessage {
+    ts: String,
+    user: String,
+    count: u64,
+    permalink: Option<String>,
+}
+
+fn reactions_of(m: &Value) -> impl Iterator<Item = &Value> {
+    m.get("reactions")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+}
+
+fn reaction_count(r: &Value) -> u64 {
+    r.get("count").and_then(|v| v.as_u64()).unwrap_or(0)
+}
+
+/// Totals, emoji ranking and the `top` most-reacted-to messages. Reactors are left empty;
+/// they come from the full reaction lists of the leaders (see `top_reactors`).
+fn aggregate(messages: &[Value], top: usize) -> ReactionStats {
+    let mut emojis: HashMap<String, u64> = HashMap::new();
+    let mut per_message = Vec::new();
+    let mut total = 0;
+    for m in messages {
+        let mut count = 0;
+        for r in reactions_of(m) {
+            let name = r.get("name").and_then(|v| v.as_str()).unwrap_or("?");
+            *emojis.entry(name.to_string()).or_default() += reaction_count(r);
+            count += reaction_count(r);
+        }
+        if count > 0 {
+            per_message.push(TopMessage {
+                ts: m.get("ts").and_then(|v| v.as_str()).unwrap_or("").into(),
+                user: m
+                    .get("user")
+                    .and_then(|v| v.as_str())
+                    .or_else(|| m.get("bot_id").and_then(|v| v.as_str()))
+                    .unwrap_or("unknown")
+                    .into(),
+                count,
+                permalink: None,
+            });
+        }
+        total += count;
+    }
+    let messages_with_reactions = per_message.len();
+    per_message.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.ts.cmp(&b.ts)));
+    per_message.truncate(top);
+    ReactionStats {
+        total,
+        messages: messages.len(),
+        messages_with_reactions,
+        top_emojis: ranked(emojis, top),
+        top_messages: per_message,
+        top_reactors: Vec::new(),
+    }
+}
+
+/// Who reacted most across `messages`, using each reaction's `users` list.
+fn top_reactors(messages: &[Value], top: usize) -> Vec<Counted> {
+    let mut counts: HashMap<String, u64> = HashMap::new();
+    for r in messages.iter().flat_map(reactions_of) {
+        for uid in r
+            .get("users")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_str())
+        {
+            *counts.entry(uid.to_string()).or_default() += 1;
+        }
+    }
+    ranked(counts, top)
+}
+
+/// Highest counts first; ties broken by name so output is stable.
+fn ranked(counts: HashMap<String, u64>, top: usize) -> Vec<Counted> {
+    let mut v: Vec<Counted> = counts
+        .into_iter()
+        .map(|(name, count)| Counted { name, count })
+        .collect();
+    v.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    v.truncate(top);
+    v
+}
+
+fn render_text(channel: &str, since: &str, stats: &ReactionStats) -> String {
+    let mut out = format!(
+        "{} reactions on {} of {} messages in {channel} (since {since})\n",
+        stats.total, stats.messages_with_reactions, stats.messages
+    );
+    if !stats.top_emojis.is_empty() {
+        out.push_str("\nTop emojis:\n");
+        for e in &stats.top_emojis {
+            out.push_str(&format!("  {:>5}  :{}:\n", e.count, e.name));
+        }
+    }
+    if !stats.top_messages.is_empty() {
+        out.push_str("\nMost reacted-to messages:\n");
+        for m in &stats.top_messages {
+            out.push_str(&format!(
+                "  {:>5}  {}  {}  {}\n",
+                m.count,
+                m.ts,
+                m.user,
+                m.permalink.as_deref().unwrap_or("-")
+            ));
+        }
+    }
+    if !stats.top_reactors.is_empty() {
+        out.push_str("\nTop reactors (on those messages):\n");
+        for r in &stats.top_reactors {
+            out.push_str(&format!("  {:>5}  {}\n", r.count, r.name));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn msg(ts: &str, user: &str, reactions: Value) -> Value {
+        json!({ "ts": ts, "user": user, "text": "x", "reactions": reactions })
+    }
+
+    #[test]
+    fn since_accepts_durations_and_timestamps() {
+        let now = 10_000_000;
+        assert_eq!(parse_since("30d", now).unwrap(), now - 30 * 86_400);
+        assert_eq!(parse_since("12h", now).unwrap(), now - 12 * 3600);
+        assert_eq!(parse_since("2w", now).unwrap(), now - 14 * 86_400);
+        assert_eq!(
+            parse_since("1712345678.000100", now).unwrap(),
+            1_712_345_678
+        );
+        assert_eq!(parse_since("999w", 10).unwrap(), 0);
+        assert!(parse_since("soon", now).is_err());
+        assert!(parse_since("xd", now).is_err());
+        assert_eq!(parse_duration("45m").unwrap(), Some(2700));
+        assert_eq!(parse_duration("1712345678").unwrap(), None);
+        assert_eq!(parse_duration("xd").unwrap(), None);
+        assert!(parse_duration("99999999999999999w").is_err());
+        assert!(parse_duration("99999999999999999999999m").is_err());
+        assert!(parse_since("99999999999999999w", now).is_err());
+    }
+
+    #[test]
+    fn aggregates_totals_emojis_and_leaders() {
+        let messages = vec![
+            msg(
+                "1.0",
+                "U1",
+                json!([{ "name": "tada", "count": 3 }, { "name": "eyes", "count": 1 }]),
+            ),
+            msg("2.0", "U2", json!([{ "name": "tada", "count": 5 }])),
+            json!({ "ts": "3.0", "user": "U3", "text": "quiet" }),
+            msg("4.0", "U1", json!([{ "name": "eyes", "count": 4 }])),
+        ];
+        let stats = aggregate(&messages, 2);
+        assert_eq!(stats.total, 13);
+        assert_eq!(stats.messages, 4);
+        assert_eq!(stats.messages_with_reactions, 3);
+        assert_eq!(
+            stats.top_emojis,
+            vec![
+                Counted {
+                    name: "tada".into(),
+                    count: 8
+                },
+                Counted {
+                    name: "eyes".into(),
+                    count: 5
+                },
+            ]
+        );
+        let leaders: Vec<(&str, u64)> = stats
+            .top_messages
+            .iter()
+            .map(|m| (m.ts.as_str(), m.count))
+            .collect();
+        // 1.0 and 4.0 tie at 4; the older message wins.
+        assert_eq!(leaders, vec![("2.0", 5), ("1.0", 4)]);
+    }
+
+    #[test]
+    fn reactors_count_each_reaction_once_per_user() {
+        let messages = vec![
+            msg(
+                "1.0",
+                "U1",
+                json!([
+                    { "name": "tada", "count": 2, "users": ["U2", "U3"] },
+                    { "name": "eyes", "count": 1, "users": ["U2"] }
+                ]),
+            ),
+            msg(
+                "2.0",
+                "U2",
+                json!([{ "name": "tada", "count": 1, "users": ["U3"] }]),
+            ),
+        ];
+        let reactors = top_reactors(&messages, 10);
+        assert_eq!(
+            reactors,
+            vec![
+                Counted {
+                    name: "U2".into(),
+                    count: 2
+                },
+                Counted {
+                    name: "U3".into(),
+                    count: 2
+                },
+            ]
+        );
+    }
+}
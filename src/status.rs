@@ -0,0 +1,239 @@
+//! `slack status`: read, set and clear your own status (users.profile.get/set).
+
+use crate::config::{self, StatusPreset};
+use crate::export_html::days_from_civil;
+use crate::output::outln;
+use crate::reactions::normalize_emoji;
+use crate::reminders::{local_time, profile_tz_offset};
+use crate::stats::parse_duration;
+use crate::{ensure_token, http, slack_post, unix_now, StatusArgs, StatusCommand, StatusSetArgs};
+use anyhow::{anyhow, Context, Result};
+use reqwest::blocking::Client;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+pub fn run(args: StatusArgs) -> Result<()> {
+    if args.list_presets {
+        return list_presets(&config::load()?.status);
+    }
+    match args.command {
+        None | Some(StatusCommand::Get) => get(),
+        Some(StatusCommand::Set(args)) => set(args),
+        Some(StatusCommand::Clear) => {
+            let token = ensure_token()?;
+            write(&http(), &token, "", "", 0)?;
+            outln!("status cleared");
+            Ok(())
+        }
+        Some(StatusCommand::Preset(words)) => {
+            let [name] = words.as_slice() else {
+                return Err(anyhow!(
+                    "`slack status {}`: a preset takes no arguments",
+                    words.join(" ")
+                ));
+            };
+            apply_preset(&config::load()?.status, name)
+        }
+    }
+}
+
+fn list_presets(presets: &BTreeMap<String, StatusPreset>) -> Result<()> {
+    if presets.is_empty() {
+        outln!("no status presets; {}", hint());
+    }
+    for (name, p) in presets {
+        let duration = p.duration.as_deref().unwrap_or("-");
+        outln!("{name}\t{}\t{}\t{duration}", p.emoji, p.text);
+    }
+    Ok(())
+}
+
+fn apply_preset(presets: &BTreeMap<String, StatusPreset>, name: &str) -> Result<()> {
+    let preset = presets.get(name).ok_or_else(|| {
+        if presets.is_empty() {
+            anyhow!(
+                "unknown status preset `{name}`: none are configured; {}",
+                hint()
+            )
+        } else {
+            let names: Vec<&str> = presets.keys().map(String::as_str).collect();
+            anyhow!(
+                "unknown status preset `{name}`; configured: {}",
+                names.join(", ")
+            )
+        }
+    })?;
+    let expires_in = match preset.duration.as_deref() {
+        Some(d) => parse_duration(d)
+            .with_context(|| format!("status preset `{name}`"))?
+            .ok_or_else(|| {
+                anyhow!("status preset `{name}` has duration `{d}`; use e.g. 45m, 2h or 1d")
+            })?,
+        None => 0,
+    };
+    let emoji = match preset.emoji.as_str() {
+        "" => String::new(),
+        e => format!(":{}:", normalize_emoji(e)?),
+    };
+    let token = ensure_token()?;
+    let client = http();
+    let expiration = if expires_in > 0 {
+        unix_now()
+            .checked_add(expires_in)
+            .and_then(|t| i64::try_from(t).ok())
+            .ok_or_else(|| anyhow!("status preset `{name}`: duration is too long"))?
+    } else {
+        0
+    };
+    write(&client, &token, &emoji, &preset.text, expiration)?;
+    let offset = if expiration > 0 {
+        profile_tz_offset(&client, &token).unwrap_or(0)
+    } else {
+        0
+    };
+    report(&emoji, &preset.text, expiration, offset);
+    Ok(())
+}
+
+fn hint() -> String {
+    match config::config_path() {
+        Some(p) => format!("add [status.NAME] tables to {}", p.display()),
+        None => "add [status.NAME] tables to config.toml".to_string(),
+    }
+}
+
+fn get() -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let resp = slack_post(&client, "users.profile.get", &token, None)?;
+    let str_at = |ptr: &str| resp.pointer(ptr).and_then(|v| v.as_str()).unwrap_or("");
+    let (emoji, text) = (
+        str_at("/profile/status_emoji"),
+        str_at("/profile/status_text"),
+    );
+    if emoji.is_empty() && text.is_empty() {
+        outln!("no status");
+        return Ok(());
+    }
+    let expires = resp
+        .pointer("/profile/status_expiration")
+        .and_then(|v| v.as_i64())
+        .unwrap_or(0);
+    let until = if expires > 0 {
+        let offset = profile_tz_offset(&client, &token).unwrap_or(0);
+        format!("until {}", local_time(expires, offset))
+    } else {
+        "no expiry".to_string()
+    };
+    outln!("{emoji}\t{text}\t{until}");
+    Ok(())
+}
+
+fn set(args: StatusSetArgs) -> Result<()> {
+    let emoji = match args.emoji.as_deref() {
+        Some(e) => format!(":{}:", normalize_emoji(e)?),
+        None => String::new(),
+    };
+    if emoji.is_empty() && args.text.is_empty() {
+        return Err(anyhow!(
+            "nothing to set; pass --emoji and/or --text (`slack status clear` clears it)"
+        ));
+    }
+    let token = ensure_token()?;
+    let client = http();
+    let (expiration, offset) = match args.expires.as_deref() {
+        None => (0, 0),
+        Some(s) => match s.trim().parse::<i64>() {
+            Ok(secs) => (secs, 0),
+            Err(_) => {
+                let offset = profile_tz_offset(&client, &token)?;
+                (parse_local(s, offset)?, offset)
+            }
+        },
+    };
+    if args.expires.is_some() && expiration <= unix_now() as i64 {
+        return Err(anyhow!(
+            "--expires {} is in the past",
+            local_time(expiration, offset)
+        ));
+    }
+    write(&client, &token, &emoji, &args.text, expiration)?;
+    report(&emoji, &args.text, expiration, offset);
+    Ok(())
+}
+
+/// `status set: :palm_tree: On PTO until 2025-07-01 18:00 -07:00`
+fn report(emoji: &str, text: &str, expiration: i64, offset: i64) {
+    let until = if expiration > 0 {
+        format!(" until {}", local_time(expiration, offset))
+    } else {
+        String::new()
+    };
+    let shown = [emoji, text]
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    outln!("status set: {shown}{until}");
+}
+
+/// One users.profile.set call; empty strings and a zero expiration clear the status.
+fn write(client: &Client, token: &str, emoji: &str, text: &str, expiration: i64) -> Result<()> {
+    let profile = json!({
+        "status_emoji": emoji,
+        "status_text": text,
+        "status_expiration": expiration,
+    })
+    .to_string();
+    slack_post(
+        client,
+        "users.profile.set",
+        token,
+        Some(&[("profile", profile.as_str())]),
+    )?;
+    Ok(())
+}
+
+/// `2025-07-01 18:00` (or `2025-07-01T18:00`, or just the day for midnight) in the zone
+/// `offset` seconds east of UTC, as unix seconds.
+fn parse_local(s: &str, offset: i64) -> Result<i64> {
+    let s = s.trim();
+    let bad = || anyhow!("`{s}` is not a time; use YYYY-MM-DD HH:MM or a unix time");
+    let (day, time) = s.split_once([' ', 'T']).unwrap_or((s, "00:00"));
+    let mut date = day.splitn(3, '-').map(|p| p.parse::<u32>().ok());
+    let (Some(Some(y)), Some(Some(m)), Some(Some(d))) = (date.next(), date.next(), date.next())
+    else {
+        return Err(bad());
+    };
+    let (hh, mm) = time.trim().split_once(':').ok_or_else(bad)?;
+    let (hh, mm): (i64, i64) = (
+        hh.parse().map_err(|_| bad())?,
+        mm.parse().map_err(|_| bad())?,
+    );
+    if !(1..=12).contains(&m)
+        || !(1..=31).contains(&d)
+        || !(0..24).contains(&hh)
+        || !(0..60).contains(&mm)
+    {
+        return Err(bad());
+    }
+    Ok(days_from_civil(i64::from(y), m, d) * 86_400 + hh * 3600 + mm * 60 - offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_times_are_shifted_by_the_offset() {
+        assert_eq!(parse_local("2024-04-05 19:34", 0).unwrap(), 1_712_345_640);
+        assert_eq!(
+            parse_local("2024-04-05T12:34", -7 * 3600).unwrap(),
+            1_712_345_640
+        );
+        assert_eq!(parse_local("2024-04-05", 0).unwrap(), 1_712_275_200);
+        assert!(parse_local("2024-13-01 10:00", 0).is_err());
+        assert!(parse_local("tomorrow", 0).is_err());
+        assert!(parse_local("2024-04-05 25:00", 0).is_err());
+    }
+}
@@ -0,0 +1,213 @@
+//! Aligned columns for terminal output.
+//!
+//! Widths are measured in terminal cells (CJK and most emoji take two), not bytes or
+//! chars, and ANSI color codes count for nothing, so columns line up whatever the names
+//! are written in.
+
+use unicode_width::UnicodeWidthChar;
+
+const ELLIPSIS: char = '…';
+const GAP: &str = "  ";
+
+/// Display width of `s` in terminal cells, ignoring ANSI escape sequences.
+pub fn width(s: &str) -> usize {
+    segments(s)
+        .filter_map(|seg| match seg {
+            Segment::Char(c) => Some(c.width().unwrap_or(0)),
+            Segment::Escape(_) => None,
+        })
+        .sum()
+}
+
+/// `s` padded with spaces to exactly `cols` cells, or cut to fit with a trailing `…`
+/// (which takes one of the cells). Color codes are kept; a cut colored cell gets a
+/// reset so the color doesn't leak into the padding.
+pub fn fit(s: &str, cols: usize) -> String {
+    let full = width(s);
+    if full <= cols {
+        return format!("{s}{}", " ".repeat(cols - full));
+    }
+    let budget = cols.saturating_sub(1);
+    let (mut out, mut used, mut colored) = (String::new(), 0, false);
+    for seg in segments(s) {
+        match seg {
+            Segment::Escape(e) => {
+                colored = true;
+                out.push_str(e);
+            }
+            Segment::Char(c) => {
+                let w = c.width().unwrap_or(0);
+                if used + w > budget {
+                    break;
+                }
+                out.push(c);
+                used += w;
+            }
+        }
+    }
+    if cols > 0 {
+        out.push(ELLIPSIS);
+        used += 1;
+    }
+    if colored {
+        out.push_str("\x1b[0m");
+    }
+    // A double-width char that didn't fit can leave one cell over.
+    out.push_str(&" ".repeat(cols - used));
+    out
+}
+
+/// Lay out `header` and `rows` in columns separated by two spaces. Each column is as wide
+/// as its widest cell, capped by `max[i]` when given; the last column is written out
+/// whole, neither padded nor cut.
+pub fn render(header: &[&str], rows: &[Vec<String>], max: &[Option<usize>]) -> String {
+    let widths = widths(header, rows, max);
+    let header: Vec<String> = header.iter().map(|h| h.to_string()).collect();
+    let mut out = render_rows(std::slice::from_ref(&header), &widths);
+    out.push_str(&render_rows(rows, &widths));
+    out
+}
+
+/// The column widths `render` would use. A table printed a page at a time takes them
+/// from its first page and lays out the rest with `render_rows`.
+pub fn widths(header: &[&str], rows: &[Vec<String>], max: &[Option<usize>]) -> Vec<usize> {
+    let mut widths: Vec<usize> = header.iter().map(|h| width(h)).collect();
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            if i < widths.len() {
+                widths[i] = widths[i].max(width(cell));
+            }
+        }
+    }
+    for (w, cap) in widths.iter_mut().zip(max) {
+        if let Some(cap) = cap {
+            *w = (*w).min(*cap);
+        }
+    }
+    widths
+}
+
+/// `rows` in columns of the given `widths`, without a header.
+pub fn render_rows(rows: &[Vec<String>], widths: &[usize]) -> String {
+    let mut out = String::new();
+    for row in rows {
+        let last = row.len().min(widths.len()).saturating_sub(1);
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(i, (cell, &w))| {
+                if i == last {
+                    cell.clone()
+                } else {
+                    fit(cell, w)
+                }
+            })
+            .collect();
+        out.push_str(cells.join(GAP).trim_end_matches(' '));
+        out.push('\n');
+    }
+    out
+}
+
+enum Segment<'a> {
+    Char(char),
+    Escape(&'a str),
+}
+
+/// Split `s` into printable chars and ANSI CSI sequences (`ESC [ … final`).
+fn segments(s: &str) -> impl Iterator<Item = Segment<'_>> {
+    let mut rest = s;
+    std::iter::from_fn(move || {
+        let c = rest.chars().next()?;
+        if let Some(after) = rest.strip_prefix("\x1b[") {
+            let end = after
+                .find(|c: char| ('\x40'..='\x7e').contains(&c))
+                .map(|i| i + 1)
+                .unwrap_or(after.len());
+            let (esc, tail) = rest.split_at(2 + end);
+            rest = tail;
+            return Some(Segment::Escape(esc));
+        }
+        rest = &rest[c.len_utf8()..];
+        Some(Segment::Char(c))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: &str = "\x1b[31m";
+    const RESET: &str = "\x1b[0m";
+
+    #[test]
+    fn width_counts_cells_not_chars() {
+        assert_eq!(width("abc"), 3);
+        assert_eq!(width("山田太郎"), 8);
+        assert_eq!(width("김민준"), 6);
+        assert_eq!(width("sam🚀"), 5);
+        assert_eq!(width(&format!("{RED}red{RESET}")), 3);
+    }
+
+    #[test]
+    fn fit_pads_and_truncates_by_display_width() {
+        assert_eq!(fit("ab", 4), "ab  ");
+        assert_eq!(fit("abcdef", 4), "abc…");
+        // 山田 fills 4 cells, 太 wouldn't fit next to the ellipsis: pad the gap.
+        assert_eq!(fit("山田太郎", 6), "山田… ");
+        assert_eq!(width(&fit("山田太郎", 6)), 6);
+        assert_eq!(fit("x", 0), "");
+        let cut = fit(&format!("{RED}important{RESET}"), 5);
+        assert_eq!(cut, format!("{RED}impo…{RESET}"));
+        assert_eq!(width(&cut), 5);
+    }
+
+    #[test]
+    fn columns_line_up_across_scripts_emoji_and_color() {
+        let rows = vec![
+            vec!["U1".into(), "@山田太郎".into(), "Yamada".into()],
+            vec!["U2".into(), "@minjun🎉".into(), "김민준".into()],
+            vec!["U3".into(), format!("{RED}@alice{RESET}"), "Alice".into()],
+            vec![
+                "U4".into(),
+                "@a-very-long-handle-indeed".into(),
+                "Long".into(),
+            ],
+        ];
+        let out = render(&["ID", "NAME", "REAL"], &rows, &[None, Some(12), None]);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 5);
+        // The third column starts at the same cell on every line.
+        for (line, real) in lines
+            .iter()
+            .zip(["REAL", "Yamada", "김민준", "Alice", "Long"])
+        {
+            let at = line.rfind(real).unwrap();
+            assert_eq!(width(&line[..at]), 2 + 2 + 12 + 2, "{line:?}");
+        }
+        assert!(lines[4].contains("@a-very-lon…"), "{out}");
+        assert!(
+            lines[3].contains(&format!("{RED}@alice{RESET}      ")),
+            "{out}"
+        );
+        assert!(!out.lines().any(|l| l.ends_with(' ')));
+    }
+
+    #[test]
+    fn later_pages_keep_the_first_pages_widths() {
+        let header = ["ID", "NAME", "EMAIL"];
+        let first = vec![vec!["U1".into(), "@kim".into(), "kim@example.com".into()]];
+        let widths = widths(&header, &first, &[None, Some(8), None]);
+        assert_eq!(widths, [2, 4, 15]);
+        let later = vec![vec![
+            "U2".into(),
+            "@jane-doe".into(),
+            "jane.doe@example.com".into(),
+        ]];
+        assert_eq!(
+            render_rows(&later, &widths),
+            "U2  @ja…  jane.doe@example.com\n"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! `slack team`: the workspace's name, domain, ID and icon (team.info).
+
+use crate::output::outln;
+use crate::{ensure_token, http, invocation, slack_post, ReportFormat, TeamArgs};
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Debug, PartialEq, Serialize)]
+struct Team {
+    name: Option<String>,
+    domain: Option<String>,
+    id: Option<String>,
+    /// `https://<domain>.slack.com`.
+    url: Option<String>,
+    icon: Option<String>,
+}
+
+pub fn run(args: TeamArgs) -> Result<()> {
+    let token = ensure_token()?;
+    // team.info takes `team`, not `team_id`, so --team-id is passed along by hand.
+    let team_id = invocation().team_id;
+    let form: Vec<(&str, &str)> = team_id
+        .as_deref()
+        .map(|t| ("team", t))
+        .into_iter()
+        .collect();
+    let resp = slack_post(&http(), "team.info", &token, Some(&form))?;
+    let team = parse(resp.get("team").unwrap_or(&Value::Null));
+    match args.format {
+        ReportFormat::Json => outln!("{}", serde_json::to_string_pretty(&team)?),
+        ReportFormat::Text => {
+            let shown = [
+                ("name", &team.name),
+                ("domain", &team.domain),
+                ("id", &team.id),
+                ("url", &team.url),
+                ("icon", &team.icon),
+            ];
+            for (key, value) in shown {
+                outln!("{key}: {}", value.as_deref().unwrap_or("-"));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse(team: &Value) -> Team {
+    let str_at = |k: &str| {
+        team.get(k)
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    let domain = str_at("domain");
+    Team {
+        name: str_at("name"),
+        url: domain.as_ref().map(|d| format!("https://{d}.slack.com")),
+        domain,
+        id: str_at("id"),
+        icon: icon_url(team),
+    }
+}
+
+/// The largest icon Slack has, skipping the generated placeholder.
+fn icon_url(team: &Value) -> Option<String> {
+    let icon = team.get("icon")?;
+    if icon.get("image_default").and_then(|v| v.as_bool()) == Some(true) {
+        return None;
+    }
+    [
+        "image_original",
+        "image_230",
+        "image_132",
+        "image_88",
+        "image_68",
+    ]
+    .iter()
+    .find_map(|k| icon.get(*k).and_then(|v| v.as_str()))
+    .filter(|s| !s.is_empty())
+    .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_builds_the_url_and_picks_the_largest_icon() {
+        let team = parse(&json!({
+            "id": "T1", "name": "Acme", "domain": "acme",
+            "icon": { "image_68": "https://a/68.png", "image_230": "https://a/230.png" }
+        }));
+        assert_eq!(team.url.as_deref(), Some("https://acme.slack.com"));
+        assert_eq!(team.icon.as_deref(), Some("https://a/230.png"));
+        let plain =
+            parse(&json!({ "id": "T2", "icon": { "image_default": true, "image_68": "x" } }));
+        assert_eq!(plain.icon, None);
+        assert_eq!(plain.name, None);
+    }
+}
@@ -0,0 +1,545 @@
+//! Where the user token lives between runs.
+//!
+//! The OS keyring is preferred. On hosts without a keyring backend (headless Linux,
+//! containers) the token is kept in a passphrase-encrypted file under the config dir.
+//! The passphrase is asked for only by `init` and `unlock`; `unlock` leaves the derived
+//! key in the runtime dir so later commands can read the token without a prompt. A
+//! `key_file` set in config.toml replaces the passphrase altogether.
+
+use crate::output::errln;
+use anyhow::{anyhow, Context, Result};
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use clap::ValueEnum;
+use dialoguer::Password;
+use keyring::Entry;
+use std::path::{Path, PathBuf};
+
+const SERVICE: &str = "slackcli_user";
+const USERNAME: &str = "token";
+/// Profile whose token keeps the original, unsuffixed keyring entry and file name.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// File header; bump the trailing digit if the layout ever changes.
+const MAGIC: &[u8] = b"MDSLACK1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+
+/// Which backend holds the token (`--store`).
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StoreKind {
+    /// Keyring, falling back to the encrypted file when no keyring backend exists
+    #[default]
+    Auto,
+    /// OS keyring only (Credential Manager, Keychain or Secret Service)
+    Keyring,
+    /// Passphrase-encrypted file in the config dir
+    File,
+}
+
+pub trait TokenStore {
+    fn read(&self) -> Result<Option<String>>;
+    fn store(&self, token: &str) -> Result<()>;
+    fn delete(&self) -> Result<()>;
+}
+
+/// The store for `profile`'s token.
+pub fn open(kind: StoreKind, profile: &str) -> Result<Box<dyn TokenStore>> {
+    Ok(match kind {
+        StoreKind::Keyring => Box::new(KeyringStore::new(profile)),
+        StoreKind::File => Box::new(FileStore::for_profile(profile)?),
+        StoreKind::Auto => Box::new(AutoStore {
+            keyring: KeyringStore::new(profile),
+            file: FileStore::for_profile(profile)?,
+        }),
+    })
+}
+
+pub struct KeyringStore {
+    username: String,
+}
+
+impl KeyringStore {
+    fn new(profile: &str) -> Self {
+        let username = if profile == DEFAULT_PROFILE {
+            USERNAME.to_string()
+        } else {
+            format!("{USERNAME}:{profile}")
+        };
+        KeyringStore { username }
+    }
+
+    fn entry(&self) -> Result<Entry> {
+        let entry = Entry::new(SERVICE, &self.username).context("keyring unavailable")?;
+        // keyring hands out its in-memory mock on platforms it has no native store for;
+        // a token kept there is gone when the process exits.
+        if entry.get_credential().is::<keyring::mock::MockCredential>() {
+            return Err(anyhow::Error::new(keyring::Error::NoStorageAccess(
+                "no native keyring on this platform".into(),
+            )));
+        }
+        Ok(entry)
+    }
+}
+
+impl TokenStore for KeyringStore {
+    fn read(&self) -> Result<Option<String>> {
+        match self.entry()?.get_password() {
+            Ok(s) if s.trim().is_empty() => Ok(None),
+            Ok(s) => Ok(Some(s)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(anyhow::Error::new(e).context("keyring read error")),
+        }
+    }
+
+    fn store(&self, token: &str) -> Result<()> {
+        self.entry()?
+            .set_password(token)
+            .map_err(|e| anyhow::Error::new(e).context("keyring write error"))
+    }
+
+    fn delete(&self) -> Result<()> {
+        self.entry()?
+            .set_password("")
+            .map_err(|e| anyhow::Error::new(e).context("keyring delete/overwrite error"))
+    }
+}
+
+/// True when the failure means "there is no keyring here", not "the keyring said no".
+fn keyring_unavailable(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<keyring::Error>(),
+        Some(keyring::Error::PlatformFailure(_) | keyring::Error::NoStorageAccess(_))
+    )
+}
+
+struct AutoStore {
+    keyring: KeyringStore,
+    file: FileStore,
+}
+
+impl AutoStore {
+    fn with<T>(&self, op: impl Fn(&dyn TokenStore) -> Result<T>) -> Result<T> {
+        match op(&self.keyring) {
+            Err(e) if keyring_unavailable(&e) => {
+                errln!(
+                    "No keyring backend available; using encrypted token file {}",
+                    self.file.path.display()
+                );
+                op(&self.file)
+            }
+            other => other,
+        }
+    }
+}
+
+impl TokenStore for AutoStore {
+    fn read(&self) -> Result<Option<String>> {
+        self.with(|s| s.read())
+    }
+
+    fn store(&self, token: &str) -> Result<()> {
+        self.with(|s| s.store(token))
+    }
+
+    fn delete(&self) -> Result<()> {
+        self.with(|s| s.delete())
+    }
+}
+
+/// Token encrypted with ChaCha20-Poly1305 under an Argon2id key derived from a passphrase
+/// or from the contents of a key file (`key_file` in config.toml).
+pub struct FileStore {
+    path: PathBuf,
+    key_file: Option<PathBuf>,
+    /// Where `unlock` leaves the derived key (salt | key) for passphrase-protected files.
+    unlocked: PathBuf,
+    /// For error messages: the `--profile` flag to repeat, if any.
+    profile_flag: String,
+}
+
+impl FileStore {
+    pub fn new(path: PathBuf, key_file: Option<PathBuf>, unlocked: PathBuf) -> Self {
+        FileStore {
+            path,
+            key_file,
+            unlocked,
+            profile_flag: String::new(),
+        }
+    }
+
+    fn for_profile(profile: &str) -> Result<Self> {
+        let stem = if profile == DEFAULT_PROFILE {
+            "token".to_string()
+        } else {
+            format!("token-{profile}")
+        };
+        let config_dir = crate::config_dir().unwrap_or_else(|| PathBuf::from("."));
+        // Relative key_file paths are taken from the config dir, next to config.toml.
+        let key_file = crate::config::load()?
+            .key_file
+            .map(|kf| config_dir.join(kf));
+        let mut store = FileStore::new(
+            config_dir.join(format!("{stem}.enc")),
+            key_file,
+            runtime_dir().join(format!("{stem}.unlocked")),
+        );
+        if profile != DEFAULT_PROFILE {
+            store.profile_flag = format!(" --profile {profile}");
+        }
+        Ok(store)
+    }
+
+    fn key_file_secret(&self, kf: &Path) -> Result<Vec<u8>> {
+        let bytes = std::fs::read(kf)
+            .with_context(|| format!("failed to read key file {}", kf.display()))?;
+        if bytes.is_empty() {
+            return Err(anyhow!("key file {} is empty", kf.display()));
+        }
+        Ok(bytes)
+    }
+
+    /// The key `unlock` left for this file, if it is still the one the file was sealed
+    /// with (a re-init changes the salt).
+    fn unlocked_key(&self, data: &[u8]) -> Option<Key> {
+        let (salt, _, _) = split_sealed(data).ok()?;
+        let cached = std::fs::read(&self.unlocked).ok()?;
+        let (cached_salt, key) = cached.split_at_checked(SALT_LEN)?;
+        (cached_salt == salt && key.len() == KEY_LEN).then(|| *Key::from_slice(key))
+    }
+
+    fn remember_key(&self, salt: &[u8], key: &Key) -> Result<()> {
+        if let Some(dir) = self.unlocked.parent() {
+            std::fs::create_dir_all(dir).context("failed to create runtime dir")?;
+        }
+        let mut data = salt.to_vec();
+        data.extend_from_slice(key);
+        write_private(&self.unlocked, &data).context("failed to save the unlocked key")
+    }
+
+    fn forget_key(&self) -> Result<bool> {
+        match std::fs::remove_file(&self.unlocked) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(anyhow::Error::new(e).context("failed to remove the unlocked key")),
+        }
+    }
+
+    /// Ask for the passphrase, check it against the file and keep its key for later
+    /// commands.
+    pub fn unlock(&self) -> Result<()> {
+        if let Some(kf) = &self.key_file {
+            return Err(anyhow!(
+                "the token file is opened with key_file {}; there's nothing to unlock",
+                kf.display()
+            ));
+        }
+        let data = match std::fs::read(&self.path) {
+            Ok(d) => d,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(anyhow!(
+                    "no encrypted token file at {}; run `slack{} --store file init` first",
+                    self.path.display(),
+                    self.profile_flag
+                ));
+            }
+            Err(e) => return Err(anyhow::Error::new(e).context("token file read error")),
+        };
+        let (salt, _, _) = split_sealed(&data)?;
+        let key = derive_key(&prompt_passphrase(false)?, salt)?;
+        decrypt_with_key(&key, &data)?;
+        self.remember_key(salt, &key)
+    }
+
+    /// Drop the key `unlock` left; false if there was none.
+    pub fn lock(&self) -> Result<bool> {
+        self.forget_key()
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// The file store for `profile`, for `unlock` and `lock`.
+pub fn file_store(profile: &str) -> Result<FileStore> {
+    FileStore::for_profile(profile)
+}
+
+/// Per-session directory for unlocked keys: `$XDG_RUNTIME_DIR` (a tmpfs cleared at
+/// logout) where there is one, else the state dir.
+fn runtime_dir() -> PathBuf {
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .filter(|d| !d.is_empty())
+        .map(|d| PathBuf::from(d).join("mdslackcli"))
+        .or_else(crate::state_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+fn prompt_passphrase(creating: bool) -> Result<Vec<u8>> {
+    let mut prompt = Password::new().with_prompt("Passphrase for the token file");
+    if creating {
+        prompt = prompt.with_confirmation("Repeat passphrase", "Passphrases do not match");
+    }
+    let pass = prompt
+        .interact()
+        .context("failed to read passphrase from prompt")?;
+    if pass.is_empty() {
+        return Err(anyhow!("Passphrase must not be empty"));
+    }
+    Ok(pass.into_bytes())
+}
+
+impl TokenStore for FileStore {
+    fn read(&self) -> Result<Option<String>> {
+        let data = match std::fs::read(&self.path) {
+            Ok(d) => d,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(anyhow::Error::new(e).context("token file read error")),
+        };
+        let token = match &self.key_file {
+            Some(kf) => decrypt_token(&self.key_file_secret(kf)?, &data)?,
+            None => {
+                split_sealed(&data)?;
+                let key = self.unlocked_key(&data).ok_or_else(|| {
+                    anyhow!(
+                        "the token file {} is locked; run `slack{} unlock` (or set key_file in config.toml)",
+                        self.path.display(),
+                        self.profile_flag
+                    )
+                })?;
+                decrypt_with_key(&key, &data)?
+            }
+        };
+        Ok(Some(token).filter(|t| !t.trim().is_empty()))
+    }
+
+    fn store(&self, token: &str) -> Result<()> {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let key = match &self.key_file {
+            Some(kf) => derive_key(&self.key_file_secret(kf)?, &salt)?,
+            None => derive_key(&prompt_passphrase(true)?, &salt)?,
+        };
+        let data = seal(&key, &salt, token)?;
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).context("failed to create config dir")?;
+        }
+        write_private(&self.path, &data).context("token file write error")?;
+        // Typing the passphrase at init counts as unlocking.
+        if self.key_file.is_none() {
+            self.remember_key(&salt, &key)?;
+        }
+        Ok(())
+    }
+
+    fn delete(&self) -> Result<()> {
+        self.forget_key()?;
+        match std::fs::remove_file(&self.path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(anyhow::Error::new(e).context("token file delete error"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn write_private(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?
+        .write_all(data)
+}
+
+#[cfg(not(unix))]
+pub(crate) fn write_private(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    std::fs::write(path, data)
+}
+
+fn derive_key(secret: &[u8], salt: &[u8]) -> Result<Key> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(secret, salt, &mut key)
+        .map_err(|e| anyhow!("key derivation failed: {e}"))?;
+    Ok(key)
+}
+
+#[cfg(test)]
+fn encrypt_token(secret: &[u8], token: &str) -> Result<Vec<u8>> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    seal(&derive_key(secret, &salt)?, &salt, token)
+}
+
+/// Layout: MAGIC | salt | nonce | ciphertext+tag.
+fn seal(key: &Key, salt: &[u8], token: &str) -> Result<Vec<u8>> {
+    let cipher = ChaCha20Poly1305::new(key);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ct = cipher
+        .encrypt(&nonce, token.as_bytes())
+        .map_err(|_| anyhow!("token encryption failed"))?;
+    let mut out = Vec::with_capacity(MAGIC.len() + SALT_LEN + NONCE_LEN + ct.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(salt);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ct);
+    Ok(out)
+}
+
+fn corrupted() -> anyhow::Error {
+    anyhow!("token file is corrupted or not an mdslackcli token file")
+}
+
+/// (salt, nonce, ciphertext+tag) of a token file.
+fn split_sealed(data: &[u8]) -> Result<(&[u8], &[u8], &[u8])> {
+    let rest = data.strip_prefix(MAGIC).ok_or_else(corrupted)?;
+    // Poly1305 tag is 16 bytes; anything shorter cannot be a valid ciphertext.
+    if rest.len() < SALT_LEN + NONCE_LEN + 16 {
+        return Err(corrupted());
+    }
+    let (salt, rest) = rest.split_at(SALT_LEN);
+    let (nonce, ct) = rest.split_at(NONCE_LEN);
+    Ok((salt, nonce, ct))
+}
+
+fn decrypt_token(secret: &[u8], data: &[u8]) -> Result<String> {
+    let (salt, _, _) = split_sealed(data)?;
+    decrypt_with_key(&derive_key(secret, salt)?, data)
+}
+
+fn decrypt_with_key(key: &Key, data: &[u8]) -> Result<String> {
+    let (_, nonce, ct) = split_sealed(data)?;
+    let pt = ChaCha20Poly1305::new(key)
+        .decrypt(Nonce::from_slice(nonce), ct)
+        .map_err(|_| {
+            anyhow!("could not decrypt token file: wrong passphrase or the file was modified")
+        })?;
+    String::from_utf8(pt).map_err(|_| corrupted())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let data = encrypt_token(b"hunter2", "xoxp-1-2-3").unwrap();
+        assert!(data.starts_with(MAGIC));
+        assert!(!data.windows(10).any(|w| w == b"xoxp-1-2-3"));
+        assert_eq!(decrypt_token(b"hunter2", &data).unwrap(), "xoxp-1-2-3");
+    }
+
+    #[test]
+    fn fresh_salt_and_nonce_each_time() {
+        let a = encrypt_token(b"pw", "xoxp-1").unwrap();
+        let b = encrypt_token(b"pw", "xoxp-1").unwrap();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn wrong_passphrase_is_reported() {
+        let data = encrypt_token(b"right", "xoxp-1").unwrap();
+        let err = decrypt_token(b"wrong", &data).unwrap_err();
+        assert!(err.to_string().contains("wrong passphrase"));
+    }
+
+    #[test]
+    fn corrupted_files_are_rejected() {
+        let data = encrypt_token(b"pw", "xoxp-1").unwrap();
+
+        let err = decrypt_token(b"pw", b"not a token file").unwrap_err();
+        assert!(err.to_string().contains("corrupted"));
+
+        let err = decrypt_token(b"pw", &data[..MAGIC.len() + 8]).unwrap_err();
+        assert!(err.to_string().contains("corrupted"));
+
+        // A flipped ciphertext bit fails authentication.
+        let mut flipped = data.clone();
+        *flipped.last_mut().unwrap() ^= 0x01;
+        assert!(decrypt_token(b"pw", &flipped).is_err());
+    }
+
+    #[test]
+    fn file_store_with_key_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = dir.path().join("key");
+        std::fs::write(&key, b"0123456789abcdef").unwrap();
+        let store = FileStore::new(
+            dir.path().join("cfg/token.enc"),
+            Some(key.clone()),
+            dir.path().join("run/token.unlocked"),
+        );
+
+        assert_eq!(store.read().unwrap(), None);
+        store.store("xoxp-file").unwrap();
+        assert_eq!(store.read().unwrap().as_deref(), Some("xoxp-file"));
+
+        let other = FileStore::new(
+            dir.path().join("cfg/token.enc"),
+            Some(dir.path().join("nope")),
+            dir.path().join("run/token.unlocked"),
+        );
+        assert!(other.read().is_err());
+
+        std::fs::write(&key, b"a different key").unwrap();
+        let err = store.read().unwrap_err();
+        assert!(err.to_string().contains("wrong passphrase"));
+
+        store.delete().unwrap();
+        assert_eq!(store.read().unwrap(), None);
+        // Deleting twice is fine.
+        store.delete().unwrap();
+    }
+
+    #[test]
+    fn empty_key_file_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = dir.path().join("key");
+        std::fs::write(&key, b"").unwrap();
+        let store = FileStore::new(
+            dir.path().join("token.enc"),
+            Some(key),
+            dir.path().join("u"),
+        );
+        assert!(store.store("xoxp-1").is_err());
+    }
+
+    #[test]
+    fn passphrase_files_need_unlocking_and_lock_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let unlocked = dir.path().join("run/token.unlocked");
+        let store = FileStore::new(dir.path().join("token.enc"), None, unlocked.clone());
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let key = derive_key(b"hunter2", &salt).unwrap();
+        std::fs::write(store.path(), seal(&key, &salt, "xoxp-locked").unwrap()).unwrap();
+
+        let err = store.read().unwrap_err();
+        assert!(
+            err.to_string().contains("is locked; run `slack unlock`"),
+            "{err}"
+        );
+
+        store.remember_key(&salt, &key).unwrap();
+        assert_eq!(store.read().unwrap().as_deref(), Some("xoxp-locked"));
+
+        // A key for an older file (different salt) doesn't count.
+        let mut other_salt = salt;
+        other_salt[0] ^= 0xff;
+        store.remember_key(&other_salt, &key).unwrap();
+        assert!(store.read().is_err());
+
+        assert!(store.lock().unwrap());
+        assert!(!unlocked.exists());
+        assert!(!store.lock().unwrap());
+    }
+}
@@ -0,0 +1,231 @@
+//! `slack profile set`: write fields of a Slack user profile, standard or custom; and
+//! `slack profile-fields`: the workspace's custom field schema.
+
+use crate::output::outln;
+use crate::{ensure_token, http, slack_post, ProfileSetArgs, SlackError};
+use anyhow::{anyhow, Result};
+use reqwest::blocking::Client;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Fields users.profile.set takes at the top level of `profile`; any other name is looked
+/// up among the workspace's custom fields.
+const STANDARD_FIELDS: &[&str] = &[
+    "display_name",
+    "real_name",
+    "first_name",
+    "last_name",
+    "title",
+    "phone",
+    "email",
+    "status_text",
+    "status_emoji",
+    "status_expiration",
+];
+
+/// One custom profile field from team.profile.get.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ProfileField {
+    pub id: String,
+    pub label: String,
+    /// text, long_text, date, link, options_list or user.
+    pub kind: String,
+    /// The choices of an options_list field; empty for the others.
+    pub options: Vec<String>,
+}
+
+pub fn fields() -> Result<()> {
+    let token = ensure_token()?;
+    let fields = custom_fields(&http(), &token)?;
+    if fields.is_empty() {
+        outln!("no custom profile fields");
+        return Ok(());
+    }
+    for f in &fields {
+        let options = if f.options.is_empty() {
+            "-".to_string()
+        } else {
+            f.options.join(", ")
+        };
+        outln!("{}\t{}\t{}\t{options}", f.id, f.label, f.kind);
+    }
+    Ok(())
+}
+
+pub fn set(args: ProfileSetArgs) -> Result<()> {
+    let pairs = args
+        .fields
+        .iter()
+        .map(|f| {
+            f.split_once('=')
+                .map(|(k, v)| (k.trim(), v))
+                .filter(|(k, _)| !k.is_empty())
+                .ok_or_else(|| anyhow!("--field must be name=value, got `{f}`"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let token = ensure_token()?;
+    let client = http();
+    let custom = if pairs.iter().any(|(k, _)| !STANDARD_FIELDS.contains(k)) {
+        custom_fields(&client, &token)?
+    } else {
+        Vec::new()
+    };
+    let (profile, labels) = build(&pairs, &custom)?;
+
+    let profile_json = Value::Object(profile).to_string();
+    let mut form = vec![("profile", profile_json.as_str())];
+    if let Some(user) = args.user.as_deref() {
+        form.push(("user", user));
+    }
+    let resp = slack_post(&client, "users.profile.set", &token, Some(&form))
+        .map_err(|e| explain(e, args.user.as_deref()))?;
+    let updated = resp.get("profile").cloned().unwrap_or_default();
+    for (label, ptr) in labels {
+        let value = updated
+            .pointer(&ptr)
+            .and_then(|v| v.as_str())
+            .unwrap_or("-");
+        outln!("{label}: {value}");
+    }
+    Ok(())
+}
+
+/// A field's label and the JSON pointer to its value in the profile Slack sends back.
+type Shown = (String, String);
+
+/// The workspace's custom profile fields (team.profile.get). The schema rarely changes,
+/// so it is fetched once per token for the life of the process (which matters for the
+/// daemon).
+pub(crate) fn custom_fields(client: &Client, token: &str) -> Result<Vec<ProfileField>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, Vec<ProfileField>>>> = OnceLock::new();
+    let cache = CACHE.get_or_init(Default::default);
+    if let Some(fields) = cache.lock().unwrap().get(token) {
+        return Ok(fields.clone());
+    }
+    let resp = slack_post(client, "team.profile.get", token, None)?;
+    let fields = parse_fields(&resp);
+    cache
+        .lock()
+        .unwrap()
+        .insert(token.to_string(), fields.clone());
+    Ok(fields)
+}
+
+fn parse_fields(resp: &Value) -> Vec<ProfileField> {
+    let mut fields: Vec<(i64, ProfileField)> = resp
+        .pointer("/profile/fields")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|f| {
+            let str_at = |k: &str| f.get(k).and_then(|v| v.as_str()).unwrap_or("");
+            let id = f.get("id")?.as_str()?.to_string();
+            let options = f
+                .get("possible_values")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect();
+            let ordering = f.get("ordering").and_then(|v| v.as_i64()).unwrap_or(0);
+            Some((
+                ordering,
+                ProfileField {
+                    id,
+                    label: str_at("label").to_string(),
+                    kind: str_at("type").to_string(),
+                    options,
+                },
+            ))
+        })
+        .collect();
+    // Slack's own order, as the fields appear on a profile.
+    fields.sort_by_key(|(ordering, _)| *ordering);
+    fields.into_iter().map(|(_, f)| f).collect()
+}
+
+/// The `profile` object to send, and how to show each field once set. Custom fields
+/// match by label (any case) or ID.
+fn build(
+    pairs: &[(&str, &str)],
+    custom: &[ProfileField],
+) -> Result<(Map<String, Value>, Vec<Shown>)> {
+    let mut profile = Map::new();
+    let mut fields = Map::new();
+    let mut labels = Vec::new();
+    for &(name, value) in pairs {
+        if STANDARD_FIELDS.contains(&name) {
+            profile.insert(name.to_string(), json!(value));
+            labels.push((name.to_string(), format!("/{name}")));
+            continue;
+        }
+        let field = custom
+            .iter()
+            .find(|f| f.label.eq_ignore_ascii_case(name) || f.id == name)
+            .ok_or_else(|| {
+                let mut known: Vec<&str> = STANDARD_FIELDS.to_vec();
+                known.extend(custom.iter().map(|f| f.label.as_str()));
+                anyhow!(
+                    "unknown profile field `{name}`; known: {}",
+                    known.join(", ")
+                )
+            })?;
+        fields.insert(field.id.clone(), json!({ "value": value, "alt": "" }));
+        labels.push((field.label.clone(), format!("/fields/{}/value", field.id)));
+    }
+    if !fields.is_empty() {
+        profile.insert("fields".to_string(), Value::Object(fields));
+    }
+    Ok((profile, labels))
+}
+
+fn explain(e: anyhow::Error, user: Option<&str>) -> anyhow::Error {
+    match (SlackError::code_of(&e), user) {
+        (Some("not_admin" | "cannot_update_admin_user" | "missing_scope"), Some(user)) => e
+            .context(format!(
+                "setting {user}'s profile needs an admin's user token with users.profile:write"
+            )),
+        (Some("invalid_profile"), _) => {
+            e.context("Slack rejected a value; some fields are locked by your admins")
+        }
+        _ => e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_names_go_top_level_and_the_rest_to_fields() {
+        let field = |id: &str, label: &str| ProfileField {
+            id: id.to_string(),
+            label: label.to_string(),
+            kind: "text".to_string(),
+            options: Vec::new(),
+        };
+        let custom = vec![field("Xf01", "Pronouns"), field("Xf02", "Cost center")];
+        let (profile, labels) = build(
+            &[("title", "Staff Engineer"), ("pronouns", "they/them")],
+            &custom,
+        )
+        .unwrap();
+        assert_eq!(
+            Value::Object(profile),
+            json!({
+                "title": "Staff Engineer",
+                "fields": { "Xf01": { "value": "they/them", "alt": "" } }
+            })
+        );
+        assert_eq!(
+            labels,
+            [
+                ("title".to_string(), "/title".to_string()),
+                ("Pronouns".to_string(), "/fields/Xf01/value".to_string())
+            ]
+        );
+        let err = build(&[("shoe size", "44")], &custom).unwrap_err();
+        assert!(err.to_string().contains("Cost center"), "{err}");
+    }
+}
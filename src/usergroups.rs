@@ -0,0 +1,463 @@
+//! `slack usergroups`, `usergroup-members`, `usergroup-create` and `usergroup-update`:
+//! the workspace's user groups and who is in them.
+
+use crate::output::{out, outln};
+use crate::{
+    each_page, ensure_token, fetch_users_map, http, slack_post, table, ReportFormat, SlackError,
+    UsergroupCreateArgs, UsergroupMembersArgs, UsergroupUpdateArgs, UsergroupsArgs,
+};
+use anyhow::{anyhow, Result};
+use reqwest::blocking::Client;
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct Usergroup {
+    pub id: String,
+    /// Without the `@`.
+    pub handle: String,
+    pub name: String,
+    pub members: Option<u64>,
+    pub disabled: bool,
+}
+
+pub fn list(args: UsergroupsArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let groups = fetch_usergroups(&http(), &token, args.include_disabled)?;
+    match args.format {
+        ReportFormat::Json => outln!("{}", serde_json::to_string_pretty(&groups)?),
+        ReportFormat::Text if groups.is_empty() => outln!("no user groups"),
+        ReportFormat::Text => {
+            let rows: Vec<Vec<String>> = groups
+                .iter()
+                .map(|g| {
+                    vec![
+                        g.id.clone(),
+                        format!("@{}", g.handle),
+                        g.name.clone(),
+                        g.members.map_or("-".to_string(), |n| n.to_string()),
+                        if g.disabled { "disabled" } else { "active" }.to_string(),
+                    ]
+                })
+                .collect();
+            out!(
+                "{}",
+                table::render(
+                    &["ID", "HANDLE", "NAME", "MEMBERS", "STATUS"],
+                    &rows,
+                    &[None, Some(32), Some(40), None, None]
+                )
+            );
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct Member {
+    id: String,
+    display_name: Option<String>,
+    real_name: Option<String>,
+    email: Option<String>,
+}
+
+pub fn members_of(args: UsergroupMembersArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let group = resolve_group(&client, &token, &args.group)?;
+    let ids = members(&client, &token, &group)?;
+    let users = if args.resolve && !ids.is_empty() {
+        fetch_users_map(&client, &token)?
+    } else {
+        Default::default()
+    };
+    let members: Vec<Member> = ids
+        .into_iter()
+        .map(|id| {
+            let user = users.get(&id);
+            Member {
+                display_name: user.map(|u| u.display_name.clone()),
+                real_name: user.and_then(|u| u.real_name.clone()),
+                email: user.and_then(|u| u.email.clone()),
+                id,
+            }
+        })
+        .collect();
+    match args.format {
+        ReportFormat::Json => outln!("{}", serde_json::to_string_pretty(&members)?),
+        ReportFormat::Text if members.is_empty() => outln!("{} has no members", args.group),
+        ReportFormat::Text => {
+            for m in &members {
+                if !args.resolve {
+                    outln!("{}", m.id);
+                    continue;
+                }
+                let or_dash = |s: &Option<String>| s.clone().unwrap_or_else(|| "-".to_string());
+                outln!(
+                    "{}\t{}\t{}\t{}",
+                    m.id,
+                    or_dash(&m.display_name),
+                    or_dash(&m.real_name),
+                    or_dash(&m.email)
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn create(args: UsergroupCreateArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let handle = args.handle.trim_start_matches('@');
+    let channels = args.channels.join(",");
+    let mut form = vec![("name", args.name.as_str()), ("handle", handle)];
+    if !channels.is_empty() {
+        form.push(("channels", channels.as_str()));
+    }
+    if let Some(description) = args.description.as_deref() {
+        form.push(("description", description));
+    }
+    let resp = slack_post(&http(), "usergroups.create", &token, Some(&form)).map_err(explain)?;
+    let group = parse(resp.get("usergroup").unwrap_or(&Value::Null));
+    outln!("created {} @{} ({})", group.id, group.handle, group.name);
+    Ok(())
+}
+
+pub fn update(args: UsergroupUpdateArgs) -> Result<()> {
+    let changes_members = !args.add_users.is_empty() || !args.remove_users.is_empty();
+    if !changes_members && !args.enable && !args.disable {
+        return Err(anyhow!(
+            "nothing to change; pass --add-users, --remove-users, --enable or --disable"
+        ));
+    }
+    let token = ensure_token()?;
+    let client = http();
+    let group = resolve_group(&client, &token, &args.group)?;
+    let form = [("usergroup", group.as_str())];
+    // Enable first so a re-enabled group can take its new members; disable last so a
+    // group can be trimmed and then retired in one go.
+    if args.enable {
+        slack_post(&client, "usergroups.enable", &token, Some(&form)).map_err(explain)?;
+        outln!("enabled {group}");
+    }
+    if changes_members {
+        let current = members(&client, &token, &group)?;
+        let (wanted, added, removed) = apply_delta(&current, &args.add_users, &args.remove_users);
+        if wanted.is_empty() {
+            return Err(anyhow!(
+                "that would leave {group} with no members, which Slack doesn't allow; \
+                 use --disable to retire it"
+            ));
+        }
+        let users = wanted.join(",");
+        let resp = slack_post(
+            &client,
+            "usergroups.users.update",
+            &token,
+            Some(&[("usergroup", group.as_str()), ("users", users.as_str())]),
+        )
+        .map_err(explain)?;
+        let now: Vec<&str> = resp
+            .pointer("/usergroup/users")
+            .and_then(|v| v.as_array())
+            .map(|a| a.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_else(|| wanted.iter().map(String::as_str).collect());
+        outln!("added: {}", list_or_none(&added));
+        outln!("removed: {}", list_or_none(&removed));
+        outln!("members ({}): {}", now.len(), now.join(","));
+    }
+    if args.disable {
+        slack_post(&client, "usergroups.disable", &token, Some(&form)).map_err(explain)?;
+        outln!("disabled {group}");
+    }
+    Ok(())
+}
+
+fn list_or_none(ids: &[String]) -> String {
+    if ids.is_empty() {
+        "-".to_string()
+    } else {
+        ids.join(",")
+    }
+}
+
+/// The new member list, then the IDs actually added and actually removed.
+type Delta = (Vec<String>, Vec<String>, Vec<String>);
+
+/// The membership after adding `add` and removing `remove` from `current`, in the
+/// current order with new members at the end.
+fn apply_delta(current: &[String], add: &[String], remove: &[String]) -> Delta {
+    let mut wanted: Vec<String> = Vec::new();
+    let mut removed = Vec::new();
+    for id in current {
+        if remove.contains(id) {
+            removed.push(id.clone());
+        } else if !wanted.contains(id) {
+            wanted.push(id.clone());
+        }
+    }
+    let mut added = Vec::new();
+    for id in add {
+        if !wanted.contains(id) && !remove.contains(id) {
+            wanted.push(id.clone());
+            added.push(id.clone());
+        }
+    }
+    (wanted, added, removed)
+}
+
+/// `text` with each `{group:handle}` placeholder replaced by that group's mention, and a
+/// mention for each of `prepend` not already placed that way put in front. Every handle
+/// is checked before anything is returned, so a typo fails before a message goes out.
+pub(crate) fn expand_group_mentions(
+    client: &Client,
+    token: &str,
+    text: &str,
+    prepend: &[String],
+) -> Result<String> {
+    let placeholders = placeholders(text);
+    if prepend.is_empty() && placeholders.is_empty() {
+        return Ok(text.to_string());
+    }
+    let groups = fetch_usergroups(client, token, false)?;
+    let mention = |handle: &str| -> Result<String> {
+        let handle = handle.trim_start_matches('@');
+        match groups
+            .iter()
+            .find(|g| g.handle.eq_ignore_ascii_case(handle))
+        {
+            Some(g) => Ok(format!("<!subteam^{}|@{}>", g.id, g.handle)),
+            None => {
+                let close = close_matches(handle, groups.iter().map(|g| g.handle.as_str()));
+                Err(if close.is_empty() {
+                    anyhow!("no user group with handle @{handle}; `slack usergroups` lists them")
+                } else {
+                    anyhow!(
+                        "no user group with handle @{handle}; did you mean {}?",
+                        close
+                            .iter()
+                            .map(|h| format!("@{h}"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                })
+            }
+        }
+    };
+    let mut out = text.to_string();
+    for (placeholder, handle) in &placeholders {
+        out = out.replace(placeholder.as_str(), &mention(handle)?);
+    }
+    let placed = |h: &str| {
+        let h = h.trim_start_matches('@');
+        placeholders
+            .iter()
+            .any(|(_, p)| p.trim_start_matches('@').eq_ignore_ascii_case(h))
+    };
+    let mut front = Vec::new();
+    for handle in prepend.iter().filter(|h| !placed(h)) {
+        front.push(mention(handle)?);
+    }
+    if !front.is_empty() {
+        out = format!("{} {out}", front.join(" "));
+    }
+    Ok(out)
+}
+
+/// The `{group:handle}` placeholders in `text`, as (placeholder, handle).
+fn placeholders(text: &str) -> Vec<(String, String)> {
+    let mut found = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{group:") {
+        let after = &rest[start + "{group:".len()..];
+        let Some(end) = after.find('}') else {
+            break;
+        };
+        let handle = after[..end].trim();
+        if !handle.is_empty() {
+            found.push((format!("{{group:{}}}", &after[..end]), handle.to_string()));
+        }
+        rest = &after[end + 1..];
+    }
+    found
+}
+
+/// Handles that `handle` is probably a typo of: containing it, contained in it, or at
+/// most two edits away.
+fn close_matches<'a>(handle: &str, handles: impl Iterator<Item = &'a str>) -> Vec<&'a str> {
+    let wanted = handle.to_lowercase();
+    handles
+        .filter(|h| {
+            let h = h.to_lowercase();
+            h.contains(&wanted) || wanted.contains(&h) || edit_distance(&h, &wanted) <= 2
+        })
+        .collect()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                prev
+            } else {
+                1 + prev.min(cur).min(row[j])
+            };
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
+
+/// Member IDs of a user group (usergroups.users.list).
+pub(crate) fn members(client: &Client, token: &str, group: &str) -> Result<Vec<String>> {
+    let resp = slack_post(
+        client,
+        "usergroups.users.list",
+        token,
+        Some(&[("usergroup", group)]),
+    )
+    .map_err(explain)?;
+    Ok(resp
+        .get("users")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_str().map(str::to_string))
+        .collect())
+}
+
+/// A group ID as given (`S0123…`), or the ID of the group whose handle matches
+/// `@backend-team` / `backend-team` (any case, disabled groups included).
+pub(crate) fn resolve_group(client: &Client, token: &str, group: &str) -> Result<String> {
+    let looks_like_id = group.starts_with('S')
+        && group.len() > 1
+        && group
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+    if looks_like_id {
+        return Ok(group.to_string());
+    }
+    let handle = group.trim_start_matches('@');
+    fetch_usergroups(client, token, true)?
+        .into_iter()
+        .find(|g| g.handle.eq_ignore_ascii_case(handle))
+        .map(|g| g.id)
+        .ok_or_else(|| {
+            anyhow!("no user group with handle @{handle}; `slack usergroups` lists them")
+        })
+}
+
+/// Every user group, with member counts; disabled ones only when asked for.
+/// usergroups.list answers in one response today, but follows a cursor if it sends one.
+pub(crate) fn fetch_usergroups(
+    client: &Client,
+    token: &str,
+    include_disabled: bool,
+) -> Result<Vec<Usergroup>> {
+    let form = [
+        ("include_count", "true"),
+        (
+            "include_disabled",
+            if include_disabled { "true" } else { "false" },
+        ),
+    ];
+    let mut groups = Vec::new();
+    each_page(
+        client,
+        token,
+        "usergroups.list",
+        &form,
+        "usergroups",
+        |page| {
+            groups.extend(page.iter().map(parse));
+            true
+        },
+    )
+    .map_err(explain)?;
+    Ok(groups)
+}
+
+fn parse(g: &Value) -> Usergroup {
+    let str_at = |k: &str| g.get(k).and_then(|v| v.as_str()).unwrap_or("").to_string();
+    Usergroup {
+        id: str_at("id"),
+        handle: str_at("handle"),
+        name: str_at("name"),
+        members: g.get("user_count").and_then(|v| {
+            v.as_u64()
+                .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+        }),
+        // A disabled group has a non-zero date_delete.
+        disabled: g.get("date_delete").and_then(|v| v.as_i64()).unwrap_or(0) > 0,
+    }
+}
+
+fn explain(e: anyhow::Error) -> anyhow::Error {
+    match SlackError::code_of(&e) {
+        Some("missing_scope") => {
+            e.context("user groups need the usergroups:read scope; add it to the app and run `slack init --force`")
+        }
+        Some("permission_denied" | "not_allowed_token_type") => e.context(
+            "changing user groups needs usergroups:write, and your workspace may allow only \
+             admins to do it",
+        ),
+        Some("name_already_exists" | "handle_already_exists") => {
+            e.context("another user group already uses that name or handle")
+        }
+        Some("no_such_subteam") => {
+            e.context("no such user group; `slack usergroups` lists their IDs and handles")
+        }
+        _ => e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn placeholders_and_close_matches() {
+        assert_eq!(
+            placeholders("ping {group:backend} and {group:@sre}, not {group:}"),
+            [
+                ("{group:backend}".to_string(), "backend".to_string()),
+                ("{group:@sre}".to_string(), "@sre".to_string())
+            ]
+        );
+        let handles = ["backend-team", "frontend", "sre"];
+        assert_eq!(
+            close_matches("backend", handles.into_iter()),
+            ["backend-team"]
+        );
+        assert_eq!(close_matches("srr", handles.into_iter()), ["sre"]);
+        assert!(close_matches("design", handles.into_iter()).is_empty());
+    }
+
+    #[test]
+    fn apply_delta_keeps_order_and_reports_real_changes() {
+        let ids = |s: &str| -> Vec<String> { s.split(',').map(str::to_string).collect() };
+        let (wanted, added, removed) = apply_delta(&ids("U1,U2,U3"), &ids("U2,U4"), &ids("U3,U9"));
+        assert_eq!(wanted, ids("U1,U2,U4"));
+        assert_eq!(added, ids("U4"));
+        assert_eq!(removed, ids("U3"));
+    }
+
+    #[test]
+    fn parse_reads_count_and_disabled() {
+        let g = parse(&json!({
+            "id": "S1", "handle": "backend-team", "name": "Backend",
+            "user_count": "12", "date_delete": 0
+        }));
+        assert_eq!(g.members, Some(12));
+        assert!(!g.disabled);
+        let old = parse(&json!({ "id": "S2", "handle": "old", "date_delete": 1712345678 }));
+        assert_eq!(old.members, None);
+        assert!(old.disabled);
+    }
+}
@@ -0,0 +1,152 @@
+//! `slack userinfo`: everything Slack will say about one user.
+
+use crate::output::outln;
+use crate::reminders::local_time;
+use crate::{ensure_token, http, slack_post, unix_now, SlackError, UserInfoArgs};
+use anyhow::Result;
+use serde_json::Value;
+
+pub fn run(args: UserInfoArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let by_email = args.user.contains('@');
+    let resp = if by_email {
+        slack_post(
+            &client,
+            "users.lookupByEmail",
+            &token,
+            Some(&[("email", args.user.as_str())]),
+        )
+    } else {
+        slack_post(
+            &client,
+            "users.info",
+            &token,
+            Some(&[("user", args.user.as_str())]),
+        )
+    }
+    .map_err(|e| match SlackError::code_of(&e) {
+        Some("users_not_found") => e.context(format!("no user with email {}", args.user)),
+        Some("user_not_found") => e.context(format!("no user with ID {}", args.user)),
+        _ => e,
+    })?;
+    let user = resp.get("user").cloned().unwrap_or_default();
+    for (key, value) in fields(&user, unix_now() as i64) {
+        outln!("{key}: {value}");
+    }
+    Ok(())
+}
+
+/// The profile as `(label, value)` pairs in display order; missing values are `-`.
+fn fields(user: &Value, now: i64) -> Vec<(&'static str, String)> {
+    let str_at = |ptr: &str| {
+        user.pointer(ptr)
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .unwrap_or("-")
+            .to_string()
+    };
+    let timezone = match user.get("tz").and_then(|v| v.as_str()) {
+        Some(tz) => {
+            let offset = user.get("tz_offset").and_then(|v| v.as_i64()).unwrap_or(0);
+            format!("{tz}, now {}", local_time(now, offset))
+        }
+        None => "-".to_string(),
+    };
+    let status = [
+        str_at("/profile/status_emoji"),
+        str_at("/profile/status_text"),
+    ]
+    .into_iter()
+    .filter(|s| s != "-")
+    .collect::<Vec<_>>()
+    .join(" ");
+    let avatar = [
+        "/profile/image_512",
+        "/profile/image_192",
+        "/profile/image_72",
+    ]
+    .into_iter()
+    .map(str_at)
+    .find(|s| s != "-")
+    .unwrap_or_else(|| "-".to_string());
+    vec![
+        ("id", str_at("/id")),
+        ("display name", str_at("/profile/display_name")),
+        ("real name", str_at("/profile/real_name")),
+        ("email", str_at("/profile/email")),
+        ("title", str_at("/profile/title")),
+        ("phone", str_at("/profile/phone")),
+        ("timezone", timezone),
+        (
+            "status",
+            if status.is_empty() {
+                "-".into()
+            } else {
+                status
+            },
+        ),
+        ("account", account_kind(user)),
+        ("avatar", avatar),
+    ]
+}
+
+/// `member`, `bot`, `admin`, `owner`, `primary owner`, `guest` or `single-channel guest`,
+/// plus `deactivated` when the account is deleted.
+fn account_kind(user: &Value) -> String {
+    let flag = |k: &str| user.get(k).and_then(|v| v.as_bool()).unwrap_or(false);
+    let kind = if flag("is_bot") {
+        "bot"
+    } else if flag("is_primary_owner") {
+        "primary owner"
+    } else if flag("is_owner") {
+        "owner"
+    } else if flag("is_admin") {
+        "admin"
+    } else if flag("is_ultra_restricted") {
+        "single-channel guest"
+    } else if flag("is_restricted") {
+        "guest"
+    } else {
+        "member"
+    };
+    if flag("deleted") {
+        format!("{kind} (deactivated)")
+    } else {
+        kind.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn fields_fill_gaps_and_compute_local_time() {
+        let user = json!({
+            "id": "U1",
+            "tz": "America/Los_Angeles",
+            "tz_offset": -25200,
+            "is_admin": true,
+            "profile": {
+                "display_name": "jane",
+                "real_name": "Jane Doe",
+                "status_emoji": ":palm_tree:",
+                "status_text": "",
+                "image_72": "https://avatars.test/72.png"
+            }
+        });
+        let got = fields(&user, 1_712_345_678);
+        let get = |k: &str| got.iter().find(|(key, _)| *key == k).unwrap().1.clone();
+        assert_eq!(get("display name"), "jane");
+        assert_eq!(get("email"), "-");
+        assert_eq!(
+            get("timezone"),
+            "America/Los_Angeles, now 2024-04-05 12:34 -07:00"
+        );
+        assert_eq!(get("status"), ":palm_tree:");
+        assert_eq!(get("account"), "admin");
+        assert_eq!(get("avatar"), "https://avatars.test/72.png");
+    }
+}
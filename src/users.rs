@@ -0,0 +1,246 @@
+//! `slack users` and `users-export`: the workspace roster from users.list, filtered for
+//! the terminal or whole as CSV.
+
+use crate::export_html::day_and_time;
+use crate::output::{errln, out, outln};
+use crate::{
+    each_user_page, ensure_token, http, table, user_info, UserInfo, UsersArgs, UsersExportArgs,
+};
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::io::Write;
+
+/// Columns of `users-export`, in order.
+const EXPORT_COLUMNS: &[&str] = &[
+    "user_id",
+    "username",
+    "display_name",
+    "real_name",
+    "email",
+    "title",
+    "tz",
+    "is_admin",
+    "is_bot",
+    "is_restricted",
+    "deleted",
+    "updated",
+];
+
+pub fn run(args: UsersArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let mut users: Vec<(String, UserInfo)> = Vec::new();
+    // Home workspaces of everyone listed, not just those kept, so --team still gets the
+    // TEAM column on a Grid org.
+    let mut homes = HashSet::new();
+    each_user_page(&http(), &token, |page| {
+        for (id, u) in page.iter().filter_map(user_info) {
+            homes.extend(u.teams.first().cloned());
+            if keep(&u, &args) {
+                users.push((id, u));
+            }
+        }
+        users.len() < args.limit
+    })?;
+    let grid = homes.len() > 1;
+    users.truncate(args.limit);
+    if users.is_empty() {
+        outln!("no users match");
+        return Ok(());
+    }
+    let rows: Vec<Vec<String>> = users
+        .into_iter()
+        .map(|(id, u)| {
+            let mut row = vec![
+                id,
+                format!("@{}", u.display_name),
+                u.real_name.clone().unwrap_or_default(),
+                u.email.clone().unwrap_or_default(),
+                u.flags(),
+            ];
+            if grid {
+                row.push(u.teams.first().cloned().unwrap_or_default());
+            }
+            row
+        })
+        .collect();
+    if args.table {
+        let mut header = vec!["USER", "NAME", "REAL NAME", "EMAIL", "FLAGS"];
+        let mut widths = vec![None, Some(24), Some(28), Some(32), None];
+        if grid {
+            header.push("TEAM");
+            widths.push(None);
+        }
+        out!("{}", table::render(&header, &rows, &widths));
+    } else {
+        for row in rows {
+            outln!("{}", row.join("\t"));
+        }
+    }
+    Ok(())
+}
+
+pub fn export(args: UsersExportArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let mut csv = csv_line(EXPORT_COLUMNS.iter().map(|c| c.to_string()));
+    let mut rows = 0;
+    each_user_page(&http(), &token, |page| {
+        for m in page {
+            csv.push_str(&csv_line(export_row(m)));
+            rows += 1;
+        }
+        true
+    })?;
+    if args.out.as_os_str() == "-" {
+        out!("{csv}");
+        errln!("exported {rows} users");
+        return Ok(());
+    }
+    let mut file = std::fs::File::create(&args.out)
+        .with_context(|| format!("failed to create {}", args.out.display()))?;
+    file.write_all(csv.as_bytes())
+        .with_context(|| format!("failed to write {}", args.out.display()))?;
+    outln!("exported {rows} users to {}", args.out.display());
+    Ok(())
+}
+
+/// One member's `EXPORT_COLUMNS` values; `updated` is UTC.
+fn export_row(m: &Value) -> Vec<String> {
+    let str_at = |ptr: &str| {
+        m.pointer(ptr)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+    let flag = |k: &str| (m.get(k).and_then(|v| v.as_bool()) == Some(true)).to_string();
+    let updated = match m.get("updated").and_then(|v| v.as_i64()) {
+        Some(t) if t > 0 => {
+            let (day, time) = day_and_time(t as f64);
+            format!("{day} {time}")
+        }
+        _ => String::new(),
+    };
+    vec![
+        str_at("/id"),
+        str_at("/name"),
+        str_at("/profile/display_name"),
+        str_at("/profile/real_name"),
+        str_at("/profile/email"),
+        str_at("/profile/title"),
+        str_at("/tz"),
+        flag("is_admin"),
+        flag("is_bot"),
+        flag("is_restricted"),
+        flag("deleted"),
+        updated,
+    ]
+}
+
+/// One CSV record with its line break; fields holding a comma, quote or line break are
+/// quoted, with quotes doubled (RFC 4180).
+fn csv_line(fields: impl IntoIterator<Item = String>) -> String {
+    let quoted: Vec<String> = fields
+        .into_iter()
+        .map(|f| {
+            if f.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", f.replace('"', "\"\""))
+            } else {
+                f
+            }
+        })
+        .collect();
+    format!("{}\r\n", quoted.join(","))
+}
+
+/// Bots and deactivated accounts only when asked for; `--guests-only` and
+/// `--admins-only` then narrow what's left (both together: either kind).
+fn keep(u: &UserInfo, args: &UsersArgs) -> bool {
+    if (u.is_bot && !args.include_bots) || (u.deleted && !args.include_deleted) {
+        return false;
+    }
+    if args.team.as_deref().is_some_and(|team| !u.in_team(team)) {
+        return false;
+    }
+    let guest = u.is_restricted || u.is_ultra_restricted;
+    let admin = u.is_admin || u.is_owner || u.is_primary_owner;
+    match (args.guests_only, args.admins_only) {
+        (false, false) => true,
+        (true, false) => guest,
+        (false, true) => admin,
+        (true, true) => guest || admin,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args() -> UsersArgs {
+        UsersArgs {
+            limit: 500,
+            include_bots: false,
+            include_deleted: false,
+            guests_only: false,
+            admins_only: false,
+            table: false,
+            team: None,
+        }
+    }
+
+    #[test]
+    fn csv_line_quotes_what_needs_it() {
+        let line = csv_line(
+            ["U1", "Doe, Jane", "say \"hi\"", "two\nlines"]
+                .iter()
+                .map(|s| s.to_string()),
+        );
+        assert_eq!(
+            line,
+            "U1,\"Doe, Jane\",\"say \"\"hi\"\"\",\"two\nlines\"\r\n"
+        );
+    }
+
+    #[test]
+    fn filters_compose() {
+        let bot = UserInfo {
+            is_bot: true,
+            ..Default::default()
+        };
+        let gone_guest = UserInfo {
+            is_restricted: true,
+            deleted: true,
+            ..Default::default()
+        };
+        let admin = UserInfo {
+            is_admin: true,
+            ..Default::default()
+        };
+        assert!(!keep(&bot, &args()));
+        assert!(!keep(&gone_guest, &args()));
+        assert!(keep(&admin, &args()));
+
+        let guests = UsersArgs {
+            guests_only: true,
+            include_deleted: true,
+            ..args()
+        };
+        assert!(keep(&gone_guest, &guests));
+        assert!(!keep(&admin, &guests));
+        let bots = UsersArgs {
+            include_bots: true,
+            ..args()
+        };
+        assert!(keep(&bot, &bots));
+
+        let grid_admin = UserInfo {
+            teams: vec!["T1".into(), "T2".into()],
+            ..admin.clone()
+        };
+        let in_t2 = UsersArgs {
+            team: Some("T2".into()),
+            ..args()
+        };
+        assert!(keep(&grid_admin, &in_t2));
+        assert!(!keep(&admin, &in_t2));
+    }
+}
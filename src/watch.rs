@@ -0,0 +1,311 @@
+//! `slack watch`: poll a channel for new messages and hand each one to a local
+//! program (`--exec`) or an HTTP endpoint (`--webhook`).
+
+use crate::output::{errln, outln};
+use crate::{each_page, ensure_token, http, resolve_channel, slack_post, WatchArgs};
+use anyhow::{anyhow, Context, Result};
+use reqwest::blocking::Client;
+use serde_json::Value;
+use std::io::Write;
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+pub fn run(args: WatchArgs) -> Result<()> {
+    let token = ensure_token()?;
+    let client = http();
+    let channel = resolve_channel(&client, &token, &args.channel)?;
+    let mut hook = match (&args.exec, &args.webhook) {
+        (Some(cmd), _) => Hook::Exec(ExecHook::new(cmd.clone(), args.max_concurrent)),
+        (None, Some(url)) => Hook::Webhook(WebhookHook {
+            client: client.clone(),
+            url: url.clone(),
+            retries: args.retries,
+        }),
+        (None, None) => Hook::Print,
+    };
+
+    // Start from the newest message unless told otherwise, so history isn't replayed.
+    let mut last_ts = match &args.since {
+        Some(ts) => ts.clone(),
+        None => latest_ts(&client, &token, &channel)?.unwrap_or_else(|| "0".into()),
+    };
+    loop {
+        // A long watch outlives the odd network blip or 5xx; report it and poll again.
+        let msgs = match messages_since(&client, &token, &channel, &last_ts) {
+            Ok(msgs) => msgs,
+            Err(e) if !args.once => {
+                errln!("polling {channel} failed: {e:#}; trying again next interval");
+                Vec::new()
+            }
+            Err(e) => return Err(e),
+        };
+        for m in msgs {
+            let Some(ts) = m.get("ts").and_then(|v| v.as_str()).map(str::to_string) else {
+                continue;
+            };
+            if ts_gt(&ts, &last_ts) {
+                last_ts = ts;
+            } else {
+                continue;
+            }
+            if passes(&m, &args) {
+                hook.fire(&channel, &m);
+            }
+        }
+        if args.once {
+            break;
+        }
+        std::thread::sleep(Duration::from_secs(args.interval.max(1)));
+    }
+    hook.finish();
+    Ok(())
+}
+
+/// Every message after `oldest`, oldest first. A busy channel can post more than a
+/// page between polls, so this follows the cursor to the end before anything is
+/// handed on (and `last_ts` moves past the pages not yet read).
+fn messages_since(client: &Client, token: &str, channel: &str, oldest: &str) -> Result<Vec<Value>> {
+    let form = [("channel", channel), ("oldest", oldest), ("limit", "200")];
+    let mut msgs = Vec::new();
+    each_page(
+        client,
+        token,
+        "conversations.history",
+        &form,
+        "messages",
+        |page| {
+            msgs.extend_from_slice(page);
+            true
+        },
+    )?;
+    // Pages come newest first, each newest first.
+    msgs.sort_by_key(|m| ts_key(m.get("ts").and_then(|v| v.as_str()).unwrap_or("0")));
+    Ok(msgs)
+}
+
+fn latest_ts(client: &Client, token: &str, channel: &str) -> Result<Option<String>> {
+    let resp = slack_post(
+        client,
+        "conversations.history",
+        token,
+        Some(&[("channel", channel), ("limit", "1")]),
+    )?;
+    Ok(resp
+        .get("messages")
+        .and_then(|v| v.as_array())
+        .and_then(|a| a.first())
+        .and_then(|m| m.get("ts"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string))
+}
+
+/// A Slack timestamp ("seconds.micros") as numbers, for ordering.
+fn ts_key(s: &str) -> (u64, u64) {
+    let (secs, frac) = s.split_once('.').unwrap_or((s, "0"));
+    (secs.parse().unwrap_or(0), frac.parse().unwrap_or(0))
+}
+
+/// Compare Slack timestamps numerically.
+fn ts_gt(a: &str, b: &str) -> bool {
+    ts_key(a) > ts_key(b)
+}
+
+fn passes(m: &Value, args: &WatchArgs) -> bool {
+    let user = m.get("user").and_then(|v| v.as_str()).unwrap_or("");
+    let text = m.get("text").and_then(|v| v.as_str()).unwrap_or("");
+    if let Some(from) = &args.from {
+        if user != from {
+            return false;
+        }
+    }
+    if let Some(needle) = &args.contains {
+        if !text.to_lowercase().contains(&needle.to_lowercase()) {
+            return false;
+        }
+    }
+    true
+}
+
+enum Hook {
+    Print,
+    Exec(ExecHook),
+    Webhook(WebhookHook),
+}
+
+impl Hook {
+    fn fire(&mut self, channel: &str, m: &Value) {
+        match self {
+            Hook::Print => outln!("{}", crate::format_message_line(m)),
+            Hook::Exec(h) => {
+                if let Err(e) = h.spawn(channel, m) {
+                    errln!("watch: hook failed to start: {e:#}");
+                }
+            }
+            Hook::Webhook(h) => {
+                if let Err(e) = h.post(channel, m) {
+                    errln!("watch: webhook failed: {e:#}");
+                }
+            }
+        }
+    }
+
+    fn finish(&mut self) {
+        if let Hook::Exec(h) = self {
+            h.wait_all();
+        }
+    }
+}
+
+/// Runs a shell command per message, with at most `max` running at once.
+struct ExecHook {
+    command: String,
+    max: usize,
+    running: Vec<(String, Child)>,
+}
+
+impl ExecHook {
+    fn new(command: String, max: usize) -> Self {
+        ExecHook {
+            command,
+            max: max.max(1),
+            running: Vec::new(),
+        }
+    }
+
+    fn spawn(&mut self, channel: &str, m: &Value) -> Result<()> {
+        self.reap();
+        while self.running.len() >= self.max {
+            let (ts, mut child) = self.running.remove(0);
+            report_exit(&ts, child.wait());
+        }
+        let ts = m.get("ts").and_then(|v| v.as_str()).unwrap_or("");
+        let mut cmd = shell(&self.command);
+        cmd.env("SLACK_CHANNEL", channel)
+            .env("SLACK_TS", ts)
+            .env(
+                "SLACK_USER",
+                m.get("user").and_then(|v| v.as_str()).unwrap_or(""),
+            )
+            .env(
+                "SLACK_TEXT",
+                m.get("text").and_then(|v| v.as_str()).unwrap_or(""),
+            )
+            .env(
+                "SLACK_THREAD_TS",
+                m.get("thread_ts").and_then(|v| v.as_str()).unwrap_or(""),
+            )
+            .stdin(Stdio::piped());
+        let mut child = cmd
+            .spawn()
+            .with_context(|| format!("could not run `{}`", self.command))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // A hook that ignores stdin may close it early; that's not an error.
+            let _ = stdin.write_all(m.to_string().as_bytes());
+        }
+        self.running.push((ts.to_string(), child));
+        Ok(())
+    }
+
+    /// Collect hooks that have already exited.
+    fn reap(&mut self) {
+        let mut i = 0;
+        while i < self.running.len() {
+            let status = match self.running[i].1.try_wait() {
+                Ok(None) => {
+                    i += 1;
+                    continue;
+                }
+                Ok(Some(s)) => Ok(s),
+                Err(e) => Err(e),
+            };
+            let (ts, _) = self.running.remove(i);
+            report_exit(&ts, status);
+        }
+    }
+
+    fn wait_all(&mut self) {
+        for (ts, mut child) in self.running.drain(..) {
+            report_exit(&ts, child.wait());
+        }
+    }
+}
+
+fn report_exit(ts: &str, status: std::io::Result<std::process::ExitStatus>) {
+    match status {
+        Ok(s) if s.success() => {}
+        Ok(s) => errln!("watch: hook for ts={ts} exited with {s}"),
+        Err(e) => errln!("watch: could not wait for hook for ts={ts}: {e}"),
+    }
+}
+
+pub(crate) fn shell(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut c = Command::new("cmd");
+        c.args(["/C", command]);
+        c
+    } else {
+        let mut c = Command::new("sh");
+        c.args(["-c", command]);
+        c
+    }
+}
+
+/// POSTs the message JSON, retrying transport errors, 429s and 5xx with backoff.
+struct WebhookHook {
+    client: Client,
+    url: String,
+    retries: u32,
+}
+
+impl WebhookHook {
+    fn post(&self, channel: &str, m: &Value) -> Result<()> {
+        let mut body = m.clone();
+        body["channel"] = Value::String(channel.to_string());
+        let mut attempt = 0;
+        loop {
+            let err = match self.client.post(&self.url).json(&body).send() {
+                Ok(r) if r.status().is_success() => return Ok(()),
+                Ok(r) if r.status().as_u16() == 429 || r.status().is_server_error() => {
+                    anyhow!("HTTP {}", r.status())
+                }
+                Ok(r) => return Err(anyhow!("HTTP {} (not retried)", r.status())),
+                Err(e) => anyhow::Error::new(e),
+            };
+            if attempt >= self.retries {
+                return Err(err.context(format!("gave up after {} attempts", attempt + 1)));
+            }
+            attempt += 1;
+            std::thread::sleep(webhook_backoff(attempt));
+        }
+    }
+}
+
+/// Longest pause between webhook attempts, however many retries were asked for.
+const MAX_WEBHOOK_BACKOFF: Duration = Duration::from_secs(60);
+
+/// The pause before retry number `attempt` (from 1): half a second, doubling, capped.
+fn webhook_backoff(attempt: u32) -> Duration {
+    let ms = 500u64.saturating_mul(2u64.saturating_pow(attempt.saturating_sub(1)));
+    Duration::from_millis(ms).min(MAX_WEBHOOK_BACKOFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slack_ts_ordering() {
+        assert!(ts_gt("1712345678.000200", "1712345678.000100"));
+        assert!(ts_gt("1712345679.000000", "1712345678.999999"));
+        assert!(!ts_gt("1712345678.000100", "1712345678.000100"));
+        assert!(ts_gt("1.0", "0"));
+    }
+
+    #[test]
+    fn webhook_backoff_doubles_up_to_the_cap() {
+        assert_eq!(webhook_backoff(1), Duration::from_millis(500));
+        assert_eq!(webhook_backoff(3), Duration::from_secs(2));
+        assert_eq!(webhook_backoff(64), MAX_WEBHOOK_BACKOFF);
+        assert_eq!(webhook_backoff(u32::MAX), MAX_WEBHOOK_BACKOFF);
+    }
+}
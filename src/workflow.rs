@@ -0,0 +1,149 @@
+//! `slack workflow`: trigger Workflow Builder webhooks defined in `config.toml`.
+
+use crate::config::{self, Workflow};
+use crate::output::outln;
+use crate::{http, WorkflowArgs, WorkflowCommand, WorkflowTriggerArgs};
+use anyhow::{anyhow, Result};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+pub fn run(args: WorkflowArgs) -> Result<()> {
+    let cfg = config::load()?;
+    match args.command {
+        WorkflowCommand::List => {
+            if cfg.workflows.is_empty() {
+                outln!("no workflows configured; {}", hint());
+            }
+            for (name, wf) in &cfg.workflows {
+                outln!("{name}\t{}", wf.variables().join(","));
+            }
+            Ok(())
+        }
+        WorkflowCommand::Trigger(args) => trigger(&cfg.workflows, args),
+    }
+}
+
+fn trigger(workflows: &BTreeMap<String, Workflow>, args: WorkflowTriggerArgs) -> Result<()> {
+    let wf = workflows.get(&args.name).ok_or_else(|| {
+        if workflows.is_empty() {
+            anyhow!(
+                "unknown workflow `{}`: none are configured; {}",
+                args.name,
+                hint()
+            )
+        } else {
+            let names: Vec<&str> = workflows.keys().map(String::as_str).collect();
+            anyhow!(
+                "unknown workflow `{}`; configured: {}",
+                args.name,
+                names.join(", ")
+            )
+        }
+    })?;
+    let body = payload(wf, &args.vars)?;
+
+    // Plain POST: the Slack token is never sent to a webhook URL.
+    let resp = http()
+        .post(wf.url())
+        .json(&body)
+        .send()
+        .map_err(|e| anyhow!("workflow `{}` request failed: {e}", args.name))?;
+    let status = resp.status();
+    let text = resp.text().unwrap_or_default();
+    outln!("HTTP {status}");
+    if !text.is_empty() {
+        outln!("{text}");
+    }
+    if !status.is_success() {
+        return Err(anyhow!("workflow `{}` returned HTTP {status}", args.name));
+    }
+    Ok(())
+}
+
+fn hint() -> String {
+    match config::config_path() {
+        Some(p) => format!("add a [workflows] table to {}", p.display()),
+        None => "add a [workflows] table to config.toml".to_string(),
+    }
+}
+
+/// Build the JSON body from `key=value` pairs, checking them against the workflow's
+/// declared variables (when it declares any) before anything is sent.
+fn payload(wf: &Workflow, vars: &[String]) -> Result<Value> {
+    let mut body = Map::new();
+    for var in vars {
+        let (key, value) = var
+            .split_once('=')
+            .ok_or_else(|| anyhow!("--var must be key=value, got `{var}`"))?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(anyhow!("--var must be key=value, got `{var}`"));
+        }
+        body.insert(key.to_string(), Value::String(value.to_string()));
+    }
+    let expected = wf.variables();
+    if !expected.is_empty() {
+        let missing: Vec<&str> = expected
+            .iter()
+            .filter(|k| !body.contains_key(k.as_str()))
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            return Err(anyhow!("missing --var for: {}", missing.join(", ")));
+        }
+        let unknown: Vec<&str> = body
+            .keys()
+            .filter(|k| !expected.contains(k))
+            .map(String::as_str)
+            .collect();
+        if !unknown.is_empty() {
+            return Err(anyhow!(
+                "unexpected --var {} (this workflow takes: {})",
+                unknown.join(", "),
+                expected.join(", ")
+            ));
+        }
+    }
+    Ok(Value::Object(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intake() -> Workflow {
+        Workflow::Full {
+            url: "https://hooks.test/intake".into(),
+            variables: vec!["title".into(), "severity".into()],
+        }
+    }
+
+    #[test]
+    fn payload_is_a_flat_string_object() {
+        let body = payload(
+            &intake(),
+            &["title=Broken build = red".into(), "severity=high".into()],
+        )
+        .unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({ "title": "Broken build = red", "severity": "high" })
+        );
+    }
+
+    #[test]
+    fn declared_variables_are_enforced() {
+        let err = payload(&intake(), &["title=x".into()]).unwrap_err();
+        assert!(err.to_string().contains("missing --var for: severity"));
+        let err = payload(
+            &intake(),
+            &["title=x".into(), "severity=y".into(), "extra=z".into()],
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("unexpected --var extra"));
+        assert!(payload(&intake(), &["nokey".into()]).is_err());
+        // Without a declared list anything goes.
+        let open = Workflow::Url("https://hooks.test/x".into());
+        assert!(payload(&open, &["anything=1".into()]).is_ok());
+    }
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn archive_with_yes_skips_the_prompt() {
+    let server = MockServer::start();
+    let archive = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.archive")
+            .x_www_form_urlencoded_tuple("channel", "C1");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = run(&server, &["archive", "--channel", "C1", "--yes"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "archived C1\n");
+    archive.assert_hits(1);
+}
+
+#[test]
+fn archive_without_a_terminal_archives_nothing() {
+    let server = MockServer::start();
+    let info = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.info")
+            .x_www_form_urlencoded_tuple("channel", "C1");
+        then.status(200)
+            .json_body(json!({ "ok": true, "channel": { "id": "C1", "name": "ops" } }));
+    });
+    let archive = server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.archive");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = run(&server, &["archive", "--channel", "C1"]);
+    assert!(!out.status.success());
+    info.assert_hits(1);
+    archive.assert_hits(0);
+}
+
+#[test]
+fn archive_errors_are_readable_and_fail() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.archive");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "already_archived" }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.unarchive");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "not_archived" }));
+    });
+
+    let out = run(&server, &["archive", "--channel", "C1", "--yes"]);
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("C1 is already archived"), "{err}");
+
+    let out = run(&server, &["unarchive", "--channel", "C1"]);
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("C1 isn't archived"), "{err}");
+}
+
+#[test]
+fn unarchive_prints_the_channel() {
+    let server = MockServer::start();
+    let unarchive = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.unarchive")
+            .x_www_form_urlencoded_tuple("channel", "C1");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = run(&server, &["unarchive", "--channel", "C1"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "unarchived C1\n");
+    unarchive.assert_hits(1);
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use assert_cmd::Command;
+use httpmock::prelude::*;
+use predicates::prelude::*;
+
+fn slack(server: &MockServer, state: &tempfile::TempDir) -> Command {
+    let mut cmd = common::slack(server);
+    cmd.env_remove("SLACK_TOKEN")
+        .env("SLACK_STATE_DIR", state.path())
+        .args(["--token", "xoxp-cached"]);
+    cmd
+}
+
+#[test]
+fn second_whoami_within_ttl_skips_validation() {
+    let server = MockServer::start();
+    let state = tempfile::tempdir().unwrap();
+    let auth = server.mock(|when, then| {
+        when.method(POST).path("/api/auth.test");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": true, "team": "Acme Co" }));
+    });
+
+    // First run: validation + the whoami call itself.
+    slack(&server, &state).arg("whoami").assert().success();
+    auth.assert_hits(2);
+
+    // Second run within the TTL: only the whoami call.
+    slack(&server, &state)
+        .arg("whoami")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Acme Co"));
+    auth.assert_hits(3);
+
+    // --revalidate forces the upfront auth.test again.
+    slack(&server, &state)
+        .args(["--revalidate", "whoami"])
+        .assert()
+        .success();
+    auth.assert_hits(5);
+}
+
+#[test]
+fn rejected_cached_token_is_revalidated_once() {
+    let server = MockServer::start();
+    let state = tempfile::tempdir().unwrap();
+    let mut auth = server.mock(|when, then| {
+        when.method(POST).path("/api/auth.test");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": true }));
+    });
+    slack(&server, &state).arg("whoami").assert().success();
+    auth.delete();
+
+    // The token has since been revoked: the cached validation is trusted,
+    // the API call fails, the cache is dropped, and validation now fails.
+    let auth = server.mock(|when, then| {
+        when.method(POST).path("/api/auth.test");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": false, "error": "token_revoked" }));
+    });
+    let list = server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.list");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": false, "error": "token_revoked" }));
+    });
+    slack(&server, &state)
+        .arg("channels")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--token did not validate: token_revoked",
+        ));
+    list.assert_hits(1);
+    auth.assert_hits(1);
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\n0000IHDR";
+
+#[test]
+fn avatar_uploads_the_image_with_crop() {
+    let server = MockServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("me.png");
+    std::fs::write(&path, PNG).unwrap();
+    let set = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.setPhoto")
+            .header_exists("content-type")
+            .body_contains("name=\"image\"; filename=\"me.png\"")
+            .body_contains("Content-Type: image/png")
+            .body_contains("name=\"crop_w\"\r\n\r\n400");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = run(
+        &server,
+        &[
+            "avatar",
+            "--file",
+            path.to_str().unwrap(),
+            "--crop-w",
+            "400",
+        ],
+    );
+    assert!(out.status.success(), "{out:?}");
+    assert!(String::from_utf8_lossy(&out.stdout).starts_with("profile photo set from "));
+    set.assert_hits(1);
+}
+
+#[test]
+fn avatar_checks_the_file_before_uploading() {
+    let server = MockServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    let set = server.mock(|when, then| {
+        when.method(POST).path("/api/users.setPhoto");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let missing = dir.path().join("nope.png");
+    let out = run(&server, &["avatar", "--file", missing.to_str().unwrap()]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("failed to read"));
+
+    let svg = dir.path().join("me.svg");
+    std::fs::write(&svg, "<svg xmlns=\"http://www.w3.org/2000/svg\"/>").unwrap();
+    let out = run(&server, &["avatar", "--file", svg.to_str().unwrap()]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("is not a PNG, JPEG, GIF or BMP image"));
+    set.assert_hits(0);
+}
+
+#[test]
+fn avatar_reports_size_when_too_large() {
+    let server = MockServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("big.png");
+    let mut bytes = PNG.to_vec();
+    bytes.resize(3 * 1024 * 1024, 0);
+    std::fs::write(&path, &bytes).unwrap();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.setPhoto");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "too_large" }));
+    });
+
+    let out = run(&server, &["avatar", "--file", path.to_str().unwrap()]);
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("big.png is 3.0 MiB"), "{err}");
+}
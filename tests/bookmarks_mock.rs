@@ -0,0 +1,101 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn bookmarks_lists_id_title_link_and_emoji() {
+    let server = MockServer::start();
+    let list = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/bookmarks.list")
+            .x_www_form_urlencoded_tuple("channel_id", "C1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "bookmarks": [
+                { "id": "Bk1", "title": "Runbook", "link": "https://wiki/runbook", "emoji": ":books:" },
+                { "id": "Bk2", "title": "Grafana", "link": "https://grafana" }
+            ]
+        }));
+    });
+
+    let out = run(&server, &["bookmarks", "--channel", "C1"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "Bk1\tRunbook\thttps://wiki/runbook\t:books:\nBk2\tGrafana\thttps://grafana\t\n"
+    );
+    list.assert();
+}
+
+#[test]
+fn bookmark_add_sends_a_link_and_prints_its_id() {
+    let server = MockServer::start();
+    let add = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/bookmarks.add")
+            .x_www_form_urlencoded_tuple("channel_id", "C1")
+            .x_www_form_urlencoded_tuple("title", "Runbook")
+            .x_www_form_urlencoded_tuple("type", "link")
+            .x_www_form_urlencoded_tuple("link", "https://wiki/runbook")
+            .x_www_form_urlencoded_tuple("emoji", ":books:");
+        then.status(200)
+            .json_body(json!({ "ok": true, "bookmark": { "id": "Bk9" } }));
+    });
+
+    let out = run(
+        &server,
+        &[
+            "bookmark-add",
+            "--channel",
+            "C1",
+            "--title",
+            "Runbook",
+            "--link",
+            "https://wiki/runbook",
+            "--emoji",
+            ":books:",
+        ],
+    );
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "added bookmark Bk9\n");
+    add.assert();
+}
+
+#[test]
+fn bookmark_remove_and_not_in_channel_hint() {
+    let server = MockServer::start();
+    let remove = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/bookmarks.remove")
+            .x_www_form_urlencoded_tuple("channel_id", "C1")
+            .x_www_form_urlencoded_tuple("bookmark_id", "Bk1");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/bookmarks.remove")
+            .x_www_form_urlencoded_tuple("channel_id", "C2");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "not_in_channel" }));
+    });
+
+    let out = run(
+        &server,
+        &["bookmark-remove", "--channel", "C1", "--bookmark", "Bk1"],
+    );
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "removed bookmark Bk1\n"
+    );
+    remove.assert();
+
+    let out = run(
+        &server,
+        &["bookmark-remove", "--channel", "C2", "--bookmark", "Bk1"],
+    );
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("run `slack join --channel C2` first"));
+}
@@ -0,0 +1,42 @@
+use assert_cmd::prelude::*;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+#[test]
+fn bot_info_prints_one_line_per_bot_and_reports_unknown_ones() {
+    let server = MockServer::start();
+    let known = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/bots.info")
+            .x_www_form_urlencoded_tuple("bot", "B0123ABCD");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "bot": { "id": "B0123ABCD", "name": "deploybot", "app_id": "A0123", "deleted": false }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/bots.info");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "bot_not_found" }));
+    });
+
+    let out = Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["bot-info", "--bot", "B0123ABCD", "--bot", "B0999,B0123ABCD"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "B0123ABCD\tdeploybot\tA0123\tactive\n\
+         B0123ABCD\tdeploybot\tA0123\tactive\n"
+    );
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("B0999: no bot with ID B0999"), "{err}");
+    assert!(err.contains("1 of 3 bots could not be looked up"), "{err}");
+    // The repeated ID comes from the cache.
+    known.assert_hits(1);
+}
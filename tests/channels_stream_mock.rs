@@ -0,0 +1,60 @@
+use assert_cmd::cargo::CommandCargoExt;
+use httpmock::prelude::*;
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+#[test]
+fn channels_all_prints_the_first_page_before_the_second_arrives() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.list")
+            .matches(|req| {
+                !String::from_utf8_lossy(req.body.as_deref().unwrap_or_default())
+                    .contains("cursor=")
+            });
+        then.status(200).json_body(serde_json::json!({
+            "ok": true,
+            "channels": [{ "id": "C1", "name": "general" }],
+            "response_metadata": { "next_cursor": "page2" }
+        }));
+    });
+    let second = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.list")
+            .x_www_form_urlencoded_tuple("cursor", "page2");
+        then.status(200)
+            .delay(Duration::from_secs(2))
+            .json_body(serde_json::json!({
+                "ok": true,
+                "channels": [{ "id": "C2", "name": "random" }]
+            }));
+    });
+
+    let start = Instant::now();
+    let mut child = Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["channels", "--all", "--limit", "1"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut lines = BufReader::new(child.stdout.take().unwrap()).lines();
+
+    let first = lines.next().unwrap().unwrap();
+    let first_at = start.elapsed();
+    assert!(first.starts_with("C1\t#general"), "{first}");
+    let rest: Vec<String> = lines.map(Result::unwrap).collect();
+    assert!(child.wait().unwrap().success());
+    let done_at = start.elapsed();
+
+    assert_eq!(rest.len(), 1);
+    assert!(rest[0].starts_with("C2\t#random"));
+    second.assert();
+    assert!(
+        first_at + Duration::from_millis(1500) < done_at,
+        "first page at {first_at:?}, finished at {done_at:?}"
+    );
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn close_reports_each_dm_and_keeps_going_past_failures() {
+    let server = MockServer::start();
+    let closed = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.close")
+            .x_www_form_urlencoded_tuple("channel", "D1");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.close")
+            .x_www_form_urlencoded_tuple("channel", "D2");
+        then.status(200)
+            .json_body(json!({ "ok": true, "no_op": true, "already_closed": true }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.close")
+            .x_www_form_urlencoded_tuple("channel", "C3");
+        then.status(200).json_body(json!({
+            "ok": false,
+            "error": "method_not_supported_for_channel_type"
+        }));
+    });
+
+    let out = run(
+        &server,
+        &[
+            "close",
+            "--channel",
+            "D1",
+            "--channel",
+            "C3",
+            "--channel",
+            "D2",
+        ],
+    );
+    assert!(!out.status.success());
+    closed.assert();
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "closed D1\nalready closed D2\n"
+    );
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        err.contains("C3: C3 isn't a DM; use `slack leave`"),
+        "{err}"
+    );
+    assert!(
+        err.contains("1 of 3 conversations could not be closed"),
+        "{err}"
+    );
+}
@@ -0,0 +1,17 @@
+//! The command builder the mocked-API suites share. Each suite uses only some of it.
+#![allow(dead_code)]
+
+use httpmock::MockServer;
+
+/// `mdslackcli` with a user token, talking to `server` instead of Slack.
+pub fn slack(server: &MockServer) -> assert_cmd::Command {
+    let mut cmd = assert_cmd::Command::cargo_bin("mdslackcli").unwrap();
+    cmd.env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()));
+    cmd
+}
+
+/// `slack` with `args`, run to completion.
+pub fn run(server: &MockServer, args: &[&str]) -> std::process::Output {
+    slack(server).args(args).output().unwrap()
+}
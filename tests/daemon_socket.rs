@@ -0,0 +1,151 @@
+#![cfg(unix)]
+
+use assert_cmd::cargo::CommandCargoExt;
+use httpmock::prelude::*;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// Kills the daemon if the test fails early, so it can't outlive the test run.
+struct Daemon(Child);
+
+impl Drop for Daemon {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+fn slack(server: &MockServer, state: &tempfile::TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("mdslackcli").unwrap();
+    cmd.env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .env("SLACK_STATE_DIR", state.path());
+    cmd
+}
+
+#[test]
+fn commands_round_trip_through_the_daemon_socket() {
+    let server = MockServer::start();
+    let state = tempfile::tempdir().unwrap();
+    let auth = server.mock(|when, then| {
+        when.method(POST).path("/api/auth.test");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": true, "team": "Daemon Team" }));
+    });
+    // Only for the workspace the client names in its own environment.
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.list")
+            .x_www_form_urlencoded_tuple("team_id", "T2");
+        then.status(200).json_body(serde_json::json!({
+            "ok": true,
+            "channels": [{ "id": "C2", "name": "grid-only" }]
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200).json_body(serde_json::json!({
+            "ok": true,
+            "members": [{ "id": "U1", "name": "ana", "profile": { "display_name": "ana" } }]
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.list");
+        then.status(200).json_body(serde_json::json!({
+            "ok": true,
+            "channels": [{ "id": "C1", "name": "general" }]
+        }));
+    });
+
+    // Only the daemon knows the token; clients must go through it to succeed.
+    let mut daemon = Daemon(
+        slack(&server, &state)
+            .env("SLACK_TOKEN", "xoxp-daemon")
+            .arg("daemon")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap(),
+    );
+    let sock = state.path().join("daemon.sock");
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !sock.exists() {
+        assert!(Instant::now() < deadline, "daemon socket never appeared");
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    let mode =
+        std::os::unix::fs::PermissionsExt::mode(&std::fs::metadata(&sock).unwrap().permissions());
+    assert_eq!(mode & 0o077, 0, "socket must be private to the owner");
+
+    let out = slack(&server, &state)
+        .env_remove("SLACK_TOKEN")
+        .args(["--via-daemon", "whoami"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert!(String::from_utf8_lossy(&out.stdout).contains("Daemon Team"));
+
+    let out = slack(&server, &state)
+        .env_remove("SLACK_TOKEN")
+        .args(["--via-daemon", "channels"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert!(String::from_utf8_lossy(&out.stdout).contains("C1\t#general"));
+
+    // Relative paths and SLACK_TEAM_ID are the client's, not the daemon's.
+    let client_dir = tempfile::tempdir().unwrap();
+    let out = slack(&server, &state)
+        .env_remove("SLACK_TOKEN")
+        .current_dir(client_dir.path())
+        .args(["--via-daemon", "users-export", "--out", "people.csv"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    let csv = std::fs::read_to_string(client_dir.path().join("people.csv")).unwrap();
+    assert!(csv.contains("U1"), "{csv}");
+
+    let out = slack(&server, &state)
+        .env_remove("SLACK_TOKEN")
+        .env("SLACK_TEAM_ID", "T2")
+        .args(["--via-daemon", "channels"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert!(String::from_utf8_lossy(&out.stdout).contains("C2\t#grid-only"));
+
+    // Errors come back with a non-zero exit code and the message on stderr.
+    let out = slack(&server, &state)
+        .env_remove("SLACK_TOKEN")
+        .args(["--via-daemon", "msgs"])
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("--channel"));
+
+    let out = slack(&server, &state)
+        .args(["daemon", "--stop"])
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&out.stdout).contains("daemon stopped"));
+    assert!(daemon.0.wait().unwrap().success());
+    auth.assert_hits(1);
+}
+
+#[test]
+fn via_daemon_falls_back_to_in_process() {
+    let server = MockServer::start();
+    let state = tempfile::tempdir().unwrap();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/auth.test");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": true, "team": "Local Team" }));
+    });
+
+    let out = slack(&server, &state)
+        .env("SLACK_TOKEN", "xoxp-local")
+        .args(["--via-daemon", "whoami"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert!(String::from_utf8_lossy(&out.stdout).contains("Local Team"));
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn stub_timezone(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST).path("/api/auth.test");
+        then.status(200)
+            .json_body(json!({ "ok": true, "user_id": "U1" }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.info");
+        then.status(200)
+            .json_body(json!({ "ok": true, "user": { "id": "U1", "tz_offset": 3600 } }));
+    });
+}
+
+#[test]
+fn info_and_snooze_show_local_end_times() {
+    let server = MockServer::start();
+    stub_timezone(&server);
+    server.mock(|when, then| {
+        when.method(POST).path("/api/dnd.info");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "dnd_enabled": true,
+            "snooze_enabled": true,
+            "snooze_endtime": 1712345678
+        }));
+    });
+    let snooze = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/dnd.setSnooze")
+            .x_www_form_urlencoded_tuple("num_minutes", "90");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "snooze_enabled": true,
+            "snooze_endtime": 1712345678
+        }));
+    });
+
+    let out = run(&server, &["dnd"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "snoozed until 2024-04-05 20:34 +01:00\n"
+    );
+    let out = run(&server, &["dnd", "snooze", "--minutes", "90"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "snoozed until 2024-04-05 20:34 +01:00\n"
+    );
+    snooze.assert_hits(1);
+
+    for bad in ["0", "1441"] {
+        let out = run(&server, &["dnd", "snooze", "--minutes", bad]);
+        assert_eq!(out.status.code(), Some(2));
+    }
+}
+
+#[test]
+fn end_picks_the_method_and_tolerates_no_snooze() {
+    let server = MockServer::start();
+    let end_snooze = server.mock(|when, then| {
+        when.method(POST).path("/api/dnd.endSnooze");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "snooze_not_active" }));
+    });
+    let end_dnd = server.mock(|when, then| {
+        when.method(POST).path("/api/dnd.endDnd");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = run(&server, &["dnd", "end"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "no snooze was active\n"
+    );
+    let out = run(&server, &["dnd", "end", "--dnd"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "do not disturb ended\n"
+    );
+    end_snooze.assert_hits(1);
+    end_dnd.assert_hits(1);
+}
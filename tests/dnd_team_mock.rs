@@ -0,0 +1,62 @@
+use assert_cmd::prelude::*;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+#[test]
+fn dnd_team_lists_available_people_first() {
+    let server = MockServer::start();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "members": [{ "id": "U2", "name": "raj", "profile": { "display_name": "raj" } }]
+        }));
+    });
+    let team = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/dnd.teamInfo")
+            .x_www_form_urlencoded_tuple("users", "U1,U2");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "users": {
+                "U1": { "dnd_enabled": true, "next_dnd_start_ts": now - 60, "next_dnd_end_ts": now + 3600 },
+                "U2": { "dnd_enabled": false }
+            }
+        }));
+    });
+    for (id, name) in [("U1", "jane"), ("U2", "raj")] {
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/api/users.info")
+                .x_www_form_urlencoded_tuple("user", id);
+            then.status(200).json_body(json!({
+                "ok": true,
+                "user": { "id": id, "name": name, "tz": "Asia/Tokyo", "tz_offset": 32400 }
+            }));
+        });
+    }
+
+    let out = Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["dnd-team", "--users", "U1,@raj"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let rows: Vec<Vec<&str>> = stdout
+        .lines()
+        .skip(1)
+        .map(|l| l.split_whitespace().collect())
+        .collect();
+    assert_eq!(rows[0][..2], ["raj", "off"]);
+    assert_eq!(rows[1][..2], ["jane", "on"]);
+    assert!(rows[1].ends_with(&["Asia/Tokyo"]), "{stdout}");
+    team.assert_hits(1);
+}
@@ -0,0 +1,92 @@
+use assert_cmd::prelude::*;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+fn file(server: &MockServer, id: &str, name: &str) -> serde_json::Value {
+    json!({
+        "id": id,
+        "name": name,
+        "url_private_download": server.url(format!("/dl/{id}"))
+    })
+}
+
+#[test]
+fn download_all_dedupes_names_and_continues_past_failures() {
+    let server = MockServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    let page1 = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.list")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("ts_from", "1717200000")
+            .x_www_form_urlencoded_tuple("page", "1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "files": [file(&server, "F1", "notes.txt"), file(&server, "F2", "notes.txt")],
+            "paging": { "count": 100, "total": 3, "page": 1, "pages": 2 }
+        }));
+    });
+    let page2 = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.list")
+            .x_www_form_urlencoded_tuple("page", "2");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "files": [file(&server, "F3", "gone.pdf")],
+            "paging": { "count": 100, "total": 3, "page": 2, "pages": 2 }
+        }));
+    });
+    for (id, body) in [("F1", "first"), ("F2", "second")] {
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/dl/{id}"))
+                .header("authorization", "Bearer xoxp-test");
+            then.status(200).body(body);
+        });
+    }
+    server.mock(|when, then| {
+        when.method(GET).path("/dl/F3");
+        then.status(404);
+    });
+
+    let out = Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args([
+            "download-all",
+            "--channel",
+            "C1",
+            "--since",
+            "2024-06-01",
+            "--out",
+        ])
+        .arg(dir.path())
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(1), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        format!(
+            "downloaded 2 of 3 files, 11 B to {}\n",
+            dir.path().display()
+        )
+    );
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("[1/3] "), "{err}");
+    assert!(err.contains("notes-1.txt (6 bytes)"), "{err}");
+    assert!(err.contains("[3/3] F3 failed: HTTP 404"), "{err}");
+    assert!(err.contains("1 file(s) could not be downloaded"), "{err}");
+    assert_eq!(
+        std::fs::read(dir.path().join("notes.txt")).unwrap(),
+        b"first"
+    );
+    assert_eq!(
+        std::fs::read(dir.path().join("notes-1.txt")).unwrap(),
+        b"second"
+    );
+    assert!(!dir.path().join("gone.pdf").exists());
+    page1.assert();
+    page2.assert();
+}
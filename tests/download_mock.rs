@@ -0,0 +1,108 @@
+use assert_cmd::prelude::*;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+fn download(server: &MockServer, extra: &[&str], out: &std::path::Path) -> std::process::Output {
+    Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["download", "--file", "F1", "--out"])
+        .arg(out)
+        .args(extra)
+        .output()
+        .unwrap()
+}
+
+fn file_info(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/files.info")
+            .query_param("file", "F1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "file": {
+                "id": "F1",
+                "name": "q3.pdf",
+                "url_private_download": server.url("/files-pri/T1-F1/download/q3.pdf")
+            }
+        }));
+    });
+}
+
+#[test]
+fn download_saves_into_directory_with_auth() {
+    let server = MockServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    file_info(&server);
+    let fetch = server.mock(|when, then| {
+        when.method(GET)
+            .path("/files-pri/T1-F1/download/q3.pdf")
+            .header("authorization", "Bearer xoxp-test");
+        then.status(200)
+            .header("content-type", "application/pdf")
+            .body("%PDF-1.4 numbers");
+    });
+
+    let out = download(&server, &[], dir.path());
+    assert!(out.status.success(), "{out:?}");
+    let path = dir.path().join("q3.pdf");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        format!("{}\t16 bytes\n", path.display())
+    );
+    assert_eq!(std::fs::read(&path).unwrap(), b"%PDF-1.4 numbers");
+    fetch.assert();
+
+    let again = download(&server, &[], dir.path());
+    assert!(!again.status.success());
+    assert!(String::from_utf8_lossy(&again.stderr).contains("pass --force"));
+    fetch.assert_hits(1);
+
+    std::fs::write(&path, b"old").unwrap();
+    let forced = download(&server, &["--force"], dir.path());
+    assert!(forced.status.success(), "{forced:?}");
+    assert_eq!(std::fs::read(&path).unwrap(), b"%PDF-1.4 numbers");
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn download_spots_the_sign_in_page() {
+    let server = MockServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    file_info(&server);
+    server.mock(|when, then| {
+        when.method(GET).path("/files-pri/T1-F1/download/q3.pdf");
+        then.status(200)
+            .header("content-type", "text/html; charset=utf-8")
+            .body("<html>sign in</html>");
+    });
+
+    let target = dir.path().join("out.pdf");
+    let out = download(&server, &[], &target);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("files:read"));
+    assert!(!target.exists());
+}
+
+#[test]
+fn download_outlasts_the_api_request_timeout() {
+    let server = MockServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    file_info(&server);
+    server.mock(|when, then| {
+        when.method(GET).path("/files-pri/T1-F1/download/q3.pdf");
+        then.status(200)
+            .header("content-type", "application/pdf")
+            .delay(std::time::Duration::from_secs(32))
+            .body("%PDF-1.4 slow");
+    });
+
+    let out = download(&server, &[], dir.path());
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        std::fs::read(dir.path().join("q3.pdf")).unwrap(),
+        b"%PDF-1.4 slow"
+    );
+}
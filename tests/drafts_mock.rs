@@ -0,0 +1,100 @@
+mod common;
+
+use assert_cmd::Command;
+use httpmock::prelude::*;
+use predicates::prelude::*;
+
+/// Points at a closed port, so any network use fails the command.
+fn offline(config: &tempfile::TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("mdslackcli").unwrap();
+    cmd.env_remove("SLACK_TOKEN")
+        .env("SLACK_API_BASE", "http://127.0.0.1:9/api")
+        .env("SLACK_CONFIG_DIR", config.path());
+    cmd
+}
+
+fn slack(server: &MockServer, config: &tempfile::TempDir) -> Command {
+    let mut cmd = common::slack(server);
+    cmd.env("SLACK_CONFIG_DIR", config.path());
+    cmd
+}
+
+#[test]
+fn drafts_are_saved_offline_and_removed_once_sent() {
+    let server = MockServer::start();
+    let config = tempfile::tempdir().unwrap();
+    let post = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.postMessage")
+            .x_www_form_urlencoded_tuple("channel", "C2")
+            .x_www_form_urlencoded_tuple("text", "second thoughts")
+            .x_www_form_urlencoded_tuple("thread_ts", "1.0");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": true, "ts": "2.0" }));
+    });
+
+    offline(&config)
+        .args(["draft", "save", "--channel", "C1", "--text", "first idea"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("saved draft 1"));
+    offline(&config)
+        .args(["draft", "save", "--channel", "C2", "--thread-ts", "1.0"])
+        .args(["--text", "second\n  thoughts"])
+        .assert()
+        .success();
+    offline(&config)
+        .args(["draft", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1\tC1\tfirst idea"))
+        .stdout(predicate::str::contains(
+            "2\tC2 (thread 1.0)\tsecond thoughts",
+        ));
+
+    // Make the stored text match the mock exactly.
+    offline(&config)
+        .env_remove("VISUAL")
+        .env("EDITOR", "sed -i -e '2d' -e '1s/$/ thoughts/'")
+        .args(["draft", "edit", "2"])
+        .assert()
+        .success();
+    slack(&server, &config)
+        .args(["draft", "send", "2"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("sent ok, ts=2.0"));
+    post.assert();
+
+    slack(&server, &config)
+        .args(["draft", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("first idea"))
+        .stdout(predicate::str::contains("thoughts").not());
+}
+
+#[test]
+fn failed_send_keeps_the_draft() {
+    let server = MockServer::start();
+    let config = tempfile::tempdir().unwrap();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/chat.postMessage");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": false, "error": "channel_not_found" }));
+    });
+    slack(&server, &config)
+        .args(["draft", "save", "--channel", "C404", "--text", "hello"])
+        .assert()
+        .success();
+    slack(&server, &config)
+        .args(["draft", "send", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("channel_not_found"));
+    slack(&server, &config)
+        .args(["draft", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1\tC404\thello"));
+}
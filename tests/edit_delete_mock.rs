@@ -0,0 +1,127 @@
+mod common;
+
+use common::slack;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn edit_updates_the_message_from_stdin() {
+    let server = MockServer::start();
+    let update = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.update")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("ts", "1.0")
+            .x_www_form_urlencoded_tuple("text", "fixed text");
+        then.status(200)
+            .json_body(json!({ "ok": true, "channel": "C1", "ts": "1.0" }));
+    });
+
+    let out = slack(&server)
+        .args(["edit", "--channel", "C1", "--ts", "1.0", "--text", "-"])
+        .write_stdin("fixed text\n")
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "edited ok, ts=1.0\n");
+    update.assert();
+}
+
+#[test]
+fn edit_explains_slack_refusals_and_empty_text() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/chat.update");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "cant_update_message" }));
+    });
+
+    let out = slack(&server)
+        .args(["edit", "--channel", "C1", "--ts", "1.0", "--text", "x"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("only edit your own messages"), "{stderr}");
+
+    let out = slack(&server)
+        .args(["edit", "--channel", "C1", "--ts", "1.0", "--text", "  "])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("--text is empty"));
+}
+
+#[test]
+fn delete_with_yes_skips_the_lookup() {
+    let server = MockServer::start();
+    let history = server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.history");
+        then.status(200)
+            .json_body(json!({ "ok": true, "messages": [] }));
+    });
+    let delete = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.delete")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("ts", "1.0");
+        then.status(200)
+            .json_body(json!({ "ok": true, "channel": "C1", "ts": "1.0" }));
+    });
+
+    let out = slack(&server)
+        .args(["delete", "--channel", "C1", "--ts", "1.0", "--yes"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "deleted ts=1.0\n");
+    delete.assert();
+    history.assert_hits(0);
+}
+
+#[test]
+fn delete_of_someone_elses_message_fails_clearly() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/chat.delete");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "cant_delete_message" }));
+    });
+
+    let out = slack(&server)
+        .args(["delete", "--channel", "C1", "--ts", "1.0", "--yes"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("only delete its own messages"), "{stderr}");
+    assert!(stderr.contains("cant_delete_message"), "{stderr}");
+}
+
+#[test]
+fn delete_without_a_terminal_looks_up_the_message_and_refuses() {
+    let server = MockServer::start();
+    let history = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.history")
+            .x_www_form_urlencoded_tuple("latest", "1.0")
+            .x_www_form_urlencoded_tuple("inclusive", "true");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "messages": [{ "ts": "1.0", "user": "U1", "text": "oops" }]
+        }));
+    });
+    let delete = server.mock(|when, then| {
+        when.method(POST).path("/api/chat.delete");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = slack(&server)
+        .args(["delete", "--channel", "C1", "--ts", "1.0"])
+        .write_stdin("")
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    history.assert();
+    delete.assert_hits(0);
+}
@@ -0,0 +1,67 @@
+use assert_cmd::prelude::*;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+#[test]
+fn emoji_download_saves_images_lists_aliases_and_reports_failures() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/emoji.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "emoji": {
+                "shipit": server.url("/cdn/T1/shipit/aa11.png"),
+                "parrot": server.url("/cdn/T1/parrot/bb22.gif"),
+                "gone": server.url("/cdn/T1/gone/cc33.png"),
+                "party": "alias:parrot"
+            }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/cdn/T1/shipit/aa11.png");
+        then.status(200).body("png-bytes");
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/cdn/T1/parrot/bb22.gif");
+        then.status(200).body("gif");
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/cdn/T1/gone/cc33.png");
+        then.status(404);
+    });
+    let dir = tempfile::tempdir().unwrap();
+
+    let out = Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["emoji-download", "--out", dir.path().to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert_eq!(
+        std::fs::read(dir.path().join("shipit.png")).unwrap(),
+        b"png-bytes"
+    );
+    assert_eq!(
+        std::fs::read(dir.path().join("parrot.gif")).unwrap(),
+        b"gif"
+    );
+    assert!(!dir.path().join("gone.png").exists());
+    let aliases: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(dir.path().join("aliases.json")).unwrap()).unwrap();
+    assert_eq!(aliases, json!({ "party": "parrot" }));
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.starts_with("downloaded 2 of 3 emoji"), "{stdout}");
+    assert!(
+        stdout.contains("1 alias(es) listed in aliases.json"),
+        "{stdout}"
+    );
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("gone: HTTP 404"), "{stderr}");
+    assert!(
+        stderr.contains("1 emoji could not be downloaded"),
+        "{stderr}"
+    );
+}
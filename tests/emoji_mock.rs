@@ -0,0 +1,56 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn stub_emoji(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST).path("/api/emoji.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "emoji": {
+                "shipit": "https://emoji.example/shipit.png",
+                "party-parrot": "https://emoji.example/party-parrot.gif",
+                "partyparrot": "alias:party-parrot",
+                "Party-Blob": "https://emoji.example/party-blob.png"
+            }
+        }));
+    });
+}
+
+#[test]
+fn emoji_lists_sorted_with_aliases_and_count() {
+    let server = MockServer::start();
+    stub_emoji(&server);
+
+    let out = run(&server, &["emoji", "--filter", "PARTY"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "Party-Blob\thttps://emoji.example/party-blob.png\n\
+         party-parrot\thttps://emoji.example/party-parrot.gif\n\
+         partyparrot\talias of party-parrot\n\
+         3 of 4 custom emoji match `PARTY`\n"
+    );
+}
+
+#[test]
+fn emoji_format_json() {
+    let server = MockServer::start();
+    stub_emoji(&server);
+
+    let out = run(
+        &server,
+        &["emoji", "--filter", "parrot", "--format", "json"],
+    );
+    assert!(out.status.success(), "{out:?}");
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(
+        v,
+        json!([
+            { "name": "party-parrot", "url": "https://emoji.example/party-parrot.gif", "alias_of": null },
+            { "name": "partyparrot", "url": null, "alias_of": "party-parrot" }
+        ])
+    );
+}
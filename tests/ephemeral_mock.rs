@@ -0,0 +1,52 @@
+use assert_cmd::prelude::*;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+fn ephemeral(server: &MockServer) -> std::process::Output {
+    Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxb-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["ephemeral", "--channel", "C1", "--user", "U2"])
+        .args(["--text", "only you can see this"])
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn ephemeral_posts_to_one_user() {
+    let server = MockServer::start();
+    let post = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.postEphemeral")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("user", "U2")
+            .x_www_form_urlencoded_tuple("text", "only you can see this");
+        then.status(200)
+            .json_body(json!({ "ok": true, "message_ts": "1502210682.580145" }));
+    });
+
+    let out = ephemeral(&server);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "sent ok, message_ts=1502210682.580145\n"
+    );
+    post.assert();
+}
+
+#[test]
+fn user_outside_the_channel_gets_a_hint() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/chat.postEphemeral");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "user_not_in_channel" }));
+    });
+
+    let out = ephemeral(&server);
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("U2 must be a member of C1"), "{stderr}");
+}
@@ -0,0 +1,115 @@
+use assert_cmd::prelude::*;
+use httpmock::prelude::*;
+use std::process::Command;
+
+fn fixture(name: &str) -> String {
+    format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR"))
+}
+
+fn mock_channel(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.history");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body_from_file(fixture("export_history.json"));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.replies");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body_from_file(fixture("export_replies.json"));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body_from_file(fixture("export_users.json"));
+    });
+}
+
+fn export(server: &MockServer, extra: &[&str]) -> String {
+    let mut cmd = Command::cargo_bin("mdslackcli").unwrap();
+    cmd.env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["export", "--channel", "C1"])
+        .args(extra);
+    let out = cmd.assert().success().get_output().stdout.clone();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn html_export_structure() {
+    let server = MockServer::start();
+    mock_channel(&server);
+    let html = export(&server, &["--format", "html"]);
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<title>C1</title>"));
+    // Grouped by UTC day, oldest first.
+    let day1 = html.find("<h2>2024-04-05</h2>").expect("first day header");
+    let day2 = html.find("<h2>2024-04-06</h2>").expect("second day header");
+    assert!(day1 < day2);
+    // Authors and avatars come from the users map.
+    assert!(html.contains("<span class=\"author\">alice</span>"));
+    assert!(html.contains("<img class=\"avatar\" src=\"https://avatars.example/alice.png\""));
+    // mrkdwn rendering and mentions.
+    assert!(html.contains("<b>Deploy</b>"));
+    assert!(html.contains("<code>api</code>"));
+    assert!(html.contains("<i>today</i>"));
+    assert!(html.contains("<b>@bob&lt;b&gt;</b>"));
+    // Thread replies are indented under their parent.
+    let replies = html.find("<div class=\"replies\">").expect("replies block");
+    assert!(html[replies..].contains("thanks!"));
+    assert!(replies < day2);
+    // Reaction chips.
+    assert!(html.contains("<span class=\"reaction\">:eyes: 3</span>"));
+    // User content is escaped, links keep a safe href.
+    assert!(!html.contains("<script>"));
+    assert!(html.contains("&lt;script&gt;alert(&#39;pwned&#39;)&lt;/script&gt;"));
+    assert!(html.contains("<a href=\"https://example.com/doc?a=1&amp;b=2\">the doc</a>"));
+}
+
+#[test]
+fn text_export_indents_replies() {
+    let server = MockServer::start();
+    mock_channel(&server);
+    let text = export(&server, &[]);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("1712345678.000100 U1: *Deploy*"));
+    assert_eq!(lines[1], "    1712345700.000300 U2: thanks!");
+    assert!(lines[2].starts_with("1712432000.000200 U2:"));
+}
+
+#[test]
+fn embed_avatars_fetches_only_the_authors() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200).json_body(serde_json::json!({
+            "ok": true,
+            "members": [
+                { "id": "U1", "name": "alice", "profile": { "image_72": server.url("/avatars/alice.png") } },
+                { "id": "U9", "name": "zed", "profile": { "image_72": server.url("/avatars/zed.png") } }
+            ]
+        }));
+    });
+    mock_channel(&server);
+    let alice = server.mock(|when, then| {
+        when.method(GET).path("/avatars/alice.png");
+        then.status(200)
+            .header("content-type", "image/png")
+            .body("png");
+    });
+    let zed = server.mock(|when, then| {
+        when.method(GET).path("/avatars/zed.png");
+        then.status(200)
+            .header("content-type", "image/png")
+            .body("png");
+    });
+
+    let html = export(&server, &["--format", "html", "--embed-avatars"]);
+    alice.assert();
+    assert_eq!(zed.hits(), 0);
+    assert!(html.contains("src=\"data:image/png;base64,"));
+}
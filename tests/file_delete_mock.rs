@@ -0,0 +1,84 @@
+mod common;
+
+use common::slack;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn file_delete_with_yes_skips_the_prompt() {
+    let server = MockServer::start();
+    let info = server.mock(|when, then| {
+        when.method(GET).path("/api/files.info");
+        then.status(200)
+            .json_body(json!({ "ok": true, "file": { "id": "F1" } }));
+    });
+    let delete = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.delete")
+            .x_www_form_urlencoded_tuple("file", "F1");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = slack(&server)
+        .args(["file-delete", "--file", "F1", "--yes"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "deleted F1\n");
+    delete.assert();
+    info.assert_hits(0);
+}
+
+#[test]
+fn file_delete_explains_ownership_errors() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/files.delete");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "cant_delete_file" }));
+    });
+
+    let out = slack(&server)
+        .args(["file-delete", "--file", "F1", "--yes"])
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(1));
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        err.contains("only F1's uploader or a workspace admin can delete it"),
+        "{err}"
+    );
+}
+
+#[test]
+fn file_delete_without_yes_asks_first() {
+    let server = MockServer::start();
+    let info = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/files.info")
+            .query_param("file", "F1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "file": { "id": "F1", "name": "secrets.txt", "user": "U1" }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200)
+            .json_body(json!({ "ok": true, "members": [] }));
+    });
+    let delete = server.mock(|when, then| {
+        when.method(POST).path("/api/files.delete");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = slack(&server)
+        .args(["file-delete", "--file", "F1"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    // No terminal here, so the prompt itself fails and nothing is deleted.
+    assert!(String::from_utf8_lossy(&out.stderr).contains("failed to read confirmation"));
+    info.assert();
+    delete.assert_hits(0);
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::slack;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn enable_prints_name_and_public_link() {
+    let server = MockServer::start();
+    let share = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.sharedPublicURL")
+            .x_www_form_urlencoded_tuple("file", "F1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "file": {
+                "id": "F1",
+                "name": "screenshot.png",
+                "permalink_public": "https://slack-files.com/T1-F1-abc"
+            }
+        }));
+    });
+
+    let out = slack(&server)
+        .args(["file-public", "--file", "F1", "--enable"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "public link for screenshot.png (F1)\nhttps://slack-files.com/T1-F1-abc\n"
+    );
+    share.assert();
+}
+
+#[test]
+fn revoke_prints_name() {
+    let server = MockServer::start();
+    let revoke = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.revokePublicURL")
+            .x_www_form_urlencoded_tuple("file", "F1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "file": { "id": "F1", "name": "screenshot.png" }
+        }));
+    });
+
+    let out = slack(&server)
+        .args(["file-public", "--file", "F1", "--revoke"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "public link revoked for screenshot.png (F1)\n"
+    );
+    revoke.assert();
+}
+
+#[test]
+fn scope_errors_are_explained_and_flags_are_required() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/files.sharedPublicURL");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "missing_scope" }));
+    });
+
+    let out = slack(&server)
+        .args(["file-public", "--file", "F1", "--enable"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("user token (xoxp-) with files:write"));
+
+    let neither = slack(&server)
+        .args(["file-public", "--file", "F1"])
+        .output()
+        .unwrap();
+    assert_eq!(neither.status.code(), Some(2));
+    let both = slack(&server)
+        .args(["file-public", "--file", "F1", "--enable", "--revoke"])
+        .output()
+        .unwrap();
+    assert_eq!(both.status.code(), Some(2));
+}
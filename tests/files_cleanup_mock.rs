@@ -0,0 +1,90 @@
+use assert_cmd::prelude::*;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+fn cleanup(server: &MockServer, extra: &[&str]) -> std::process::Output {
+    Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["files-cleanup", "--older-than", "365d", "--types", "zips"])
+        .args(extra)
+        .output()
+        .unwrap()
+}
+
+fn stub_listing(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST).path("/api/auth.test");
+        then.status(200)
+            .json_body(json!({ "ok": true, "team_id": "T1", "user_id": "U1" }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.list")
+            .x_www_form_urlencoded_tuple("user", "U1")
+            .x_www_form_urlencoded_tuple("types", "zips")
+            .matches(|req| {
+                String::from_utf8_lossy(req.body.as_deref().unwrap_or_default()).contains("ts_to=")
+            });
+        then.status(200).json_body(json!({
+            "ok": true,
+            "files": [
+                { "id": "F1", "name": "a.zip", "size": 1024, "mimetype": "application/zip", "user": "U1", "created": 1_600_000_000 },
+                { "id": "F2", "name": "b.zip", "size": 2048, "mimetype": "application/zip", "user": "U1", "created": 1_600_000_000 }
+            ],
+            "paging": { "count": 100, "total": 2, "page": 1, "pages": 1 }
+        }));
+    });
+}
+
+#[test]
+fn dry_run_lists_and_sums_without_deleting() {
+    let server = MockServer::start();
+    stub_listing(&server);
+    let delete = server.mock(|when, then| {
+        when.method(POST).path("/api/files.delete");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = cleanup(&server, &["--dry-run"]);
+    assert!(out.status.success(), "{out:?}");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.starts_with("F1\ta.zip\t1.0 KiB\t"), "{stdout}");
+    assert!(
+        stdout.ends_with("2 files, 3.0 KiB (dry run; pass --execute to delete them)\n"),
+        "{stdout}"
+    );
+    // Dry run is also the default.
+    assert!(cleanup(&server, &[]).status.success());
+    delete.assert_hits(0);
+}
+
+#[test]
+fn execute_deletes_and_reports_failures() {
+    let server = MockServer::start();
+    stub_listing(&server);
+    let ok = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.delete")
+            .x_www_form_urlencoded_tuple("file", "F1");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+    let refused = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.delete")
+            .x_www_form_urlencoded_tuple("file", "F2");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "cant_delete_file" }));
+    });
+
+    let out = cleanup(&server, &["--execute"]);
+    assert_eq!(out.status.code(), Some(1), "{out:?}");
+    assert!(String::from_utf8_lossy(&out.stdout).ends_with("deleted 1 files, 1.0 KiB freed\n"));
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("failed to delete F2"), "{err}");
+    assert!(err.contains("1 file(s) could not be deleted"), "{err}");
+    ok.assert();
+    refused.assert();
+}
@@ -0,0 +1,172 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use common::slack;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+fn file(id: &str, created: u64) -> serde_json::Value {
+    json!({
+        "id": id,
+        "name": format!("{id}.png"),
+        "size": 2048,
+        "mimetype": "image/png",
+        "user": "U1",
+        "created": created
+    })
+}
+
+#[test]
+fn files_filters_and_pages_to_limit() {
+    let server = MockServer::start();
+    let page1 = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.list")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("user", "U1")
+            .x_www_form_urlencoded_tuple("types", "images,pdfs")
+            .x_www_form_urlencoded_tuple("ts_from", "1711929600")
+            .x_www_form_urlencoded_tuple("ts_to", "1712361599")
+            .x_www_form_urlencoded_tuple("count", "2")
+            .x_www_form_urlencoded_tuple("page", "1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "files": [file("F1", 1_712_275_200), file("F2", 1_712_188_800)],
+            "paging": { "count": 2, "total": 3, "page": 1, "pages": 2 }
+        }));
+    });
+    let page2 = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.list")
+            .x_www_form_urlencoded_tuple("page", "2");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "files": [file("F3", 1_712_102_400)],
+            "paging": { "count": 2, "total": 3, "page": 2, "pages": 2 }
+        }));
+    });
+    let users = server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "members": [{ "id": "U1", "name": "jane", "profile": { "display_name": "jane" } }]
+        }));
+    });
+
+    let out = slack(&server)
+        .args([
+            "files",
+            "--channel",
+            "C1",
+            "--user",
+            "U1",
+            "--types",
+            "images,pdfs",
+            "--from",
+            "2024-04-01",
+            "--to",
+            "2024-04-05",
+            "--limit",
+            "2",
+        ])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "F1\tF1.png\t2.0 KiB\timage/png\t@jane\t2024-04-05 00:00\n\
+         F2\tF2.png\t2.0 KiB\timage/png\t@jane\t2024-04-04 00:00\n"
+    );
+    page1.assert();
+    page2.assert_hits(0);
+    users.assert();
+
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.list")
+            .x_www_form_urlencoded_tuple("page", "1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "files": [file("F1", 1_712_275_200), file("F2", 1_712_188_800)],
+            "paging": { "count": 5, "total": 3, "page": 1, "pages": 2 }
+        }));
+    });
+    let all = slack(&server)
+        .args(["files", "--limit", "5"])
+        .output()
+        .unwrap();
+    assert!(all.status.success(), "{all:?}");
+    assert_eq!(String::from_utf8_lossy(&all.stdout).lines().count(), 3);
+    page2.assert();
+}
+
+#[test]
+fn files_rejects_bad_dates_before_calling_slack() {
+    let server = MockServer::start();
+    let list = server.mock(|when, then| {
+        when.method(POST).path("/api/files.list");
+        then.status(200)
+            .json_body(json!({ "ok": true, "files": [] }));
+    });
+
+    let out = slack(&server)
+        .args(["files", "--from", "last week"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("is not a date"));
+    list.assert_hits(0);
+}
+
+#[test]
+fn files_prints_the_first_page_before_the_second_arrives() {
+    use std::io::{BufRead, BufReader};
+    use std::time::{Duration, Instant};
+
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.list")
+            .x_www_form_urlencoded_tuple("page", "2");
+        then.status(200)
+            .delay(Duration::from_secs(2))
+            .json_body(json!({
+                "ok": true,
+                "files": [file("F2", 1_712_188_800)],
+                "paging": { "page": 2, "pages": 2 }
+            }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/files.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "files": [file("F1", 1_712_275_200)],
+            "paging": { "page": 1, "pages": 2 }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200)
+            .json_body(json!({ "ok": true, "members": [] }));
+    });
+
+    let start = Instant::now();
+    let mut child = Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["files", "--limit", "5"])
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut lines = BufReader::new(child.stdout.take().unwrap()).lines();
+    assert!(lines.next().unwrap().unwrap().starts_with("F1\t"));
+    let first_at = start.elapsed();
+    let rest: Vec<String> = lines.map(Result::unwrap).collect();
+    assert!(child.wait().unwrap().success());
+
+    assert_eq!(rest.len(), 1);
+    assert!(rest[0].starts_with("F2\t"));
+    assert!(first_at + Duration::from_millis(1500) < start.elapsed());
+}
@@ -0,0 +1,321 @@
+use assert_cmd::prelude::*;
+use httpmock::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+fn mock_workspace(server: &MockServer) -> httpmock::Mock<'_> {
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200).json_body(serde_json::json!({
+            "ok": true,
+            "members": [
+                { "id": "U1", "name": "alice", "profile": { "real_name": "Alice Admin", "title": "SRE Manager", "phone": "+1 (555) 010-4477" }, "is_admin": true },
+                { "id": "U2", "name": "gary", "profile": { "real_name": "Gary Guest" }, "is_restricted": true },
+                { "id": "U3", "name": "sam", "profile": { "real_name": "Sam Single" }, "is_restricted": true, "is_ultra_restricted": true },
+                { "id": "U4", "name": "robo", "profile": { "real_name": "Robo Bot" }, "is_bot": true },
+                { "id": "U5", "name": "mia", "tz": "Europe/Berlin", "tz_offset": 7200, "profile": { "real_name": "Mia Member", "status_emoji": ":train:", "status_text": "Commuting, answering slowly until about ten o'clock" } },
+                { "id": "U6", "name": "olga", "profile": { "real_name": "Olga Former" }, "deleted": true }
+            ]
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.list");
+        then.status(200).json_body(serde_json::json!({
+            "ok": true,
+            "channels": [{ "id": "D5", "is_im": true, "user": "U5" }]
+        }));
+    })
+}
+
+fn find_person(server: &MockServer, extra: &[&str]) -> Command {
+    let mut cmd = Command::cargo_bin("mdslackcli").unwrap();
+    cmd.env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["find-person", "--query", ""])
+        .args(extra);
+    cmd
+}
+
+#[test]
+fn find_person_renders_account_flags() {
+    let server = MockServer::start();
+    let ims = mock_workspace(&server);
+
+    find_person(&server, &["--include-bots", "--include-deleted"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "U1\t-\t@alice\tAlice Admin\t\t[admin]\tSRE Manager\t+1 (555) 010-4477\n",
+        ))
+        .stdout(predicate::str::contains(
+            "U2\t-\t@gary\tGary Guest\t\t[guest]\t\t\n",
+        ))
+        .stdout(predicate::str::contains(
+            "U3\t-\t@sam\tSam Single\t\t[single-channel]\t\t\n",
+        ))
+        .stdout(predicate::str::contains(
+            "U4\t-\t@robo\tRobo Bot\t\t[bot]\t\t\n",
+        ))
+        .stdout(predicate::str::contains(
+            "U5\t-\t@mia\tMia Member\t\t\t\t\n",
+        ))
+        .stdout(predicate::str::contains(
+            "U6\t-\t@olga (deactivated)\tOlga Former\t\t[deleted]\t\t\n",
+        ));
+    // The DM column is skipped by default, so no IM listing happens.
+    ims.assert_hits(0);
+}
+
+#[test]
+fn find_person_hides_bots_and_deactivated_by_default() {
+    let server = MockServer::start();
+    mock_workspace(&server);
+
+    find_person(&server, &[])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("@mia"))
+        .stdout(predicate::str::contains("@robo").not())
+        .stdout(predicate::str::contains("@olga").not());
+
+    find_person(&server, &["--include-deleted"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("@olga (deactivated)"))
+        .stdout(predicate::str::contains("@robo").not());
+}
+
+#[test]
+fn find_person_verbose_adds_time_zone_and_status() {
+    let server = MockServer::start();
+    mock_workspace(&server);
+
+    let out = find_person(&server, &["--verbose"]).output().unwrap();
+    assert!(out.status.success(), "{out:?}");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let mia = stdout.lines().find(|l| l.starts_with("U5\t")).unwrap();
+    assert!(
+        mia.starts_with("U5\t-\t@mia\tMia Member\t\t\t\t\tEurope/Berlin (now "),
+        "{mia}"
+    );
+    assert!(
+        mia.ends_with(")\t:train: Commuting, answering slowly unt…"),
+        "{mia}"
+    );
+    // No zone or status: empty cells, same column count.
+    let sam = stdout.lines().find(|l| l.starts_with("U3\t")).unwrap();
+    assert!(sam.ends_with("[single-channel]\t\t\t\t"), "{sam}");
+
+    // The compact output is unchanged.
+    find_person(&server, &[])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "U5\t-\t@mia\tMia Member\t\t\t\t\n",
+        ));
+}
+
+#[test]
+fn find_person_with_dm_fills_dm_column() {
+    let server = MockServer::start();
+    let ims = mock_workspace(&server);
+
+    find_person(&server, &["--with-dm"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("U5\tD5\t@mia"))
+        .stdout(predicate::str::contains("U1\t-\t@alice"));
+    ims.assert_hits(1);
+}
+
+#[test]
+fn find_person_only_guests_filters() {
+    let server = MockServer::start();
+    mock_workspace(&server);
+
+    find_person(&server, &["--only", "guests"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("@gary"))
+        .stdout(predicate::str::contains("@sam"))
+        .stdout(predicate::str::contains("@alice").not())
+        .stdout(predicate::str::contains("@robo").not())
+        .stdout(predicate::str::contains("@mia").not());
+}
+
+#[test]
+fn find_person_only_admins_and_bots() {
+    let server = MockServer::start();
+    mock_workspace(&server);
+
+    find_person(&server, &["--only", "admins"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("@alice"))
+        .stdout(predicate::str::contains("@gary").not());
+
+    find_person(&server, &["--only", "bots"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("@robo"))
+        .stdout(predicate::str::contains("@alice").not());
+}
+
+#[test]
+fn find_person_table_aligns_columns() {
+    let server = MockServer::start();
+    mock_workspace(&server);
+
+    find_person(&server, &["--only", "guests", "--table"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "USER  DM  NAME   REAL NAME   EMAIL  FLAGS             TITLE  PHONE\n",
+        ))
+        .stdout(predicate::str::contains(
+            "U2    -   @gary  Gary Guest         [guest]\n",
+        ))
+        .stdout(predicate::str::contains(
+            "U3    -   @sam   Sam Single         [single-channel]\n",
+        ));
+}
+
+#[test]
+fn find_person_ranks_fuzzy_matches_after_substrings() {
+    let server = MockServer::start();
+    mock_workspace(&server);
+
+    let run = |query: &str, extra: &[&str]| {
+        let out = Command::cargo_bin("mdslackcli")
+            .unwrap()
+            .env("SLACK_TOKEN", "xoxp-test")
+            .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+            .args(["find-person", "--query", query])
+            .args(extra)
+            .output()
+            .unwrap();
+        assert!(out.status.success(), "{out:?}");
+        String::from_utf8_lossy(&out.stdout)
+            .lines()
+            .map(|l| l.split('\t').next().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+    // "mia member" is exact; "Mia Membr" is only close to it.
+    assert_eq!(run("Mia Membr", &[]), ["U5"]);
+    assert!(run("Mia Membr", &["--exact"]).is_empty());
+    // Everyone matches the empty query, in display-name order.
+    assert_eq!(run("", &[]), ["U1", "U2", "U5", "U3"]);
+}
+
+#[test]
+fn find_person_searches_title_and_phone() {
+    let server = MockServer::start();
+    mock_workspace(&server);
+
+    for query in ["sre manager", "555-010-4477", "5550104477"] {
+        let out = Command::cargo_bin("mdslackcli")
+            .unwrap()
+            .env("SLACK_TOKEN", "xoxp-test")
+            .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+            .args(["find-person", "--query", query, "--exact"])
+            .output()
+            .unwrap();
+        assert!(out.status.success(), "{out:?}");
+        assert_eq!(
+            String::from_utf8_lossy(&out.stdout),
+            "U1\t-\t@alice\tAlice Admin\t\t[admin]\tSRE Manager\t+1 (555) 010-4477\n",
+            "{query}"
+        );
+    }
+}
+
+#[test]
+fn find_person_open_dms_a_single_match() {
+    let server = MockServer::start();
+    mock_workspace(&server);
+    let open = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.open")
+            .x_www_form_urlencoded_tuple("users", "U5");
+        then.status(200).json_body(serde_json::json!({
+            "ok": true,
+            "channel": { "id": "D5" }
+        }));
+    });
+    let post = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.postMessage")
+            .x_www_form_urlencoded_tuple("channel", "D5")
+            .x_www_form_urlencoded_tuple("text", "got a sec?");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": true, "ts": "1.0" }));
+    });
+
+    let mut cmd = Command::cargo_bin("mdslackcli").unwrap();
+    cmd.env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args([
+            "find-person",
+            "--query",
+            "Mia Member",
+            "--open",
+            "--text",
+            "got a sec?",
+        ])
+        .assert()
+        .success()
+        .stdout("opened channel: D5\n");
+    open.assert_hits(1);
+    post.assert_hits(1);
+}
+
+#[test]
+fn find_person_open_refuses_several_matches() {
+    let server = MockServer::start();
+    mock_workspace(&server);
+    let open = server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.open");
+        then.status(200).json_body(serde_json::json!({
+            "ok": true,
+            "channel": { "id": "D1" }
+        }));
+    });
+
+    find_person(&server, &["--only", "guests", "--open"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("@gary"))
+        .stdout(predicate::str::contains("@sam"))
+        .stderr(predicate::str::contains("2 people match"));
+    open.assert_hits(0);
+}
+
+#[test]
+fn find_person_on_grid_filters_by_team_and_shows_it() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200).json_body(serde_json::json!({
+            "ok": true,
+            "members": [
+                { "id": "W1", "name": "jane", "team_id": "T1", "profile": { "real_name": "Jane Doe" } },
+                { "id": "W2", "name": "janet", "team_id": "T2", "profile": { "real_name": "Janet Ito" } }
+            ]
+        }));
+    });
+
+    find_person(&server, &[])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "W1\t-\t@jane\tJane Doe\t\t\t\t\tT1\n",
+        ))
+        .stdout(predicate::str::contains(
+            "W2\t-\t@janet\tJanet Ito\t\t\t\t\tT2\n",
+        ));
+
+    find_person(&server, &["--team", "T2"])
+        .assert()
+        .success()
+        .stdout("W2\t-\t@janet\tJanet Ito\t\t\t\t\tT2\n");
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use assert_cmd::Command;
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn slack(server: &MockServer, config: &tempfile::TempDir) -> Command {
+    let mut cmd = common::slack(server);
+    cmd.env("SLACK_TOKEN", "xoxp-org")
+        .env("SLACK_CONFIG_DIR", config.path())
+        .env("SLACK_STATE_DIR", config.path());
+    cmd
+}
+
+fn mock_grid_auth(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST).path("/api/auth.test");
+        then.status(200).json_body(json!({
+            "ok": true, "team": "Org", "team_id": "E1", "user_id": "W123",
+            "enterprise_id": "E1"
+        }));
+    });
+}
+
+#[test]
+fn whoami_lists_grid_workspaces() {
+    let server = MockServer::start();
+    let config = tempfile::tempdir().unwrap();
+    mock_grid_auth(&server);
+    let teams = server.mock(|when, then| {
+        when.method(POST).path("/api/auth.teams.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "teams": [{ "id": "T1", "name": "Sales" }, { "id": "T2", "name": "Eng" }]
+        }));
+    });
+
+    let out = slack(&server, &config).arg("whoami").output().unwrap();
+    assert!(out.status.success(), "{out:?}");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("user_id: Some(\"W123\")"), "{stdout}");
+    assert!(stdout.contains("enterprise_id: Some(\"E1\")"), "{stdout}");
+    assert!(stdout.contains("  T1\tSales\n  T2\tEng\n"), "{stdout}");
+    teams.assert();
+}
+
+#[test]
+fn team_id_is_sent_to_team_scoped_methods() {
+    let server = MockServer::start();
+    let config = tempfile::tempdir().unwrap();
+    mock_grid_auth(&server);
+    let scoped = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.list")
+            .x_www_form_urlencoded_tuple("team_id", "T2");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channels": [{ "id": "C2", "name": "eng-only" }]
+        }));
+    });
+
+    let out = slack(&server, &config)
+        .args(["--team-id", "T2", "channels"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert!(String::from_utf8_lossy(&out.stdout).contains("C2\t#eng-only"));
+
+    // The same workspace as a config.toml default.
+    std::fs::write(config.path().join("config.toml"), "team_id = \"T2\"\n").unwrap();
+    let out = slack(&server, &config).arg("channels").output().unwrap();
+    assert!(out.status.success(), "{out:?}");
+    scoped.assert_hits(2);
+}
+
+#[test]
+fn unknown_team_id_points_at_whoami() {
+    let server = MockServer::start();
+    let config = tempfile::tempdir().unwrap();
+    mock_grid_auth(&server);
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.list");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "team_access_not_granted" }));
+    });
+
+    let out = slack(&server, &config)
+        .args(["--team-id", "T9", "channels"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("team_access_not_granted"), "{stderr}");
+    assert!(stderr.contains("rejected team_id T9"), "{stderr}");
+    assert!(stderr.contains("slack whoami"), "{stderr}");
+}
@@ -10,7 +10,11 @@ fn top_level_help_shows_aggregate_and_examples() {
         .success()
         .stdout(predicate::str::contains("COMMAND DETAILS:"))
         .stdout(predicate::str::contains("EXAMPLES:"))
-        .stdout(predicate::str::contains("== send =="));
+        .stdout(predicate::str::contains("== send =="))
+        .stdout(predicate::str::contains("== me =="))
+        .stdout(predicate::str::contains(
+            "me --channel C12345678 --text \"is deploying\"",
+        ));
 }
 
 #[test]
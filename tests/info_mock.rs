@@ -0,0 +1,92 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn user(server: &MockServer, id: &str, name: &str) {
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.info")
+            .x_www_form_urlencoded_tuple("user", id);
+        then.status(200).json_body(json!({
+            "ok": true,
+            "user": { "id": id, "name": name, "profile": { "display_name": name } }
+        }));
+    });
+}
+
+#[test]
+fn info_prints_a_channel_with_its_creator() {
+    let server = MockServer::start();
+    let info = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.info")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("include_num_members", "true");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channel": {
+                "id": "C1",
+                "name": "ops",
+                "is_channel": true,
+                "is_private": false,
+                "topic": { "value": "Pager rotation" },
+                "purpose": { "value": "" },
+                "num_members": 12,
+                "creator": "U1",
+                "created": 1_712_345_678,
+                "is_archived": false,
+                "is_ext_shared": true
+            }
+        }));
+    });
+    user(&server, "U1", "ana");
+
+    let out = run(&server, &["info", "--channel", "C1"]);
+    assert!(out.status.success(), "{out:?}");
+    info.assert();
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "id: C1\nname: #ops\ntype: public\ntopic: Pager rotation\npurpose: -\nmembers: 12\n\
+         creator: @ana (U1)\ncreated: 2024-04-05 19:34 UTC\narchived: no\nshared externally: yes\n"
+    );
+}
+
+#[test]
+fn info_resolves_the_other_person_in_an_im() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.info")
+            .x_www_form_urlencoded_tuple("channel", "D1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channel": { "id": "D1", "is_im": true, "user": "U2", "created": 1_712_345_678 }
+        }));
+    });
+    user(&server, "U2", "bo");
+
+    let out = run(&server, &["info", "--channel", "D1", "--format", "json"]);
+    assert!(out.status.success(), "{out:?}");
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(v["type"], "im");
+    assert_eq!(v["with"], "@bo (U2)");
+    assert_eq!(v["name"], serde_json::Value::Null);
+    assert_eq!(v["archived"], false);
+}
+
+#[test]
+fn info_explains_an_unknown_channel() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.info");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "channel_not_found" }));
+    });
+
+    let out = run(&server, &["info", "--channel", "C9"]);
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("no conversation C9 visible to you"), "{err}");
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn invite_treats_already_in_channel_as_a_notice() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.lookupByEmail")
+            .x_www_form_urlencoded_tuple("email", "kim@example.com");
+        then.status(200)
+            .json_body(json!({ "ok": true, "user": { "id": "U2", "name": "kim" } }));
+    });
+    let invite = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.invite")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("users", "U1,U2")
+            .x_www_form_urlencoded_tuple("force", "true");
+        then.status(200).json_body(json!({
+            "ok": false,
+            "error": "already_in_channel",
+            "errors": [{ "user": "U1", "ok": false, "error": "already_in_channel" }]
+        }));
+    });
+
+    let out = run(
+        &server,
+        &["invite", "--channel", "C1", "--users", "U1,kim@example.com"],
+    );
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "USER                  RESULT\n\
+         U1                    already in channel\n\
+         kim@example.com (U2)  invited\n"
+    );
+    invite.assert_hits(1);
+}
+
+#[test]
+fn invite_fails_when_someone_could_not_be_added() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.invite");
+        then.status(200).json_body(json!({
+            "ok": false,
+            "error": "user_not_found"
+        }));
+    });
+
+    let out = run(&server, &["invite", "--channel", "C1", "--users", "U404"]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stdout).contains("U404  error: user_not_found"));
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("1 of 1 users could not be invited"), "{err}");
+}
+
+#[test]
+fn invite_channel_errors_are_not_per_user() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.invite");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "not_in_channel" }));
+    });
+
+    let out = run(&server, &["invite", "--channel", "C1", "--users", "U1,U2"]);
+    assert!(!out.status.success());
+    assert!(out.stdout.is_empty());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("join it first"), "{err}");
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::slack;
+use httpmock::prelude::*;
+use predicates::prelude::*;
+use serde_json::json;
+
+fn mock_list(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.list")
+            .x_www_form_urlencoded_tuple("exclude_archived", "true")
+            .matches(|req| {
+                !String::from_utf8_lossy(req.body.as_deref().unwrap_or_default())
+                    .contains("cursor=")
+            });
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channels": [
+                { "id": "C1", "name": "platform-infra", "is_member": true, "num_members": 40 },
+                { "id": "C2", "name": "platform-db", "is_member": false, "num_members": 7 },
+                { "id": "C9", "name": "random", "is_member": false, "num_members": 90 }
+            ],
+            "response_metadata": { "next_cursor": "next" }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.list")
+            .x_www_form_urlencoded_tuple("cursor", "next");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channels": [
+                { "id": "C3", "name": "platform-ops", "is_member": false, "num_members": 12 }
+            ]
+        }));
+    });
+}
+
+#[test]
+fn dry_run_lists_matches_without_joining() {
+    let server = MockServer::start();
+    mock_list(&server);
+    let join = server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.join");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+    slack(&server)
+        .args(["join", "--pattern", "platform-*", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "#platform-db\t7 members\n#platform-ops\t12 members\n",
+        ));
+    join.assert_hits(0);
+}
+
+#[test]
+fn yes_joins_each_match_and_reports_per_channel() {
+    let server = MockServer::start();
+    mock_list(&server);
+    let ok = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.join")
+            .x_www_form_urlencoded_tuple("channel", "C2");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+    let failing = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.join")
+            .x_www_form_urlencoded_tuple("channel", "C3");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "is_archived" }));
+    });
+    slack(&server)
+        .args(["join", "--pattern", "platform-*", "--yes"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("joined #platform-db"))
+        .stdout(predicate::str::contains(
+            "failed #platform-ops: Slack error: is_archived",
+        ))
+        .stderr(predicate::str::contains("1 of 2 joins failed"));
+    ok.assert();
+    failing.assert();
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn kick_reports_each_user() {
+    let server = MockServer::start();
+    let removed = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.kick")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("user", "U1");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+    let absent = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.kick")
+            .x_www_form_urlencoded_tuple("user", "U2");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "not_in_channel" }));
+    });
+
+    let out = run(
+        &server,
+        &[
+            "kick",
+            "--channel",
+            "C1",
+            "--user",
+            "U1",
+            "--user",
+            "U2",
+            "--yes",
+        ],
+    );
+    assert!(!out.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "USER  RESULT\n\
+         U1    removed\n\
+         U2    error: isn't in the channel\n"
+    );
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("1 of 2 users could not be removed"), "{err}");
+    removed.assert_hits(1);
+    absent.assert_hits(1);
+}
+
+#[test]
+fn kick_without_a_terminal_removes_nobody() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.info");
+        then.status(200)
+            .json_body(json!({ "ok": true, "user": { "id": "U1", "name": "jane" } }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.info");
+        then.status(200)
+            .json_body(json!({ "ok": true, "channel": { "id": "C1", "name": "ops" } }));
+    });
+    let kick = server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.kick");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = run(&server, &["kick", "--channel", "C1", "--user", "U1"]);
+    assert!(!out.status.success());
+    kick.assert_hits(0);
+}
+
+#[test]
+fn kick_explains_general() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.kick");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "cant_kick_from_general" }));
+    });
+
+    let out = run(
+        &server,
+        &["kick", "--channel", "C1", "--user", "U1", "--yes"],
+    );
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("#general"), "{err}");
+}
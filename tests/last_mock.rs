@@ -0,0 +1,100 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn stub_history(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST).path("/api/auth.test");
+        then.status(200)
+            .json_body(json!({ "ok": true, "user_id": "UME" }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.history")
+            .x_www_form_urlencoded_tuple("channel", "C1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "messages": [
+                { "ts": "5.0", "user": "U2", "text": "someone else" },
+                { "ts": "4.0", "user": "UME", "text": "latest of mine" },
+                { "ts": "3.0", "user": "UME", "subtype": "channel_join", "text": "joined" },
+                { "ts": "2.0", "user": "UME", "text": "older of mine" }
+            ]
+        }));
+    });
+}
+
+#[test]
+fn last_picks_your_nth_most_recent_message() {
+    let server = MockServer::start();
+    stub_history(&server);
+    let react = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/reactions.add")
+            .x_www_form_urlencoded_tuple("timestamp", "4.0");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+    let update = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.update")
+            .x_www_form_urlencoded_tuple("ts", "2.0");
+        then.status(200)
+            .json_body(json!({ "ok": true, "ts": "2.0" }));
+    });
+
+    let out = run(
+        &server,
+        &["react", "--channel", "C1", "--last", "--emoji", "eyes"],
+    );
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "reacted :eyes: to 4.0\n"
+    );
+
+    let out = run(
+        &server,
+        &["edit", "--channel", "C1", "--last", "2", "--text", "fixed"],
+    );
+    assert!(out.status.success(), "{out:?}");
+    react.assert_hits(1);
+    update.assert_hits(1);
+}
+
+#[test]
+fn last_past_your_messages_refuses_to_act() {
+    let server = MockServer::start();
+    stub_history(&server);
+    let pin = server.mock(|when, then| {
+        when.method(POST).path("/api/pins.add");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = run(&server, &["pin", "--channel", "C1", "--last", "3"]);
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("only 2 of the last 200 messages"), "{err}");
+    pin.assert_hits(0);
+}
+
+#[test]
+fn ts_and_last_are_exclusive_and_one_is_needed() {
+    let server = MockServer::start();
+    let out = run(
+        &server,
+        &[
+            "delete",
+            "--channel",
+            "C1",
+            "--ts",
+            "1.0",
+            "--last",
+            "--yes",
+        ],
+    );
+    assert_eq!(out.status.code(), Some(2));
+    let out = run(&server, &["delete", "--channel", "C1", "--yes"]);
+    assert_eq!(out.status.code(), Some(2));
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn stub_info(server: &MockServer, id: &str, name: &str) {
+    let body = json!({ "ok": true, "channel": { "id": id, "name": name } });
+    server.mock(move |when, then| {
+        when.method(POST)
+            .path("/api/conversations.info")
+            .x_www_form_urlencoded_tuple("channel", id);
+        then.status(200).json_body(body);
+    });
+}
+
+#[test]
+fn leave_reports_one_line_per_channel() {
+    let server = MockServer::start();
+    stub_info(&server, "C1", "old-project");
+    stub_info(&server, "C2", "launch-2023");
+    stub_info(&server, "C3", "general");
+    let left = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.leave")
+            .x_www_form_urlencoded_tuple("channel", "C1");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.leave")
+            .x_www_form_urlencoded_tuple("channel", "C2");
+        then.status(200)
+            .json_body(json!({ "ok": true, "not_in_channel": true }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.leave")
+            .x_www_form_urlencoded_tuple("channel", "C3");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "cant_leave_general" }));
+    });
+
+    let out = run(
+        &server,
+        &[
+            "leave",
+            "--channel",
+            "C1",
+            "--channel",
+            "C2",
+            "--channel",
+            "C3",
+        ],
+    );
+    assert!(!out.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "left #old-project (C1)\n\
+         not in #launch-2023 (C2)\n"
+    );
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        err.contains("C3: nobody can leave the workspace's #general"),
+        "{err}"
+    );
+    assert!(err.contains("1 of 3 channels could not be left"), "{err}");
+    left.assert_hits(1);
+}
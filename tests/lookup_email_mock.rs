@@ -0,0 +1,103 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn stub_lookup(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.lookupByEmail")
+            .x_www_form_urlencoded_tuple("email", "jane@example.com");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "user": { "id": "U1", "name": "jane", "is_admin": true, "profile": {
+                "display_name": "jane", "real_name": "Jane Doe", "email": "jane@example.com"
+            } }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.lookupByEmail");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "users_not_found" }));
+    });
+}
+
+#[test]
+fn lookup_email_prints_a_find_person_row() {
+    let server = MockServer::start();
+    stub_lookup(&server);
+
+    let out = run(&server, &["lookup-email", "--email", "jane@example.com"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "U1\t-\t@jane\tJane Doe\tjane@example.com\t[admin]\t\t\n"
+    );
+
+    let out = run(&server, &["lookup-email", "--email", "nobody@example.com"]);
+    assert!(!out.status.success());
+    assert!(
+        String::from_utf8_lossy(&out.stderr).contains("no user with email nobody@example.com"),
+        "{out:?}"
+    );
+}
+
+#[test]
+fn find_person_uses_lookup_for_an_email_query() {
+    let server = MockServer::start();
+    stub_lookup(&server);
+    let list = server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200)
+            .json_body(json!({ "ok": true, "members": [] }));
+    });
+
+    let out = run(&server, &["find-person", "--query", "jane@example.com"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "U1\t-\t@jane\tJane Doe\tjane@example.com\t[admin]\t\t\n"
+    );
+    let out = run(&server, &["find-person", "--query", "x@example.com"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "");
+    list.assert_hits(0);
+}
+
+#[test]
+fn exact_email_falls_back_to_the_user_list() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.lookupByEmail");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "missing_scope" }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "members": [
+                { "id": "U1", "name": "dan", "profile": { "display_name": "dan", "email": "dan@example.com" } },
+                { "id": "U2", "name": "an", "profile": { "display_name": "an", "email": "An@Example.com" } }
+            ]
+        }));
+    });
+
+    let out = run(
+        &server,
+        &["find-person", "--query", "an@example.com", "--exact-email"],
+    );
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "U2\t-\t@an\t\tAn@Example.com\t\t\t\n"
+    );
+
+    let out = run(
+        &server,
+        &["find-person", "--query", "x@example.com", "--exact-email"],
+    );
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("no user with email x@example.com"));
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn mark_takes_ts_or_finds_the_latest() {
+    let server = MockServer::start();
+    let history = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.history")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("limit", "1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "messages": [{ "ts": "1712345679.000200", "text": "newest" }]
+        }));
+    });
+    let given = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.mark")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("ts", "1712345678.000100");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+    let latest = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.mark")
+            .x_www_form_urlencoded_tuple("ts", "1712345679.000200");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = run(
+        &server,
+        &["mark", "--channel", "C1", "--ts", "1712345678.000100"],
+    );
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "marked C1 read up to 1712345678.000100\n"
+    );
+
+    let out = run(&server, &["mark", "--channel", "C1", "--latest"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "marked C1 read up to 1712345679.000200\n"
+    );
+    given.assert_hits(1);
+    latest.assert_hits(1);
+    history.assert_hits(1);
+}
+
+#[test]
+fn mark_needs_ts_or_latest() {
+    let server = MockServer::start();
+    let out = run(&server, &["mark", "--channel", "C1"]);
+    assert!(!out.status.success());
+}
@@ -0,0 +1,192 @@
+use httpmock::prelude::*;
+use serde_json::{json, Value};
+
+/// Feed newline-delimited JSON-RPC messages to `slack mcp` and collect its replies.
+fn run_mcp(server: &MockServer, extra: &[&str], messages: &[Value]) -> Vec<Value> {
+    let state = tempfile::tempdir().unwrap();
+    let input: String = messages.iter().map(|m| format!("{m}\n")).collect();
+    let out = assert_cmd::Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .env("SLACK_STATE_DIR", state.path())
+        .arg("mcp")
+        .args(extra)
+        .write_stdin(input)
+        .output()
+        .unwrap();
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    String::from_utf8(out.stdout)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect()
+}
+
+fn initialize() -> Vec<Value> {
+    vec![
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize",
+                "params": { "protocolVersion": "2024-11-05", "capabilities": {},
+                            "clientInfo": { "name": "test", "version": "0" } } }),
+        json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+    ]
+}
+
+fn call(id: u64, name: &str, arguments: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": "tools/call",
+            "params": { "name": name, "arguments": arguments } })
+}
+
+#[test]
+fn initialize_and_list_tools_without_write_access() {
+    let server = MockServer::start();
+    let mut msgs = initialize();
+    msgs.push(json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }));
+
+    let replies = run_mcp(&server, &[], &msgs);
+    assert_eq!(replies.len(), 2, "the notification must not be answered");
+    assert_eq!(replies[0]["id"], 1);
+    assert_eq!(replies[0]["result"]["serverInfo"]["name"], "mdslackcli");
+    let names: Vec<&str> = replies[1]["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["list_channels", "read_messages", "find_person"]);
+}
+
+#[test]
+fn read_tools_return_structured_results() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channels": [
+                { "id": "C1", "name": "general", "is_private": false },
+                { "id": "D1", "is_im": true, "user": "U2" }
+            ]
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.history")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("limit", "2");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "messages": [
+                { "ts": "2.0", "user": "U2", "text": "second" },
+                { "ts": "1.0", "user": "U1", "text": "first" }
+            ]
+        }));
+    });
+    let mut msgs = initialize();
+    msgs.push(call(
+        2,
+        "list_channels",
+        json!({ "types": "public_channel,im" }),
+    ));
+    msgs.push(call(
+        3,
+        "read_messages",
+        json!({ "channel": "C1", "limit": 2 }),
+    ));
+
+    let replies = run_mcp(&server, &[], &msgs);
+    let channels = &replies[1]["result"];
+    assert_eq!(channels["isError"], false);
+    assert_eq!(
+        channels["structuredContent"]["channels"],
+        json!([
+            { "id": "C1", "name": "general", "type": "public_channel", "user": null },
+            { "id": "D1", "name": null, "type": "im", "user": "U2" }
+        ])
+    );
+    let history = &replies[2]["result"]["structuredContent"]["messages"];
+    assert_eq!(history[0]["text"], "first");
+    assert_eq!(history[1]["text"], "second");
+}
+
+#[test]
+fn send_message_is_refused_without_allow_write() {
+    let server = MockServer::start();
+    let post = server.mock(|when, then| {
+        when.method(POST).path("/api/chat.postMessage");
+        then.status(200)
+            .json_body(json!({ "ok": true, "ts": "9.0" }));
+    });
+    let mut msgs = initialize();
+    msgs.push(call(
+        2,
+        "send_message",
+        json!({ "channel": "C1", "text": "hi" }),
+    ));
+
+    let replies = run_mcp(&server, &[], &msgs);
+    let result = &replies[1]["result"];
+    assert_eq!(result["isError"], true);
+    assert_eq!(
+        result["structuredContent"]["error"]["code"],
+        "write_disabled"
+    );
+    post.assert_hits(0);
+}
+
+#[test]
+fn send_message_posts_with_allow_write() {
+    let server = MockServer::start();
+    let post = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.postMessage")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("text", "hi")
+            .x_www_form_urlencoded_tuple("thread_ts", "1.0");
+        then.status(200)
+            .json_body(json!({ "ok": true, "ts": "9.0" }));
+    });
+    let mut msgs = initialize();
+    msgs.push(call(
+        2,
+        "send_message",
+        json!({ "channel": "C1", "text": "hi", "thread_ts": "1.0" }),
+    ));
+
+    let replies = run_mcp(&server, &["--allow-write"], &msgs);
+    assert_eq!(
+        replies[1]["result"]["structuredContent"],
+        json!({ "channel": "C1", "ts": "9.0" })
+    );
+    post.assert();
+}
+
+#[test]
+fn slack_errors_come_back_as_tool_errors() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.history");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "channel_not_found" }));
+    });
+    let mut msgs = initialize();
+    msgs.push(call(2, "read_messages", json!({ "channel": "C404" })));
+    msgs.push(call(3, "read_messages", json!({})));
+
+    let replies = run_mcp(&server, &[], &msgs);
+    let slack = &replies[1]["result"];
+    assert_eq!(slack["isError"], true);
+    assert_eq!(
+        slack["structuredContent"]["error"]["code"],
+        "channel_not_found"
+    );
+    let bad = &replies[2]["result"];
+    assert_eq!(
+        bad["structuredContent"]["error"]["code"],
+        "invalid_arguments"
+    );
+}
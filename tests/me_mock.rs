@@ -0,0 +1,46 @@
+use assert_cmd::prelude::*;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+fn me(server: &MockServer) -> std::process::Output {
+    Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["me", "--channel", "C1", "--text", "is deploying"])
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn me_posts_a_me_message() {
+    let server = MockServer::start();
+    let post = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.meMessage")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("text", "is deploying");
+        then.status(200)
+            .json_body(json!({ "ok": true, "channel": "C1", "ts": "5.0" }));
+    });
+
+    let out = me(&server);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "sent ok, ts=5.0\n");
+    post.assert();
+}
+
+#[test]
+fn me_fails_outside_the_channel() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/chat.meMessage");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "not_in_channel" }));
+    });
+
+    let out = me(&server);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("not_in_channel"));
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+fn stub_members(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.members")
+            .x_www_form_urlencoded_tuple("cursor", "next");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "members": ["U3"],
+            "response_metadata": { "next_cursor": "" }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.members")
+            .x_www_form_urlencoded_tuple("channel", "C1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "members": ["U1", "U2"],
+            "response_metadata": { "next_cursor": "next" }
+        }));
+    });
+}
+
+#[test]
+fn members_pages_through_the_channel() {
+    let server = MockServer::start();
+    stub_members(&server);
+
+    let out = run(&server, &["members", "--channel", "C1"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "U1\nU2\nU3\n");
+
+    let out = run(&server, &["members", "--channel", "C1", "--count-only"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "3\n");
+}
+
+#[test]
+fn members_resolve_adds_names() {
+    let server = MockServer::start();
+    stub_members(&server);
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "members": [
+                { "id": "U1", "name": "jane", "profile": { "real_name": "Jane Doe", "email": "jane@example.com" } },
+                { "id": "U2", "name": "kim", "profile": { "real_name": "Kim Ito" } }
+            ]
+        }));
+    });
+
+    let out = run(&server, &["members", "--channel", "C1", "--resolve"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "ID  NAME   REAL NAME  EMAIL\n\
+         U1  @jane  Jane Doe   jane@example.com\n\
+         U2  @kim   Kim Ito    -\n\
+         U3  -      -          -\n"
+    );
+}
+
+#[test]
+fn members_prints_the_first_page_before_the_second_arrives() {
+    use std::io::{BufRead, BufReader};
+    use std::time::{Duration, Instant};
+
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.members")
+            .x_www_form_urlencoded_tuple("cursor", "next");
+        then.status(200)
+            .delay(Duration::from_secs(2))
+            .json_body(json!({ "ok": true, "members": ["U3"] }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.members");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "members": ["U1", "U2"],
+            "response_metadata": { "next_cursor": "next" }
+        }));
+    });
+
+    let start = Instant::now();
+    let mut child = Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["members", "--channel", "C1"])
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut lines = BufReader::new(child.stdout.take().unwrap()).lines();
+    assert_eq!(lines.next().unwrap().unwrap(), "U1");
+    let first_at = start.elapsed();
+    let rest: Vec<String> = lines.map(Result::unwrap).collect();
+    assert!(child.wait().unwrap().success());
+
+    assert_eq!(rest, ["U2", "U3"]);
+    assert!(first_at + Duration::from_millis(1500) < start.elapsed());
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn my_channels_follows_every_cursor() {
+    let server = MockServer::start();
+    // Cursor pages first: the first registered mock that matches wins.
+    let third = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.conversations")
+            .x_www_form_urlencoded_tuple("cursor", "page3");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channels": [{ "id": "C3", "name": "ops", "is_private": true }],
+            "response_metadata": { "next_cursor": "" }
+        }));
+    });
+    let second = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.conversations")
+            .x_www_form_urlencoded_tuple("cursor", "page2");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channels": [{ "id": "C2", "name": "random" }],
+            "response_metadata": { "next_cursor": "page3" }
+        }));
+    });
+    let first = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.conversations")
+            .x_www_form_urlencoded_tuple("types", "public_channel,private_channel")
+            .x_www_form_urlencoded_tuple("exclude_archived", "true");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channels": [{ "id": "C1", "name": "general" }],
+            "response_metadata": { "next_cursor": "page2" }
+        }));
+    });
+
+    let out = run(
+        &server,
+        &[
+            "my-channels",
+            "--types",
+            "public_channel,private_channel",
+            "--exclude-archived",
+        ],
+    );
+    assert!(out.status.success(), "{out:?}");
+    first.assert();
+    second.assert();
+    third.assert();
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "C1\t#general\t(public_channel)\nC2\t#random\t(public_channel)\nC3\t#ops\t(private_channel)\n"
+    );
+}
+
+#[test]
+fn my_channels_keeps_archived_unless_asked() {
+    let server = MockServer::start();
+    let list = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.conversations")
+            .x_www_form_urlencoded_tuple("types", "im")
+            .x_www_form_urlencoded_tuple("exclude_archived", "false");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channels": [{ "id": "D1", "is_im": true, "user": "U1" }]
+        }));
+    });
+
+    let out = run(&server, &["my-channels", "--types", "im"]);
+    assert!(out.status.success(), "{out:?}");
+    list.assert();
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "D1\t#(dm or unnamed)\t(im)\n"
+    );
+}
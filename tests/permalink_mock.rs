@@ -0,0 +1,57 @@
+use assert_cmd::prelude::*;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+fn permalink(server: &MockServer) -> std::process::Output {
+    Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["permalink", "--channel", "C1", "--ts", "1712345678.000100"])
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn permalink_prints_only_the_url() {
+    let server = MockServer::start();
+    let link = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/chat.getPermalink")
+            .query_param("channel", "C1")
+            .query_param("message_ts", "1712345678.000100");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channel": "C1",
+            "permalink": "https://acme.slack.com/archives/C1/p1712345678000100"
+        }));
+    });
+
+    let out = permalink(&server);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "https://acme.slack.com/archives/C1/p1712345678000100\n"
+    );
+    link.assert();
+}
+
+#[test]
+fn unknown_message_fails_with_a_clear_error() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(GET).path("/api/chat.getPermalink");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "message_not_found" }));
+    });
+
+    let out = permalink(&server);
+    assert!(!out.status.success());
+    assert!(out.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("no message with ts 1712345678.000100 in C1"),
+        "{stderr}"
+    );
+}
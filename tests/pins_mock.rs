@@ -0,0 +1,116 @@
+mod common;
+
+use common::slack;
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn run(server: &MockServer, args: &[&str]) -> std::process::Output {
+    slack(server).args(args).output().unwrap()
+}
+
+#[test]
+fn pin_and_unpin_tolerate_repeats_unless_strict() {
+    let server = MockServer::start();
+    let add = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/pins.add")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("timestamp", "1.0");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+    let add_again = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/pins.add")
+            .x_www_form_urlencoded_tuple("timestamp", "2.0");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "already_pinned" }));
+    });
+    let remove = server.mock(|when, then| {
+        when.method(POST).path("/api/pins.remove");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "no_pin" }));
+    });
+
+    let out = run(&server, &["pin", "--channel", "C1", "--ts", "1.0"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "pinned 1.0\n");
+
+    let out = run(&server, &["pin", "--channel", "C1", "--ts", "2.0"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "2.0 was already pinned\n"
+    );
+    let out = run(
+        &server,
+        &["pin", "--channel", "C1", "--ts", "2.0", "--strict"],
+    );
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("already_pinned"));
+
+    let out = run(&server, &["unpin", "--channel", "C1", "--ts", "3.0"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "3.0 was not pinned\n");
+    let out = run(
+        &server,
+        &["unpin", "--channel", "C1", "--ts", "3.0", "--strict"],
+    );
+    assert!(!out.status.success());
+
+    add.assert();
+    add_again.assert_hits(2);
+    remove.assert_hits(2);
+}
+
+#[test]
+fn pins_lists_messages_with_author_names() {
+    let server = MockServer::start();
+    let list = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/pins.list")
+            .x_www_form_urlencoded_tuple("channel", "C1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "items": [
+                { "type": "message", "message": { "ts": "1.0", "user": "U1", "text": "Runbook: https://wiki" } },
+                { "type": "message", "message": { "ts": "2.0", "user": "U9", "text": "On call rota" } }
+            ]
+        }));
+    });
+    let users = server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "members": [{ "id": "U1", "name": "jane", "profile": { "display_name": "jane" } }]
+        }));
+    });
+
+    let out = run(&server, &["pins", "--channel", "C1"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "1.0\t@jane\tRunbook: https://wiki\n2.0\tU9\tOn call rota\n"
+    );
+    list.assert();
+    users.assert();
+}
+
+#[test]
+fn pins_says_when_there_are_none() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/pins.list");
+        then.status(200)
+            .json_body(json!({ "ok": true, "items": [] }));
+    });
+    let users = server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200)
+            .json_body(json!({ "ok": true, "members": [] }));
+    });
+
+    let out = run(&server, &["pins", "--channel", "C1"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "no pins\n");
+    users.assert_hits(0);
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn get_defaults_to_you_and_explains_away() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/auth.test");
+        then.status(200)
+            .json_body(json!({ "ok": true, "user_id": "UME" }));
+    });
+    let mine = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/users.getPresence")
+            .query_param("user", "UME");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "presence": "away",
+            "online": true,
+            "auto_away": false,
+            "manual_away": true
+        }));
+    });
+    let theirs = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/users.getPresence")
+            .query_param("user", "U2");
+        then.status(200)
+            .json_body(json!({ "ok": true, "presence": "active" }));
+    });
+
+    let out = run(&server, &["presence", "get"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "UME\taway (set manually)\n"
+    );
+    let out = run(&server, &["presence", "get", "--user", "U2"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "U2\tactive\n");
+    mine.assert_hits(1);
+    theirs.assert_hits(1);
+}
+
+#[test]
+fn set_takes_away_or_auto() {
+    let server = MockServer::start();
+    let set = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.setPresence")
+            .x_www_form_urlencoded_tuple("presence", "away");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = run(&server, &["presence", "set", "away"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "presence set to away\n"
+    );
+    set.assert_hits(1);
+    let out = run(&server, &["presence", "set", "busy"]);
+    assert_eq!(out.status.code(), Some(2));
+}
+
+fn stub_users(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "members": [
+                { "id": "U1", "name": "jane", "profile": { "display_name": "jane" } },
+                { "id": "U2", "name": "raj", "profile": { "display_name": "raj" } }
+            ]
+        }));
+    });
+    for (user, presence) in [("U1", "active"), ("U2", "away")] {
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/api/users.getPresence")
+                .x_www_form_urlencoded_tuple("user", user);
+            then.status(200)
+                .json_body(json!({ "ok": true, "presence": presence }));
+        });
+    }
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.getPresence");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "user_not_found" }));
+    });
+}
+
+#[test]
+fn batch_reports_each_user_and_failures_per_row() {
+    let server = MockServer::start();
+    stub_users(&server);
+
+    let out = run(&server, &["presence-batch", "--users", "U1,U9,U2"]);
+    assert_eq!(out.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let rows: Vec<&str> = stdout.lines().collect();
+    assert_eq!(rows.len(), 4, "{stdout}");
+    assert!(rows[0].starts_with("NAME"));
+    assert!(rows[1].starts_with("jane") && rows[1].contains("active"));
+    assert!(rows[2].starts_with("U9") && rows[2].contains("user_not_found"));
+    assert!(rows[3].starts_with("raj") && rows[3].contains("away"));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("1 of 3 users could not be checked"));
+}
+
+#[test]
+fn batch_takes_a_usergroup() {
+    let server = MockServer::start();
+    stub_users(&server);
+    let group = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/usergroups.users.list")
+            .x_www_form_urlencoded_tuple("usergroup", "S1");
+        then.status(200)
+            .json_body(json!({ "ok": true, "users": ["U2", "U1"] }));
+    });
+
+    let out = run(&server, &["presence-batch", "--usergroup", "S1"]);
+    assert!(out.status.success(), "{out:?}");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let names: Vec<&str> = stdout
+        .lines()
+        .skip(1)
+        .filter_map(|l| l.split_whitespace().next())
+        .collect();
+    assert_eq!(names, ["raj", "jane"]);
+    group.assert_hits(1);
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use assert_cmd::Command;
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn slack(server: &MockServer, config: &tempfile::TempDir) -> Command {
+    let key = config.path().join("key");
+    if !key.exists() {
+        std::fs::write(&key, "test-key").unwrap();
+        std::fs::write(config.path().join("config.toml"), "key_file = \"key\"\n").unwrap();
+    }
+    let mut cmd = common::slack(server);
+    cmd.env_remove("SLACK_TOKEN")
+        .env_remove("SLACK_PROFILE")
+        .env("SLACK_CONFIG_DIR", config.path())
+        .env("SLACK_STATE_DIR", config.path())
+        .args(["--store", "file"]);
+    cmd
+}
+
+#[test]
+fn profile_check_reports_each_profile_and_fails_on_bad_ones() {
+    let server = MockServer::start();
+    let config = tempfile::tempdir().unwrap();
+    let mut ok = server.mock(|when, then| {
+        when.method(POST).path("/api/auth.test");
+        then.status(200)
+            .json_body(json!({ "ok": true, "team": "Acme", "user_id": "U1" }));
+    });
+    for (name, token) in [("work", "xoxp-good"), ("old", "xoxp-bad")] {
+        let out = slack(&server, &config)
+            .args(["init", "--profile", name, "--token", token])
+            .output()
+            .unwrap();
+        assert!(out.status.success(), "{out:?}");
+    }
+    ok.delete();
+
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/auth.test")
+            .header("authorization", "Bearer xoxp-good");
+        then.status(200)
+            .json_body(json!({ "ok": true, "team": "Acme", "user_id": "U1" }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/auth.test")
+            .header("authorization", "Bearer xoxp-bad");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "token_revoked" }));
+    });
+
+    let out = slack(&server, &config)
+        .args(["profile", "list"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "  old\n  work\n");
+
+    let out = slack(&server, &config)
+        .args(["profile", "check"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(!out.status.success(), "{stdout}");
+    assert!(
+        stdout.contains("old\t-\t-\tuser (xoxp)\trevoked"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("work\tAcme\tU1\tuser (xoxp)\tvalid"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("slack init --profile old --force"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("--profile work"), "{stdout}");
+}
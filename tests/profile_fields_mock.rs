@@ -0,0 +1,32 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn profile_fields_lists_ids_types_and_options() {
+    let server = MockServer::start();
+    let schema = server.mock(|when, then| {
+        when.method(POST).path("/api/team.profile.get");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "profile": { "fields": [
+                {
+                    "id": "Xf02", "ordering": 1, "label": "Office", "type": "options_list",
+                    "possible_values": ["Berlin", "Lisbon", "Remote"]
+                },
+                { "id": "Xf01", "ordering": 0, "label": "Pronouns", "type": "text" }
+            ] }
+        }));
+    });
+
+    let out = run(&server, &["profile-fields"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "Xf01\tPronouns\ttext\t-\n\
+         Xf02\tOffice\toptions_list\tBerlin, Lisbon, Remote\n"
+    );
+    schema.assert_hits(1);
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn profile_set_maps_custom_fields_by_label() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/team.profile.get");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "profile": { "fields": [
+                { "id": "Xf01", "label": "Pronouns" },
+                { "id": "Xf02", "label": "Cost center" }
+            ]}
+        }));
+    });
+    let set = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.profile.set")
+            .x_www_form_urlencoded_tuple(
+                "profile",
+                r#"{"fields":{"Xf01":{"alt":"","value":"they/them"}},"title":"Staff Engineer"}"#,
+            );
+        then.status(200).json_body(json!({
+            "ok": true,
+            "profile": {
+                "title": "Staff Engineer",
+                "fields": { "Xf01": { "value": "they/them", "alt": "" } }
+            }
+        }));
+    });
+
+    let out = run(
+        &server,
+        &[
+            "profile",
+            "set",
+            "--field",
+            "title=Staff Engineer",
+            "--field",
+            "pronouns=they/them",
+        ],
+    );
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "title: Staff Engineer\nPronouns: they/them\n"
+    );
+    set.assert_hits(1);
+}
+
+#[test]
+fn setting_someone_else_without_admin_rights_is_explained() {
+    let server = MockServer::start();
+    let set = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.profile.set")
+            .x_www_form_urlencoded_tuple("user", "U2");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "not_admin" }));
+    });
+
+    let out = run(
+        &server,
+        &["profile", "set", "--user", "U2", "--field", "title=Intern"],
+    );
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        err.contains("needs an admin's user token with users.profile:write"),
+        "{err}"
+    );
+    set.assert_hits(1);
+}
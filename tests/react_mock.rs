@@ -0,0 +1,188 @@
+use assert_cmd::prelude::*;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+fn react(server: &MockServer, emoji: &str) -> std::process::Output {
+    Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["react", "--channel", "C1", "--ts", "1.0", "--emoji", emoji])
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn react_strips_colons_and_posts_reaction() {
+    let server = MockServer::start();
+    let add = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/reactions.add")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("timestamp", "1.0")
+            .x_www_form_urlencoded_tuple("name", "thumbsup");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = react(&server, ":thumbsup:");
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "reacted :thumbsup: to 1.0\n"
+    );
+    add.assert();
+}
+
+#[test]
+fn already_reacted_is_not_an_error() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/reactions.add");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "already_reacted" }));
+    });
+
+    let out = react(&server, "eyes");
+    assert!(out.status.success(), "{out:?}");
+    assert!(String::from_utf8_lossy(&out.stdout).contains("already reacted :eyes:"));
+}
+
+#[test]
+fn invalid_name_explains_itself() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/reactions.add");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "invalid_name" }));
+    });
+
+    let out = react(&server, "nope");
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("`nope` is not an emoji"), "{stderr}");
+    assert!(stderr.contains("invalid_name"), "{stderr}");
+}
+
+fn unreact(server: &MockServer, extra: &[&str]) -> std::process::Output {
+    Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["unreact", "--channel", "C1", "--ts", "1.0"])
+        .args(extra)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn unreact_sends_the_normalized_name() {
+    let server = MockServer::start();
+    let remove = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/reactions.remove")
+            .x_www_form_urlencoded_tuple("timestamp", "1.0")
+            .x_www_form_urlencoded_tuple("name", "eyes");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    for emoji in [":eyes:", "eyes"] {
+        let out = unreact(&server, &["--emoji", emoji]);
+        assert!(out.status.success(), "{out:?}");
+        assert_eq!(
+            String::from_utf8_lossy(&out.stdout),
+            "removed :eyes: from 1.0\n"
+        );
+    }
+    remove.assert_hits(2);
+}
+
+#[test]
+fn missing_reaction_is_fine_unless_strict() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/reactions.remove");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "no_reaction" }));
+    });
+
+    let out = unreact(&server, &["--emoji", "eyes"]);
+    assert!(out.status.success(), "{out:?}");
+    assert!(String::from_utf8_lossy(&out.stdout).contains("you had no such reaction"));
+
+    let out = unreact(&server, &["--emoji", "eyes", "--strict"]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("no_reaction"));
+}
+
+fn reactions_get(server: &MockServer, extra: &[&str]) -> std::process::Output {
+    Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["reactions-get", "--channel", "C1", "--ts", "1.0"])
+        .args(extra)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn reactions_get_names_reactors_per_emoji() {
+    let server = MockServer::start();
+    let get = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/reactions.get")
+            .x_www_form_urlencoded_tuple("timestamp", "1.0")
+            .x_www_form_urlencoded_tuple("full", "true");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "type": "message",
+            "message": { "ts": "1.0", "reactions": [
+                { "name": "tada", "count": 2, "users": ["U1", "U2"] },
+                { "name": "eyes", "count": 1, "users": ["U2"] }
+            ] }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "members": [
+                { "id": "U1", "name": "jane", "profile": { "display_name": "jane" } },
+                { "id": "U2", "name": "bob", "profile": { "display_name": "bob" } }
+            ]
+        }));
+    });
+
+    let out = reactions_get(&server, &[]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        ":tada:\t2\t@jane @bob\n:eyes:\t1\t@bob\n"
+    );
+
+    let out = reactions_get(&server, &["--format", "json"]);
+    let json: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(json[0]["users"][0], json!({ "id": "U1", "name": "@jane" }));
+    get.assert_hits(2);
+}
+
+#[test]
+fn reactions_get_says_when_there_are_none() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/reactions.get");
+        then.status(200)
+            .json_body(json!({ "ok": true, "message": { "ts": "1.0" } }));
+    });
+    let users = server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200)
+            .json_body(json!({ "ok": true, "members": [] }));
+    });
+
+    let out = reactions_get(&server, &[]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "no reactions\n");
+    users.assert_hits(0);
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn stub_timezone(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST).path("/api/auth.test");
+        then.status(200)
+            .json_body(json!({ "ok": true, "user_id": "U1" }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.info")
+            .x_www_form_urlencoded_tuple("user", "U1");
+        then.status(200)
+            .json_body(json!({ "ok": true, "user": { "id": "U1", "tz_offset": -25200 } }));
+    });
+}
+
+#[test]
+fn remind_passes_time_through_and_explains_cannot_parse() {
+    let server = MockServer::start();
+    stub_timezone(&server);
+    let add = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/reminders.add")
+            .x_www_form_urlencoded_tuple("text", "rotate certs")
+            .x_www_form_urlencoded_tuple("time", "in 2 hours");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "reminder": { "id": "Rm1", "text": "rotate certs", "time": 1712345678 }
+        }));
+    });
+    let bad = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/reminders.add")
+            .x_www_form_urlencoded_tuple("time", "whenever");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "cannot_parse" }));
+    });
+
+    let out = run(
+        &server,
+        &["remind", "--text", "rotate certs", "--time", "in 2 hours"],
+    );
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "reminder Rm1 due 2024-04-05 12:34 -07:00\n"
+    );
+
+    let out = run(&server, &["remind", "--text", "x", "--time", "whenever"]);
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("--time \"whenever\""), "{err}");
+    add.assert_hits(1);
+    bad.assert_hits(1);
+}
+
+#[test]
+fn reminders_lists_in_profile_time_zone() {
+    let server = MockServer::start();
+    stub_timezone(&server);
+    server.mock(|when, then| {
+        when.method(POST).path("/api/reminders.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "reminders": [
+                { "id": "Rm1", "text": "rotate certs", "time": 1712345678, "recurring": false, "complete_ts": 0 },
+                { "id": "Rm2", "text": "standup every weekday", "recurring": true }
+            ]
+        }));
+    });
+
+    let out = run(&server, &["reminders"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "Rm1\t2024-04-05 12:34 -07:00\tonce\trotate certs\n\
+         Rm2\t-\trecurring\tstandup every weekday\n"
+    );
+}
+
+#[test]
+fn complete_and_delete_take_the_id() {
+    let server = MockServer::start();
+    let complete = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/reminders.complete")
+            .x_www_form_urlencoded_tuple("reminder", "Rm1");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+    let delete = server.mock(|when, then| {
+        when.method(POST).path("/api/reminders.delete");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "not_found" }));
+    });
+
+    let out = run(&server, &["remind-complete", "--id", "Rm1"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "completed reminder Rm1\n"
+    );
+
+    let out = run(&server, &["remind-delete", "--id", "Rm9"]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("no such reminder"));
+    complete.assert_hits(1);
+    delete.assert_hits(1);
+}
@@ -0,0 +1,128 @@
+use assert_cmd::prelude::*;
+use httpmock::prelude::*;
+use predicates::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+#[test]
+fn remindme_quotes_the_message_and_links_it() {
+    let server = MockServer::start();
+    let history = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.history")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("latest", "1712345678.000100")
+            .x_www_form_urlencoded_tuple("inclusive", "true");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "messages": [{ "ts": "1712345678.000100", "user": "U1", "text": "Please review the Q3 plan" }]
+        }));
+    });
+    let permalink = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/chat.getPermalink")
+            .query_param("message_ts", "1712345678.000100");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "permalink": "https://acme.slack.com/archives/C1/p1712345678000100"
+        }));
+    });
+    let add = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/reminders.add")
+            .x_www_form_urlencoded_tuple(
+                "text",
+                "\u{201c}Please review the Q3 plan\u{201d} https://acme.slack.com/archives/C1/p1712345678000100",
+            )
+            .x_www_form_urlencoded_tuple("time", "in 3 hours");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "reminder": { "id": "Rm123", "time": 1712356478 }
+        }));
+    });
+
+    Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args([
+            "remindme",
+            "--ts",
+            "https://acme.slack.com/archives/C1/p1712345678000100",
+            "--time",
+            "in 3 hours",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "reminder Rm123 due 2024-04-05 22:34 UTC",
+        ));
+    history.assert();
+    permalink.assert();
+    add.assert();
+}
+
+#[test]
+fn remind_msg_says_which_step_failed() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.history");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "messages": [
+                { "ts": "1.0", "user": "U1", "text": "ship it" },
+                { "ts": "3.0", "user": "U1", "text": "ship it again" }
+            ]
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/chat.getPermalink")
+            .query_param("message_ts", "1.0");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "message_not_found" }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/api/chat.getPermalink");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "permalink": "https://acme.slack.com/archives/C1/p2"
+        }));
+    });
+    let add = server.mock(|when, then| {
+        when.method(POST).path("/api/reminders.add");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "cannot_parse" }));
+    });
+    let remind = |ts: &str| {
+        Command::cargo_bin("mdslackcli")
+            .unwrap()
+            .env("SLACK_TOKEN", "xoxp-test")
+            .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+            .args([
+                "remind-msg",
+                "--channel",
+                "C1",
+                "--ts",
+                ts,
+                "--time",
+                "someday",
+            ])
+            .assert()
+            .failure()
+    };
+
+    remind("1.0").stderr(predicate::str::contains(
+        "could not get a permalink for 1.0 in C1",
+    ));
+    add.assert_hits(0);
+
+    remind("2.0").stderr(predicate::str::contains("could not read message 2.0 in C1"));
+    add.assert_hits(0);
+
+    remind("3.0").stderr(
+        predicate::str::contains("no reminder was set")
+            .and(predicate::str::contains("--time \"someday\"")),
+    );
+    add.assert_hits(1);
+}
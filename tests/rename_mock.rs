@@ -0,0 +1,72 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn stub_info(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.info")
+            .x_www_form_urlencoded_tuple("channel", "C1");
+        then.status(200)
+            .json_body(json!({ "ok": true, "channel": { "id": "C1", "name": "launch" } }));
+    });
+}
+
+#[test]
+fn rename_prints_old_and_new_names() {
+    let server = MockServer::start();
+    stub_info(&server);
+    let rename = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.rename")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("name", "launch-retro");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channel": { "id": "C1", "name": "launch-retro" }
+        }));
+    });
+
+    let out = run(
+        &server,
+        &["rename", "--channel", "C1", "--name", "Launch Retro"],
+    );
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "renamed #launch to #launch-retro (C1)\n"
+    );
+    rename.assert_hits(1);
+}
+
+#[test]
+fn rename_checks_the_name_before_calling_slack() {
+    let server = MockServer::start();
+    let rename = server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.rename");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = run(&server, &["rename", "--channel", "C1", "--name", "ops!"]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("`!` can't be in a channel name"));
+    rename.assert_hits(0);
+}
+
+#[test]
+fn rename_explains_not_authorized() {
+    let server = MockServer::start();
+    stub_info(&server);
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.rename");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "not_authorized" }));
+    });
+
+    let out = run(&server, &["rename", "--channel", "C1", "--name", "ops"]);
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("creator and workspace admins"), "{err}");
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::slack;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn replies_prints_thread_and_notes_more() {
+    let server = MockServer::start();
+    let replies = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.replies")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("ts", "100.0")
+            .x_www_form_urlencoded_tuple("limit", "2");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "has_more": true,
+            "messages": [
+                { "ts": "100.0", "thread_ts": "100.0", "user": "U1", "text": "parent" },
+                { "ts": "101.0", "thread_ts": "100.0", "user": "U2", "text": "first reply" }
+            ],
+            "response_metadata": { "next_cursor": "more" }
+        }));
+    });
+
+    let out = slack(&server)
+        .args([
+            "replies",
+            "--channel",
+            "C1",
+            "--ts",
+            "100.0",
+            "--limit",
+            "2",
+        ])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "100.0 U1: parent\n101.0 U2: first reply\n"
+    );
+    assert!(String::from_utf8_lossy(&out.stderr).contains("more than 2 messages"));
+    replies.assert_hits(1);
+}
+
+#[test]
+fn replies_follows_a_reply_ts_to_its_thread() {
+    let server = MockServer::start();
+    let lone = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.replies")
+            .x_www_form_urlencoded_tuple("ts", "101.0");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "messages": [
+                { "ts": "101.0", "thread_ts": "100.0", "user": "U2", "text": "first reply" }
+            ]
+        }));
+    });
+    let thread = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.replies")
+            .x_www_form_urlencoded_tuple("ts", "100.0");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "messages": [
+                { "ts": "100.0", "thread_ts": "100.0", "user": "U1", "text": "parent" },
+                { "ts": "101.0", "thread_ts": "100.0", "user": "U2", "text": "first reply" }
+            ]
+        }));
+    });
+
+    let out = slack(&server)
+        .args(["replies", "--channel", "C1", "--ts", "101.0"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "100.0 U1: parent\n101.0 U2: first reply\n"
+    );
+    assert!(out.stderr.is_empty());
+    lone.assert_hits(1);
+    thread.assert_hits(1);
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn saved_pages_through_stars_and_names_channels() {
+    let server = MockServer::start();
+    let page2 = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/stars.list")
+            .x_www_form_urlencoded_tuple("cursor", "next");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "items": [{ "type": "file", "file": { "id": "F1", "name": "plan.pdf" } }]
+        }));
+    });
+    let page1 = server.mock(|when, then| {
+        when.method(POST).path("/api/stars.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "items": [{
+                "type": "message",
+                "channel": "C1",
+                "message": { "ts": "1.0", "text": "follow up" }
+            }],
+            "response_metadata": { "next_cursor": "next" }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channels": [{ "id": "C1", "name": "ops" }]
+        }));
+    });
+
+    let out = run(&server, &["saved"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "message\t#ops\t1.0\tfollow up\nfile\t-\tF1\tplan.pdf\n"
+    );
+    page1.assert_hits(1);
+    page2.assert_hits(1);
+}
+
+#[test]
+fn save_and_unsave_treat_repeats_as_notices() {
+    let server = MockServer::start();
+    let add = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/stars.add")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("timestamp", "1.0");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "already_starred" }));
+    });
+    let remove = server.mock(|when, then| {
+        when.method(POST).path("/api/stars.remove");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "not_starred" }));
+    });
+
+    let out = run(&server, &["save", "--channel", "C1", "--ts", "1.0"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "1.0 was already saved\n"
+    );
+    let out = run(&server, &["unsave", "--channel", "C1", "--ts", "1.0"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "1.0 was not saved\n");
+    add.assert_hits(1);
+    remove.assert_hits(1);
+}
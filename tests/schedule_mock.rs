@@ -0,0 +1,97 @@
+mod common;
+
+use common::slack;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn in_an_hour() -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    (now + 3600).to_string()
+}
+
+#[test]
+fn schedule_posts_thread_reply_and_reports_id() {
+    let server = MockServer::start();
+    let post_at = in_an_hour();
+    let scheduled = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.scheduleMessage")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("text", "standup reminder")
+            .x_www_form_urlencoded_tuple("post_at", &post_at)
+            .x_www_form_urlencoded_tuple("thread_ts", "1.0");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channel": "C1",
+            "scheduled_message_id": "Q1298393284",
+            "post_at": 1712345678
+        }));
+    });
+
+    let out = slack(&server)
+        .args(["schedule", "--channel", "C1", "--text", "standup reminder"])
+        .args(["--post-at", &post_at, "--thread-ts", "1.0"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "scheduled Q1298393284 for 2024-04-05 19:34 UTC\n"
+    );
+    scheduled.assert();
+}
+
+#[test]
+fn past_post_at_is_refused_before_calling_slack() {
+    let server = MockServer::start();
+    let scheduled = server.mock(|when, then| {
+        when.method(POST).path("/api/chat.scheduleMessage");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = slack(&server)
+        .args([
+            "schedule",
+            "--channel",
+            "C1",
+            "--text",
+            "x",
+            "--post-at",
+            "1000",
+        ])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("in the past"));
+    scheduled.assert_hits(0);
+}
+
+#[test]
+fn schedule_rejects_blank_text_without_calling_slack() {
+    let server = MockServer::start();
+    let scheduled = server.mock(|when, then| {
+        when.method(POST).path("/api/chat.scheduleMessage");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = slack(&server)
+        .args([
+            "schedule",
+            "--channel",
+            "C1",
+            "--text",
+            "  \n ",
+            "--post-at",
+            &in_an_hour(),
+        ])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("--text is empty"), "{err}");
+    scheduled.assert_hits(0);
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use common::slack;
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn file(id: &str, size: u64) -> serde_json::Value {
+    json!({
+        "id": id,
+        "name": format!("{id}.pdf"),
+        "filetype": "pdf",
+        "size": size,
+        "user": "U1",
+        "username": "jane",
+        "permalink": format!("https://acme.slack.com/files/U1/{id}/report.pdf")
+    })
+}
+
+#[test]
+fn search_files_pages_until_limit() {
+    let server = MockServer::start();
+    // --limit 150 is split into two pages of 75.
+    let page1 = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/search.files")
+            .x_www_form_urlencoded_tuple("query", "quarterly report")
+            .x_www_form_urlencoded_tuple("count", "75")
+            .x_www_form_urlencoded_tuple("page", "1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "files": {
+                "matches": [file("F1", 2048)],
+                "paging": { "count": 75, "total": 2, "page": 1, "pages": 2 }
+            }
+        }));
+    });
+    let page2 = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/search.files")
+            .x_www_form_urlencoded_tuple("page", "2");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "files": {
+                "matches": [file("F2", 5 * 1024 * 1024)],
+                "paging": { "count": 75, "total": 2, "page": 2, "pages": 2 }
+            }
+        }));
+    });
+
+    let out = slack(&server)
+        .args([
+            "search-files",
+            "--query",
+            "quarterly report",
+            "--limit",
+            "150",
+        ])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "F1\tF1.pdf\tpdf\t2.0 KiB\tjane\thttps://acme.slack.com/files/U1/F1/report.pdf\n\
+         F2\tF2.pdf\tpdf\t5.0 MiB\tjane\thttps://acme.slack.com/files/U1/F2/report.pdf\n"
+    );
+    page1.assert();
+    page2.assert();
+}
+
+#[test]
+fn search_files_fails_when_file_search_is_off() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/search.files");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = slack(&server)
+        .args(["search-files", "--query", "x"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("search may be turned off"));
+}
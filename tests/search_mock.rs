@@ -0,0 +1,222 @@
+mod common;
+
+use assert_cmd::Command;
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn slack(server: &MockServer, token: &str) -> Command {
+    let mut cmd = common::slack(server);
+    cmd.env("SLACK_TOKEN", token);
+    cmd
+}
+
+fn hit(ts: &str, text: &str) -> serde_json::Value {
+    json!({
+        "ts": ts,
+        "channel": { "id": "C1", "name": "ops" },
+        "user": "U1",
+        "username": "jane",
+        "text": text,
+        "permalink": format!("https://acme.slack.com/archives/C1/p{}", ts.replace('.', ""))
+    })
+}
+
+#[test]
+fn search_prints_matches_and_next_page() {
+    let server = MockServer::start();
+    let search = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/search.messages")
+            .x_www_form_urlencoded_tuple("query", "deploy failed")
+            .x_www_form_urlencoded_tuple("count", "2")
+            .x_www_form_urlencoded_tuple("page", "1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "messages": {
+                "matches": [hit("2.0", "deploy failed again"), hit("1.0", "deploy failed")],
+                "paging": { "count": 2, "total": 5, "page": 1, "pages": 3 }
+            }
+        }));
+    });
+
+    let out = slack(&server, "xoxp-test")
+        .args(["search", "--query", "deploy failed", "--limit", "2"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "2.0\t#ops\tjane\tdeploy failed again\thttps://acme.slack.com/archives/C1/p20\n\
+         1.0\t#ops\tjane\tdeploy failed\thttps://acme.slack.com/archives/C1/p10\n"
+    );
+    assert!(
+        String::from_utf8_lossy(&out.stderr).contains("5 matches in all; continue with --page 2")
+    );
+    search.assert();
+}
+
+#[test]
+fn search_follows_pages_up_to_limit() {
+    let server = MockServer::start();
+    let page2 = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/search.messages")
+            .x_www_form_urlencoded_tuple("count", "1")
+            .x_www_form_urlencoded_tuple("page", "2");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "messages": {
+                "matches": [hit("2.0", "second")],
+                "paging": { "count": 1, "total": 2, "page": 2, "pages": 2 }
+            }
+        }));
+    });
+    let page3 = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/search.messages")
+            .x_www_form_urlencoded_tuple("page", "3");
+        then.status(200)
+            .json_body(json!({ "ok": true, "messages": { "matches": [] } }));
+    });
+
+    let out = slack(&server, "xoxp-test")
+        .args(["search", "--query", "x", "--limit", "1", "--page", "2"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert!(String::from_utf8_lossy(&out.stdout).starts_with("2.0\t#ops\tjane\tsecond\t"));
+    assert!(out.stderr.is_empty(), "{out:?}");
+    page2.assert();
+    page3.assert_hits(0);
+}
+
+#[test]
+fn search_with_no_matches_says_so() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/search.messages");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "messages": { "matches": [], "paging": { "count": 20, "total": 0, "page": 1, "pages": 0 } }
+        }));
+    });
+
+    let out = slack(&server, "xoxp-test")
+        .args(["search", "--query", "nothing"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "no matches\n");
+}
+
+#[test]
+fn search_explains_token_type_errors() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/search.messages");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "not_allowed_token_type" }));
+    });
+
+    let out = slack(&server, "xoxp-test")
+        .args(["search", "--query", "x"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("search needs a user token"));
+
+    let bot = slack(&server, "xoxb-test")
+        .args(["search", "--query", "x"])
+        .output()
+        .unwrap();
+    assert!(!bot.status.success());
+    assert!(String::from_utf8_lossy(&bot.stderr).contains("`search` needs a user token"));
+}
+
+#[test]
+fn search_all_walks_every_page_with_sort() {
+    let server = MockServer::start();
+    let page1 = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/search.messages")
+            .x_www_form_urlencoded_tuple("count", "100")
+            .x_www_form_urlencoded_tuple("page", "1")
+            .x_www_form_urlencoded_tuple("sort", "timestamp")
+            .x_www_form_urlencoded_tuple("sort_dir", "asc");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "messages": {
+                "matches": [hit("1.0", "first"), hit("2.0", "second")],
+                "paging": { "count": 100, "total": 3, "page": 1, "pages": 2 }
+            }
+        }));
+    });
+    let page2 = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/search.messages")
+            .x_www_form_urlencoded_tuple("page", "2")
+            .x_www_form_urlencoded_tuple("sort", "timestamp");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "messages": {
+                "matches": [hit("3.0", "third")],
+                "paging": { "count": 100, "total": 3, "page": 2, "pages": 2 }
+            }
+        }));
+    });
+
+    let out = slack(&server, "xoxp-test")
+        .args([
+            "search",
+            "--query",
+            "x",
+            "--all",
+            "--sort",
+            "timestamp",
+            "--sort-dir",
+            "asc",
+        ])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let ts: Vec<&str> = stdout
+        .lines()
+        .map(|l| l.split('\t').next().unwrap())
+        .collect();
+    assert_eq!(ts, ["1.0", "2.0", "3.0"]);
+    assert!(out.stderr.is_empty(), "{out:?}");
+    page1.assert();
+    page2.assert();
+}
+
+#[test]
+fn search_count_only_prints_the_total() {
+    let server = MockServer::start();
+    let search = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/search.messages")
+            .x_www_form_urlencoded_tuple("count", "1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "messages": {
+                "matches": [hit("1.0", "first")],
+                "pagination": { "total_count": 1234, "page": 1, "page_count": 1234 }
+            }
+        }));
+    });
+
+    let out = slack(&server, "xoxp-test")
+        .args(["search", "--query", "x", "--count-only"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "1234\n");
+    search.assert_hits(1);
+
+    let clash = slack(&server, "xoxp-test")
+        .args(["search", "--query", "x", "--count-only", "--all"])
+        .output()
+        .unwrap();
+    assert_eq!(clash.status.code(), Some(2));
+}
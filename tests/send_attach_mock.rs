@@ -0,0 +1,123 @@
+mod common;
+
+use common::slack;
+use httpmock::prelude::*;
+use predicates::prelude::*;
+
+fn mock_upload_steps(server: &MockServer) -> (httpmock::Mock<'_>, httpmock::Mock<'_>) {
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.getUploadURLExternal")
+            .x_www_form_urlencoded_tuple("filename", "report.txt")
+            .x_www_form_urlencoded_tuple("length", "11");
+        then.status(200).json_body(serde_json::json!({
+            "ok": true,
+            "upload_url": server.url("/upload/F1"),
+            "file_id": "F1"
+        }));
+    });
+    let upload = server.mock(|when, then| {
+        when.method(POST).path("/upload/F1").body("hello world");
+        then.status(200).body("OK - 11");
+    });
+    let complete = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.completeUploadExternal")
+            .x_www_form_urlencoded_tuple("channel_id", "C1")
+            .x_www_form_urlencoded_tuple("thread_ts", "111.000");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": true, "files": [{ "id": "F1" }] }));
+    });
+    (upload, complete)
+}
+
+fn report(dir: &tempfile::TempDir) -> String {
+    let path = dir.path().join("report.txt");
+    std::fs::write(&path, "hello world").unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn send_with_attach_uploads_into_the_new_message_thread() {
+    let server = MockServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    let post = server.mock(|when, then| {
+        when.method(POST).path("/api/chat.postMessage");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": true, "ts": "111.000" }));
+    });
+    let (upload, complete) = mock_upload_steps(&server);
+
+    slack(&server)
+        .args(["send", "--channel", "C1", "--text", "here's the report"])
+        .args(["--attach", &report(&dir)])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("sent ok, ts=111.000"))
+        .stdout(predicate::str::contains("attached 1 file(s): F1"));
+    post.assert_hits(1);
+    upload.assert_hits(1);
+    complete.assert_hits(1);
+}
+
+#[test]
+fn attach_inline_skips_the_separate_post() {
+    let server = MockServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    let post = server.mock(|when, then| {
+        when.method(POST).path("/api/chat.postMessage");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": true, "ts": "999.000" }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/files.getUploadURLExternal");
+        then.status(200).json_body(serde_json::json!({
+            "ok": true,
+            "upload_url": server.url("/upload/F1"),
+            "file_id": "F1"
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/upload/F1");
+        then.status(200);
+    });
+    let complete = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.completeUploadExternal")
+            .x_www_form_urlencoded_tuple("initial_comment", "see attached");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": true }));
+    });
+
+    slack(&server)
+        .args(["send", "--channel", "C1", "--text", "see attached"])
+        .args(["--attach", &report(&dir), "--attach-inline"])
+        .assert()
+        .success();
+    post.assert_hits(0);
+    complete.assert_hits(1);
+}
+
+#[test]
+fn failed_upload_reports_the_orphaned_message() {
+    let server = MockServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/chat.postMessage");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": true, "ts": "111.000" }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/files.getUploadURLExternal");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": false, "error": "missing_scope" }));
+    });
+
+    slack(&server)
+        .args(["send", "--channel", "C1", "--text", "report"])
+        .args(["--attach", &report(&dir)])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("message was posted (ts=111.000)"))
+        .stderr(predicate::str::contains("missing_scope"));
+}
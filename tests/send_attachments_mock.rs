@@ -0,0 +1,59 @@
+mod common;
+
+use common::slack;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn attachments_file_is_posted_as_json() {
+    let server = MockServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("status.json");
+    std::fs::write(
+        &file,
+        r##"[ { "color": "#36a64f", "text": "all green" } ]"##,
+    )
+    .unwrap();
+    let post = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.postMessage")
+            .x_www_form_urlencoded_tuple(
+                "attachments",
+                r##"[{"color":"#36a64f","text":"all green"}]"##,
+            );
+        then.status(200)
+            .json_body(json!({ "ok": true, "ts": "1.0" }));
+    });
+
+    let out = slack(&server)
+        .args(["send", "--channel", "C1", "--text", "Build #42"])
+        .arg("--attachments-file")
+        .arg(&file)
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    post.assert();
+}
+
+#[test]
+fn malformed_attachments_are_rejected_before_posting() {
+    let server = MockServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("bad.json");
+    std::fs::write(&file, r#"{ "color": "good" }"#).unwrap();
+    let post = server.mock(|when, then| {
+        when.method(POST).path("/api/chat.postMessage");
+        then.status(200)
+            .json_body(json!({ "ok": true, "ts": "1.0" }));
+    });
+
+    let out = slack(&server)
+        .args(["send", "--channel", "C1", "--text", "x"])
+        .arg("--attachments-file")
+        .arg(&file)
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("array of objects"));
+    post.assert_hits(0);
+}
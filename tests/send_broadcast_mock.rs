@@ -0,0 +1,63 @@
+mod common;
+
+use common::slack;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn broadcast_is_sent_only_when_asked() {
+    let server = MockServer::start();
+    let broadcast = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.postMessage")
+            .x_www_form_urlencoded_tuple("thread_ts", "1.0")
+            .x_www_form_urlencoded_tuple("reply_broadcast", "true");
+        then.status(200)
+            .json_body(json!({ "ok": true, "ts": "2.0" }));
+    });
+    let plain = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.postMessage")
+            .matches(|req| {
+                !String::from_utf8_lossy(req.body.as_deref().unwrap_or_default())
+                    .contains("reply_broadcast")
+            });
+        then.status(200)
+            .json_body(json!({ "ok": true, "ts": "3.0" }));
+    });
+
+    let out = slack(&server)
+        .args(["send", "--channel", "C1", "--text", "fixed"])
+        .args(["--thread-ts", "1.0", "--broadcast"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "sent ok, ts=2.0\n");
+
+    let out = slack(&server)
+        .args([
+            "send",
+            "--channel",
+            "C1",
+            "--text",
+            "fixed",
+            "--thread-ts",
+            "1.0",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "sent ok, ts=3.0\n");
+    broadcast.assert_hits(1);
+    plain.assert_hits(1);
+}
+
+#[test]
+fn broadcast_needs_a_thread() {
+    let server = MockServer::start();
+    let out = slack(&server)
+        .args(["send", "--channel", "C1", "--text", "x", "--broadcast"])
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("--thread-ts"));
+}
@@ -0,0 +1,55 @@
+use assert_cmd::prelude::*;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+fn send(server: &MockServer, token: &str, extra: &[&str]) -> std::process::Output {
+    Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", token)
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["send", "--channel", "C1", "--text", "Deployed"])
+        .args(extra)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn username_and_icon_are_posted_and_user_tokens_warned() {
+    let server = MockServer::start();
+    let post = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.postMessage")
+            .x_www_form_urlencoded_tuple("username", "Deploy Bot 🚀")
+            .x_www_form_urlencoded_tuple("icon_emoji", ":rocket:");
+        then.status(200)
+            .json_body(json!({ "ok": true, "ts": "1.0" }));
+    });
+    let custom = ["--username", "Deploy Bot 🚀", "--icon-emoji", ":rocket:"];
+
+    let out = send(&server, "xoxb-bot", &custom);
+    assert!(out.status.success(), "{out:?}");
+    assert!(out.stderr.is_empty(), "{out:?}");
+
+    let out = send(&server, "xoxp-user", &custom);
+    assert!(out.status.success(), "{out:?}");
+    assert!(String::from_utf8_lossy(&out.stderr).contains("ignore them for this user token"));
+    post.assert_hits(2);
+}
+
+#[test]
+fn icon_emoji_and_url_are_exclusive() {
+    let server = MockServer::start();
+    let out = send(
+        &server,
+        "xoxb-bot",
+        &[
+            "--icon-emoji",
+            ":rocket:",
+            "--icon-url",
+            "https://x.test/i.png",
+        ],
+    );
+    assert_eq!(out.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("--icon-url"));
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn stub_groups(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST).path("/api/usergroups.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "usergroups": [
+                { "id": "S0123ABC", "handle": "backend", "name": "Backend" },
+                { "id": "S0456DEF", "handle": "sre", "name": "SRE" }
+            ]
+        }));
+    });
+}
+
+#[test]
+fn mention_group_prepends_and_fills_placeholders() {
+    let server = MockServer::start();
+    stub_groups(&server);
+    let post = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.postMessage")
+            .x_www_form_urlencoded_tuple(
+                "text",
+                "<!subteam^S0123ABC|@backend> deploy at 3pm, <!subteam^S0456DEF|@sre> on standby",
+            );
+        then.status(200)
+            .json_body(json!({ "ok": true, "ts": "1712345678.000100" }));
+    });
+
+    let out = run(
+        &server,
+        &[
+            "send",
+            "--channel",
+            "C12345678",
+            "--text",
+            "deploy at 3pm, {group:sre} on standby",
+            "--mention-group",
+            "backend",
+            "--mention-group",
+            "@sre",
+        ],
+    );
+    assert!(out.status.success(), "{out:?}");
+    post.assert_hits(1);
+}
+
+#[test]
+fn unknown_group_fails_before_posting() {
+    let server = MockServer::start();
+    stub_groups(&server);
+    let post = server.mock(|when, then| {
+        when.method(POST).path("/api/chat.postMessage");
+        then.status(200)
+            .json_body(json!({ "ok": true, "ts": "1.0" }));
+    });
+
+    let out = run(
+        &server,
+        &["send", "--channel", "C1", "--text", "hi {group:backnd}"],
+    );
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        err.contains("no user group with handle @backnd; did you mean @backend?"),
+        "{err}"
+    );
+    post.assert_hits(0);
+}
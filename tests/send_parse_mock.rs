@@ -0,0 +1,44 @@
+use assert_cmd::prelude::*;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+fn send(server: &MockServer, extra: &[&str]) -> std::process::Output {
+    Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["send", "--channel", "C1", "--text", "@jane see #general"])
+        .args(extra)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn link_names_and_parse_are_posted() {
+    let server = MockServer::start();
+    let post = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.postMessage")
+            .x_www_form_urlencoded_tuple("link_names", "1")
+            .x_www_form_urlencoded_tuple("parse", "full");
+        then.status(200)
+            .json_body(json!({ "ok": true, "ts": "1.0" }));
+    });
+
+    let out = send(&server, &["--link-names", "--parse", "full"]);
+    assert!(out.status.success(), "{out:?}");
+    post.assert();
+}
+
+#[test]
+fn parse_only_accepts_known_modes() {
+    let server = MockServer::start();
+    let out = send(&server, &["--parse", "loose"]);
+    assert_eq!(out.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("full") && stderr.contains("none"),
+        "{stderr}"
+    );
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use common::slack;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn permalink_is_printed_after_each_sent_part() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/chat.postMessage");
+        then.status(200)
+            .json_body(json!({ "ok": true, "ts": "111.000" }));
+    });
+    let link = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/chat.getPermalink")
+            .query_param("channel", "C1")
+            .query_param("message_ts", "111.000");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "permalink": "https://example.slack.com/archives/C1/p111000"
+        }));
+    });
+
+    let out = slack(&server)
+        .args(["send", "--channel", "C1", "--text", "notes", "--permalink"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "sent ok, ts=111.000\nhttps://example.slack.com/archives/C1/p111000\n"
+    );
+    link.assert_hits(1);
+}
+
+#[test]
+fn permalink_failure_only_warns() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/chat.postMessage");
+        then.status(200)
+            .json_body(json!({ "ok": true, "ts": "111.000" }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/api/chat.getPermalink");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "message_not_found" }));
+    });
+
+    let out = slack(&server)
+        .args(["send", "--channel", "C1", "--text", "notes", "--permalink"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "sent ok, ts=111.000\n"
+    );
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("message_not_found"), "{stderr}");
+}
+
+#[test]
+fn no_permalink_lookup_without_the_flag() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/chat.postMessage");
+        then.status(200)
+            .json_body(json!({ "ok": true, "ts": "111.000" }));
+    });
+    let link = server.mock(|when, then| {
+        when.method(GET).path("/api/chat.getPermalink");
+        then.status(200)
+            .json_body(json!({ "ok": true, "permalink": "https://x.test/p" }));
+    });
+
+    let out = slack(&server)
+        .args(["send", "--channel", "C1", "--text", "notes"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "sent ok, ts=111.000\n"
+    );
+    link.assert_hits(0);
+}
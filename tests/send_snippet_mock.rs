@@ -0,0 +1,205 @@
+mod common;
+
+use common::slack;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn long_text_becomes_a_snippet() {
+    let server = MockServer::start();
+    let text = format!("Build 42 log\n{}", "line\n".repeat(1000));
+    let get_url = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.getUploadURLExternal")
+            .x_www_form_urlencoded_tuple("filename", "message.txt")
+            .x_www_form_urlencoded_tuple("snippet_type", "text");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "upload_url": server.url("/upload/F9"),
+            "file_id": "F9"
+        }));
+    });
+    let upload = server.mock(|when, then| {
+        when.method(POST).path("/upload/F9").body(text.trim_end());
+        then.status(200);
+    });
+    let complete = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.completeUploadExternal")
+            .x_www_form_urlencoded_tuple("channel_id", "C1")
+            .x_www_form_urlencoded_tuple("files", r#"[{"id":"F9","title":"Build 42 log"}]"#);
+        then.status(200)
+            .json_body(json!({ "ok": true, "files": [{ "id": "F9" }] }));
+    });
+    let info = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/files.info")
+            .query_param("file", "F9");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "file": { "id": "F9", "permalink": "https://acme.slack.com/files/U1/F9/message.txt" }
+        }));
+    });
+    let post = server.mock(|when, then| {
+        when.method(POST).path("/api/chat.postMessage");
+        then.status(200)
+            .json_body(json!({ "ok": true, "ts": "1.0" }));
+    });
+
+    let out = slack(&server)
+        .args([
+            "send",
+            "--channel",
+            "C1",
+            "--text",
+            "-",
+            "--as-snippet-if-long",
+        ])
+        .write_stdin(text.clone())
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "uploaded snippet F9\nhttps://acme.slack.com/files/U1/F9/message.txt\n"
+    );
+    get_url.assert();
+    upload.assert();
+    complete.assert();
+    info.assert();
+    post.assert_hits(0);
+}
+
+#[test]
+fn short_text_is_sent_normally() {
+    let server = MockServer::start();
+    let post = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.postMessage")
+            .x_www_form_urlencoded_tuple("text", "short");
+        then.status(200)
+            .json_body(json!({ "ok": true, "ts": "1.0" }));
+    });
+    let upload = server.mock(|when, then| {
+        when.method(POST).path("/api/files.getUploadURLExternal");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = slack(&server)
+        .args([
+            "send",
+            "--channel",
+            "C1",
+            "--text",
+            "short",
+            "--as-snippet-if-long",
+        ])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "sent ok, ts=1.0\n");
+    post.assert();
+    upload.assert_hits(0);
+}
+
+#[test]
+fn no_split_text_over_the_limit_still_goes_up_as_a_snippet() {
+    let server = MockServer::start();
+    let text = format!("Huge log\n{}", "0123456789\n".repeat(5000));
+    let get_url = server.mock(|when, then| {
+        when.method(POST).path("/api/files.getUploadURLExternal");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "upload_url": server.url("/upload/F8"),
+            "file_id": "F8"
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/upload/F8");
+        then.status(200);
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/files.completeUploadExternal");
+        then.status(200)
+            .json_body(json!({ "ok": true, "files": [{ "id": "F8" }] }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/api/files.info");
+        then.status(200)
+            .json_body(json!({ "ok": true, "file": { "id": "F8", "permalink": "https://x/F8" } }));
+    });
+    let post = server.mock(|when, then| {
+        when.method(POST).path("/api/chat.postMessage");
+        then.status(200)
+            .json_body(json!({ "ok": true, "ts": "1.0" }));
+    });
+
+    let out = slack(&server)
+        .args([
+            "send",
+            "--channel",
+            "C1",
+            "--text",
+            "-",
+            "--no-split",
+            "--as-snippet-if-long",
+        ])
+        .write_stdin(text)
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    get_url.assert();
+    post.assert_hits(0);
+}
+
+#[test]
+fn snippet_over_one_megabyte_fails_before_uploading() {
+    let server = MockServer::start();
+    let get_url = server.mock(|when, then| {
+        when.method(POST).path("/api/files.getUploadURLExternal");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "unexpected" }));
+    });
+
+    let out = slack(&server)
+        .args([
+            "send",
+            "--channel",
+            "C1",
+            "--as-snippet-if-long",
+            "--text",
+            "-",
+        ])
+        .write_stdin("x".repeat(1024 * 1024 + 1))
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("text is 1048577 bytes"), "{err}");
+    assert_eq!(get_url.hits(), 0);
+}
+
+#[test]
+fn message_only_options_conflict_with_snippets() {
+    let server = MockServer::start();
+    for flag in [
+        &["--link-names"][..],
+        &["--username", "deploybot"],
+        &["--parse", "full"],
+    ] {
+        let out = slack(&server)
+            .args([
+                "send",
+                "--channel",
+                "C1",
+                "--as-snippet-if-long",
+                "--text",
+                "hi",
+            ])
+            .args(flag)
+            .output()
+            .unwrap();
+        assert!(!out.status.success(), "{flag:?}");
+        assert!(String::from_utf8_lossy(&out.stderr).contains("cannot be used with"));
+    }
+}
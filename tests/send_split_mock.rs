@@ -0,0 +1,136 @@
+mod common;
+
+use common::slack;
+use httpmock::prelude::*;
+use predicates::prelude::*;
+
+fn long_text() -> String {
+    (0..300)
+        .map(|i| format!("log line {i:04} something happened"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn long_text_is_posted_in_threaded_parts() {
+    let server = MockServer::start();
+    let first = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.postMessage")
+            .body_contains("text=%5B1%2F3%5D");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": true, "ts": "111.000" }));
+    });
+    let rest = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.postMessage")
+            .x_www_form_urlencoded_tuple("thread_ts", "111.000");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": true, "ts": "222.000" }));
+    });
+
+    slack(&server)
+        .args(["send", "--channel", "C1", "--text", "-"])
+        .write_stdin(long_text())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("sent ok, ts=111.000"))
+        .stdout(predicate::str::contains("sent ok, ts=222.000").count(2));
+    first.assert_hits(1);
+    rest.assert_hits(2);
+}
+
+#[test]
+fn no_split_sends_a_single_message() {
+    let server = MockServer::start();
+    let post = server.mock(|when, then| {
+        when.method(POST).path("/api/chat.postMessage");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": false, "error": "msg_too_long" }));
+    });
+
+    slack(&server)
+        .args(["send", "--channel", "C1", "--no-split", "--text", "-"])
+        .write_stdin(long_text())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("msg_too_long"));
+    post.assert_hits(1);
+}
+
+#[test]
+fn no_split_over_the_hard_limit_fails_before_posting() {
+    let server = MockServer::start();
+    let post = server.mock(|when, then| {
+        when.method(POST).path("/api/chat.postMessage");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": true, "ts": "1.0" }));
+    });
+
+    slack(&server)
+        .args(["send", "--channel", "C1", "--no-split", "--text", "-"])
+        .write_stdin("x".repeat(40_001))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("text is 40001 characters"));
+    post.assert_hits(0);
+}
+
+#[test]
+fn over_the_hard_limit_needs_split() {
+    let server = MockServer::start();
+    let post = server.mock(|when, then| {
+        when.method(POST).path("/api/chat.postMessage");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": true, "ts": "111.000" }));
+    });
+    let huge = "log line\n".repeat(4_500);
+
+    slack(&server)
+        .args(["send", "--channel", "C1", "--text", "-"])
+        .write_stdin(huge.clone())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("text is 40499 characters"))
+        .stderr(predicate::str::contains("pass --split"));
+    post.assert_hits(0);
+
+    slack(&server)
+        .args(["send", "--channel", "C1", "--split", "--text", "-"])
+        .write_stdin(huge)
+        .assert()
+        .success();
+    assert!(post.hits() > 10);
+}
+
+#[test]
+fn split_is_explicit_opt_in_and_conflicts_with_no_split() {
+    let server = MockServer::start();
+    let post = server.mock(|when, then| {
+        when.method(POST).path("/api/chat.postMessage");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": true, "ts": "111.000" }));
+    });
+
+    slack(&server)
+        .args(["send", "--channel", "C1", "--split", "--text", "-"])
+        .write_stdin(long_text())
+        .assert()
+        .success();
+    post.assert_hits(3);
+
+    slack(&server)
+        .args([
+            "send",
+            "--channel",
+            "C1",
+            "--split",
+            "--no-split",
+            "--text",
+            "hi",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+    post.assert_hits(3);
+}
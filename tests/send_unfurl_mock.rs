@@ -0,0 +1,65 @@
+use assert_cmd::prelude::*;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+fn send(server: &MockServer, extra: &[&str]) -> std::process::Output {
+    Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args([
+            "send",
+            "--channel",
+            "C1",
+            "--text",
+            "see https://example.com",
+        ])
+        .args(extra)
+        .output()
+        .unwrap()
+}
+
+fn body(req: &HttpMockRequest) -> String {
+    String::from_utf8_lossy(req.body.as_deref().unwrap_or_default()).into_owned()
+}
+
+#[test]
+fn unfurl_fields_are_sent_only_when_requested() {
+    let server = MockServer::start();
+    let off = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.postMessage")
+            .x_www_form_urlencoded_tuple("unfurl_links", "false")
+            .x_www_form_urlencoded_tuple("unfurl_media", "false");
+        then.status(200)
+            .json_body(json!({ "ok": true, "ts": "1.0" }));
+    });
+    let links_on = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.postMessage")
+            .x_www_form_urlencoded_tuple("unfurl_links", "true")
+            .matches(|req| !body(req).contains("unfurl_media"));
+        then.status(200)
+            .json_body(json!({ "ok": true, "ts": "2.0" }));
+    });
+    let defaults = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/chat.postMessage")
+            .matches(|req| !body(req).contains("unfurl_"));
+        then.status(200)
+            .json_body(json!({ "ok": true, "ts": "3.0" }));
+    });
+
+    assert!(send(&server, &["--no-unfurl-links", "--no-unfurl-media"])
+        .status
+        .success());
+    assert!(send(&server, &["--unfurl-links"]).status.success());
+    assert!(send(&server, &[]).status.success());
+    off.assert_hits(1);
+    links_on.assert_hits(1);
+    defaults.assert_hits(1);
+
+    let out = send(&server, &["--unfurl-links", "--no-unfurl-links"]);
+    assert_eq!(out.status.code(), Some(2));
+}
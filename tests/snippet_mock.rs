@@ -0,0 +1,112 @@
+mod common;
+
+use common::slack;
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn stub_upload<'a>(server: &'a MockServer, snippet_type: &str, title: &str) -> httpmock::Mock<'a> {
+    let get_url = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.getUploadURLExternal")
+            .x_www_form_urlencoded_tuple("snippet_type", snippet_type);
+        then.status(200).json_body(json!({
+            "ok": true,
+            "upload_url": server.url("/upload/F1"),
+            "file_id": "F1"
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/upload/F1");
+        then.status(200);
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.completeUploadExternal")
+            .x_www_form_urlencoded_tuple("channel_id", "C1")
+            .x_www_form_urlencoded_tuple(
+                "files",
+                json!([{ "id": "F1", "title": title }]).to_string(),
+            );
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/api/files.info");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "file": { "permalink": "https://acme.slack.com/files/U1/F1/snippet" }
+        }));
+    });
+    get_url
+}
+
+#[test]
+fn snippet_uses_given_title_and_filetype() {
+    let server = MockServer::start();
+    let get_url = stub_upload(&server, "log", "prod error");
+
+    let out = slack(&server)
+        .args([
+            "snippet",
+            "--channel",
+            "C1",
+            "--title",
+            "prod error",
+            "--filetype",
+            "log",
+        ])
+        .write_stdin("ERROR boom\nat main.rs:1\n")
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "uploaded snippet F1\nhttps://acme.slack.com/files/U1/F1/snippet\n"
+    );
+    get_url.assert();
+}
+
+#[test]
+fn snippet_guesses_filetype_and_title() {
+    let server = MockServer::start();
+    let get_url = stub_upload(&server, "json", "{\"status\": \"down\"}");
+
+    let out = slack(&server)
+        .args(["snippet", "--channel", "C1"])
+        .write_stdin("{\"status\": \"down\"}\n")
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    get_url.assert();
+}
+
+#[test]
+fn snippet_refuses_empty_stdin() {
+    let server = MockServer::start();
+    let out = slack(&server)
+        .args(["snippet", "--channel", "C1"])
+        .write_stdin("  \n")
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("nothing on stdin"));
+}
+
+#[test]
+fn snippet_over_the_size_limit_fails_before_uploading() {
+    let server = MockServer::start();
+    let get_url = server.mock(|when, then| {
+        when.method(POST).path("/api/files.getUploadURLExternal");
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": true }));
+    });
+
+    let out = slack(&server)
+        .args(["snippet", "--channel", "C1"])
+        .write_stdin("x".repeat(1024 * 1024 + 1))
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("over Slack's 1 MB snippet limit"), "{err}");
+    get_url.assert_hits(0);
+}
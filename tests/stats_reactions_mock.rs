@@ -0,0 +1,100 @@
+use assert_cmd::prelude::*;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+#[test]
+fn stats_reactions_reports_leaders_with_permalinks_and_reactors() {
+    let server = MockServer::start();
+    let page1 = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.history")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("oldest", "0")
+            .matches(|req| {
+                !String::from_utf8_lossy(req.body.as_deref().unwrap_or_default())
+                    .contains("cursor=")
+            });
+        then.status(200).json_body(json!({
+            "ok": true,
+            "messages": [
+                { "ts": "3.0", "user": "U3", "text": "meh" },
+                { "ts": "2.0", "user": "U2", "text": "ship it",
+                  "reactions": [{ "name": "tada", "count": 3, "users": ["U1"] }] }
+            ],
+            "response_metadata": { "next_cursor": "page2" }
+        }));
+    });
+    let page2 = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.history")
+            .x_www_form_urlencoded_tuple("cursor", "page2");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "messages": [
+                { "ts": "1.0", "user": "U1", "text": "hello",
+                  "reactions": [{ "name": "wave", "count": 1, "users": ["U2"] }] }
+            ]
+        }));
+    });
+    let permalink = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/chat.getPermalink")
+            .query_param("message_ts", "2.0");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "permalink": "https://example.slack.com/archives/C1/p2000000"
+        }));
+    });
+    let full = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/reactions.get")
+            .x_www_form_urlencoded_tuple("timestamp", "2.0")
+            .x_www_form_urlencoded_tuple("full", "true");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "message": { "ts": "2.0", "reactions": [
+                { "name": "tada", "count": 3, "users": ["U1", "U3", "U4"] }
+            ] }
+        }));
+    });
+
+    let out = Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["stats", "reactions", "--channel", "C1", "--since", "0"])
+        .args(["--top", "1", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let report: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+
+    page1.assert();
+    page2.assert();
+    permalink.assert();
+    full.assert();
+    assert_eq!(report["total"], 4);
+    assert_eq!(report["messages"], 3);
+    assert_eq!(report["messages_with_reactions"], 2);
+    assert_eq!(
+        report["top_emojis"],
+        json!([{ "name": "tada", "count": 3 }])
+    );
+    assert_eq!(
+        report["top_messages"],
+        json!([{
+            "ts": "2.0", "user": "U2", "count": 3,
+            "permalink": "https://example.slack.com/archives/C1/p2000000"
+        }])
+    );
+    // Ties are broken by user ID.
+    assert_eq!(
+        report["top_reactors"],
+        json!([{ "name": "U1", "count": 1 }])
+    );
+}
@@ -0,0 +1,179 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+fn stub_timezone(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST).path("/api/auth.test");
+        then.status(200)
+            .json_body(json!({ "ok": true, "user_id": "U1" }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.info");
+        then.status(200)
+            .json_body(json!({ "ok": true, "user": { "id": "U1", "tz_offset": -25200 } }));
+    });
+}
+
+#[test]
+fn set_adds_colons_and_reads_expiry_in_profile_zone() {
+    let server = MockServer::start();
+    stub_timezone(&server);
+    let set = server.mock(|when, then| {
+        when.method(POST).path("/api/users.profile.set").x_www_form_urlencoded_tuple(
+            "profile",
+            r#"{"status_emoji":":palm_tree:","status_expiration":4086637200,"status_text":"On PTO"}"#,
+        );
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = run(
+        &server,
+        &[
+            "status",
+            "set",
+            "--emoji",
+            "palm_tree",
+            "--text",
+            "On PTO",
+            "--expires",
+            "2099-07-01 18:00",
+        ],
+    );
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "status set: :palm_tree: On PTO until 2099-07-01 18:00 -07:00\n"
+    );
+    set.assert_hits(1);
+}
+
+#[test]
+fn past_expiry_is_refused_and_clear_sends_empty_strings() {
+    let server = MockServer::start();
+    stub_timezone(&server);
+    let clear = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.profile.set")
+            .x_www_form_urlencoded_tuple(
+                "profile",
+                r#"{"status_emoji":"","status_expiration":0,"status_text":""}"#,
+            );
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = run(
+        &server,
+        &[
+            "status",
+            "set",
+            "--text",
+            "lunch",
+            "--expires",
+            "2020-01-01 12:00",
+        ],
+    );
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("is in the past"));
+
+    let out = run(&server, &["status", "clear"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "status cleared\n");
+    clear.assert_hits(1);
+}
+
+#[test]
+fn get_shows_emoji_text_and_expiry() {
+    let server = MockServer::start();
+    stub_timezone(&server);
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.profile.get");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "profile": {
+                "status_emoji": ":palm_tree:",
+                "status_text": "On PTO",
+                "status_expiration": 1712345678
+            }
+        }));
+    });
+
+    let out = run(&server, &["status", "get"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        ":palm_tree:\tOn PTO\tuntil 2024-04-05 12:34 -07:00\n"
+    );
+}
+
+fn with_presets(
+    config: &tempfile::TempDir,
+    server: &MockServer,
+    args: &[&str],
+) -> std::process::Output {
+    std::fs::write(
+        config.path().join("config.toml"),
+        "[status.lunch]\nemoji = \"sandwich\"\ntext = \"Lunch\"\nduration = \"1h\"\n\n\
+         [status.focus]\ntext = \"Heads down\"\n",
+    )
+    .unwrap();
+    Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .env("SLACK_CONFIG_DIR", config.path())
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn presets_apply_with_an_expiry_from_now() {
+    let server = MockServer::start();
+    let config = tempfile::tempdir().unwrap();
+    stub_timezone(&server);
+    let set = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.profile.set")
+            .matches(|req| {
+                let body = String::from_utf8_lossy(req.body.as_deref().unwrap_or_default());
+                body.contains("%3Asandwich%3A")
+                    && body.contains("Lunch")
+                    && !body.contains("status_expiration%22%3A0")
+            });
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = with_presets(&config, &server, &["status", "lunch"]);
+    assert!(out.status.success(), "{out:?}");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        stdout.starts_with("status set: :sandwich: Lunch until "),
+        "{stdout}"
+    );
+    set.assert_hits(1);
+
+    let out = with_presets(&config, &server, &["status", "--list-presets"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "focus\t\tHeads down\t-\nlunch\tsandwich\tLunch\t1h\n"
+    );
+}
+
+#[test]
+fn unknown_preset_lists_the_configured_names() {
+    let server = MockServer::start();
+    let config = tempfile::tempdir().unwrap();
+    let out = with_presets(&config, &server, &["status", "brunch"]);
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        err.contains("unknown status preset `brunch`; configured: focus, lunch"),
+        "{err}"
+    );
+}
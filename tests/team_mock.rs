@@ -0,0 +1,80 @@
+mod common;
+
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn run(server: &MockServer, args: &[&str]) -> std::process::Output {
+    let config = tempfile::tempdir().unwrap();
+    common::slack(server)
+        .env("SLACK_CONFIG_DIR", config.path())
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn team_prints_workspace_and_passes_team_id() {
+    let server = MockServer::start();
+    let scoped = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/team.info")
+            .x_www_form_urlencoded_tuple("team", "T0123ABCD");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "team": {
+                "id": "T0123ABCD", "name": "Acme Ops", "domain": "acme-ops",
+                "icon": { "image_132": "https://avatars.example/acme_132.png" }
+            }
+        }));
+    });
+
+    let out = run(&server, &["--team-id", "T0123ABCD", "team"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "name: Acme Ops\n\
+         domain: acme-ops\n\
+         id: T0123ABCD\n\
+         url: https://acme-ops.slack.com\n\
+         icon: https://avatars.example/acme_132.png\n"
+    );
+    scoped.assert_hits(1);
+}
+
+#[test]
+fn team_explains_a_team_the_token_cannot_see() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/team.info");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "team_not_found" }));
+    });
+
+    let out = run(&server, &["--team-id", "T0999ZZZZ", "team"]);
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("rejected team_id T0999ZZZZ"), "{err}");
+}
+
+#[test]
+fn team_format_json() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/team.info");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "team": { "id": "T1", "name": "Acme", "domain": "acme" }
+        }));
+    });
+
+    let out = run(&server, &["team", "--format", "json"]);
+    assert!(out.status.success(), "{out:?}");
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(
+        v,
+        json!({
+            "name": "Acme", "domain": "acme", "id": "T1",
+            "url": "https://acme.slack.com", "icon": null
+        })
+    );
+}
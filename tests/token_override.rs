@@ -0,0 +1,86 @@
+mod common;
+
+use assert_cmd::Command;
+use httpmock::prelude::*;
+use predicates::prelude::*;
+
+fn mock_auth(server: &MockServer, token: &str, team: &str) {
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/auth.test")
+            .header("authorization", format!("Bearer {token}"));
+        then.status(200)
+            .json_body(serde_json::json!({ "ok": true, "team": team }));
+    });
+}
+
+fn slack(server: &MockServer, state: &tempfile::TempDir) -> Command {
+    let mut cmd = common::slack(server);
+    cmd.env_remove("SLACK_TOKEN")
+        .env("SLACK_STATE_DIR", state.path());
+    cmd
+}
+
+#[test]
+fn token_flag_beats_slack_token_env() {
+    let server = MockServer::start();
+    let state = tempfile::tempdir().unwrap();
+    mock_auth(&server, "xoxp-env", "Env Team");
+    mock_auth(&server, "xoxp-override", "Override Team");
+
+    slack(&server, &state)
+        .env("SLACK_TOKEN", "xoxp-env")
+        .args(["--token", "xoxp-override", "whoami"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Override Team"));
+}
+
+#[test]
+fn token_flag_reads_stdin_and_files() {
+    let server = MockServer::start();
+    let state = tempfile::tempdir().unwrap();
+    mock_auth(&server, "xoxp-piped", "Piped Team");
+    mock_auth(&server, "xoxp-from-file", "File Team");
+
+    slack(&server, &state)
+        .env_remove("SLACK_TOKEN")
+        .args(["--token", "-", "whoami"])
+        .write_stdin("xoxp-piped\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Piped Team"));
+
+    let path = std::env::temp_dir().join(format!("mdslackcli-token-{}", std::process::id()));
+    std::fs::write(&path, "xoxp-from-file\n").unwrap();
+    slack(&server, &state)
+        .env_remove("SLACK_TOKEN")
+        .args(["--token", path.to_str().unwrap(), "whoami"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("File Team"));
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn token_flag_is_not_persisted() {
+    let server = MockServer::start();
+    let state = tempfile::tempdir().unwrap();
+    mock_auth(&server, "xoxp-override", "Override Team");
+
+    slack(&server, &state)
+        .env_remove("SLACK_TOKEN")
+        .args(["--token", "xoxp-override", "whoami"])
+        .assert()
+        .success();
+
+    // Without the flag there is no stored token to fall back on, and the
+    // override value must not leak into the failure output.
+    slack(&server, &state)
+        .env_remove("SLACK_TOKEN")
+        .arg("whoami")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("xoxp-override").not())
+        .stderr(predicate::str::contains("xoxp-override").not());
+}
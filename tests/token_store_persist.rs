@@ -0,0 +1,74 @@
+//! The token `init` saves has to be there for the next process, whichever store it
+//! lands in.
+
+mod common;
+
+use assert_cmd::Command;
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn slack(server: &MockServer, dir: &tempfile::TempDir) -> Command {
+    let mut cmd = common::slack(server);
+    cmd.env_remove("SLACK_TOKEN")
+        .env_remove("SLACK_PROFILE")
+        .env("SLACK_CONFIG_DIR", dir.path())
+        .env("SLACK_STATE_DIR", dir.path())
+        .env("XDG_RUNTIME_DIR", dir.path().join("run"))
+        // No session bus, so there is no secret service to reach and `auto` has to
+        // fall back to the file rather than a keyring that forgets on exit.
+        .env("DBUS_SESSION_BUS_ADDRESS", "unix:path=/nonexistent/bus");
+    cmd
+}
+
+// Elsewhere `auto` would use the real login keychain / credential manager.
+#[cfg(target_os = "linux")]
+#[test]
+fn auto_store_token_survives_into_the_next_process() {
+    let server = MockServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("token.key"), "test-key").unwrap();
+    std::fs::write(dir.path().join("config.toml"), "key_file = \"token.key\"\n").unwrap();
+    let auth = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/auth.test")
+            .header("authorization", "Bearer xoxp-persist");
+        then.status(200)
+            .json_body(json!({ "ok": true, "team": "Acme", "user_id": "U1" }));
+    });
+
+    let out = slack(&server, &dir)
+        .args(["init", "--token", "xoxp-persist"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("No keyring backend available"), "{err}");
+    assert!(dir.path().join("token.enc").exists(), "{out:?}");
+
+    let out = slack(&server, &dir)
+        .args(["--revalidate", "whoami"])
+        .output()
+        .unwrap();
+    // auth.test only answers for the token init saved.
+    assert!(out.status.success(), "{out:?}");
+    assert!(String::from_utf8_lossy(&out.stdout).contains("Acme"));
+    assert!(auth.hits() >= 2);
+}
+
+#[test]
+fn passphrase_file_is_locked_until_unlocked() {
+    let server = MockServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    // Written by an earlier `init` in a session that has since ended.
+    let mut sealed = b"MDSLACK1".to_vec();
+    sealed.extend([0u8; 16 + 12 + 32]);
+    std::fs::write(dir.path().join("token.enc"), sealed).unwrap();
+
+    let out = slack(&server, &dir)
+        .args(["--store", "file", "whoami"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("run `slack unlock`"), "{err}");
+}
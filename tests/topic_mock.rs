@@ -0,0 +1,129 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn topic_without_text_reads_it() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.info")
+            .x_www_form_urlencoded_tuple("channel", "C1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channel": { "id": "C1", "topic": { "value": "Release train departs Friday" } }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.info");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channel": { "id": "C2", "topic": { "value": "" } }
+        }));
+    });
+
+    let out = run(&server, &["topic", "--channel", "C1"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "Release train departs Friday\n"
+    );
+    let out = run(&server, &["topic", "--channel", "C2"]);
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "no topic\n");
+}
+
+#[test]
+fn topic_echoes_what_slack_stored_and_warns_on_truncation() {
+    let server = MockServer::start();
+    let long = "x".repeat(300);
+    let set = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.setTopic")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("topic", &long);
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channel": { "id": "C1", "topic": { "value": "x".repeat(250) } }
+        }));
+    });
+
+    let out = run(&server, &["topic", "--channel", "C1", "--text", &long]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        format!("topic set: {}\n", "x".repeat(250))
+    );
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("first 250 of 300 characters"), "{err}");
+    set.assert_hits(1);
+}
+
+#[test]
+fn topic_explains_not_in_channel() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.setTopic");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "not_in_channel" }));
+    });
+
+    let out = run(&server, &["topic", "--channel", "C1", "--text", "hi"]);
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("slack join --channel C1"), "{err}");
+}
+
+#[test]
+fn purpose_reads_sets_and_rejects_overlong_text() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.info")
+            .x_www_form_urlencoded_tuple("channel", "C1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channel": { "id": "C1", "purpose": { "value": "Billing migration" } }
+        }));
+    });
+    let set = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.setPurpose")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("purpose", "Coordination for the billing migration");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channel": { "id": "C1", "purpose": { "value": "Coordination for the billing migration" } }
+        }));
+    });
+
+    let out = run(&server, &["purpose", "--channel", "C1"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "Billing migration\n");
+
+    let out = run(
+        &server,
+        &[
+            "purpose",
+            "--channel",
+            "C1",
+            "--text",
+            "Coordination for the billing migration",
+        ],
+    );
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "purpose set: Coordination for the billing migration\n"
+    );
+
+    let out = run(
+        &server,
+        &["purpose", "--channel", "C1", "--text", &"x".repeat(251)],
+    );
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("at most 250"), "{err}");
+    set.assert_hits(1);
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn info<'a>(server: &'a MockServer, id: &str, channel: serde_json::Value) -> httpmock::Mock<'a> {
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.info")
+            .x_www_form_urlencoded_tuple("channel", id);
+        then.status(200)
+            .json_body(json!({ "ok": true, "channel": channel }));
+    })
+}
+
+fn newest(server: &MockServer, id: &str, text: &str) {
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.history")
+            .x_www_form_urlencoded_tuple("channel", id)
+            .x_www_form_urlencoded_tuple("limit", "1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "messages": [{ "ts": "1712345678.000100", "text": text }]
+        }));
+    });
+}
+
+#[test]
+fn unreads_sorts_by_count_and_skips_muted_and_read() {
+    let server = MockServer::start();
+    let list = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.conversations")
+            .x_www_form_urlencoded_tuple("types", "public_channel,private_channel")
+            .x_www_form_urlencoded_tuple("exclude_archived", "true");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channels": [
+                { "id": "C1", "name": "general" },
+                { "id": "C2", "name": "ops", "is_private": true },
+                { "id": "C3", "name": "random" },
+                { "id": "C4", "name": "quiet" }
+            ],
+            "response_metadata": { "next_cursor": "" }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.prefs.get");
+        then.status(200)
+            .json_body(json!({ "ok": true, "prefs": { "muted_channels": "C3,C9" } }));
+    });
+    info(
+        &server,
+        "C1",
+        json!({ "id": "C1", "unread_count_display": 2 }),
+    );
+    info(
+        &server,
+        "C2",
+        json!({ "id": "C2", "unread_count_display": 7 }),
+    );
+    let muted = info(
+        &server,
+        "C3",
+        json!({ "id": "C3", "unread_count_display": 40 }),
+    );
+    info(
+        &server,
+        "C4",
+        json!({ "id": "C4", "unread_count_display": 0 }),
+    );
+    newest(&server, "C1", "lunch?");
+    newest(&server, "C2", "deploy finished\nall green");
+
+    let out = run(&server, &["unreads"]);
+    assert!(out.status.success(), "{out:?}");
+    list.assert();
+    muted.assert_hits(0);
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "CONVERSATION  UNREAD  LATEST\n\
+         #ops          7       deploy finished all green\n\
+         #general      2       lunch?\n"
+    );
+}
+
+#[test]
+fn unreads_include_dms_names_the_other_person() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.conversations")
+            .x_www_form_urlencoded_tuple("types", "public_channel,private_channel,mpim,im");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "channels": [{ "id": "D1", "is_im": true, "user": "U1" }]
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.prefs.get");
+        then.status(200)
+            .json_body(json!({ "ok": true, "prefs": {} }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "members": [{ "id": "U1", "name": "ana", "profile": { "display_name": "ana" } }]
+        }));
+    });
+    info(
+        &server,
+        "D1",
+        json!({
+            "id": "D1",
+            "unread_count_display": 1,
+            "latest": { "ts": "1712345678.000100", "text": "got a minute?" }
+        }),
+    );
+
+    let out = run(&server, &["unreads", "--include-dms"]);
+    assert!(out.status.success(), "{out:?}");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("@ana"), "{stdout}");
+    assert!(stdout.contains("got a minute?"), "{stdout}");
+}
@@ -0,0 +1,250 @@
+use assert_cmd::prelude::*;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+fn upload(server: &MockServer, file: &std::path::Path, extra: &[&str]) -> std::process::Output {
+    Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["upload", "--channel", "C1", "--file"])
+        .arg(file)
+        .args(extra)
+        .output()
+        .unwrap()
+}
+
+fn report() -> (tempfile::TempDir, std::path::PathBuf) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("report.pdf");
+    std::fs::write(&path, b"%PDF-1.4 numbers").unwrap();
+    (dir, path)
+}
+
+fn url_ok(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST).path("/api/files.getUploadURLExternal");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "upload_url": server.url("/upload/F1"),
+            "file_id": "F1"
+        }));
+    });
+}
+
+#[test]
+fn upload_prints_id_and_permalink() {
+    let server = MockServer::start();
+    let (_dir, path) = report();
+    let get_url = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.getUploadURLExternal")
+            .x_www_form_urlencoded_tuple("filename", "q3.pdf")
+            .x_www_form_urlencoded_tuple("length", "16");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "upload_url": server.url("/upload/F1"),
+            "file_id": "F1"
+        }));
+    });
+    let bytes = server.mock(|when, then| {
+        when.method(POST)
+            .path("/upload/F1")
+            .body("%PDF-1.4 numbers");
+        then.status(200);
+    });
+    let complete = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.completeUploadExternal")
+            .x_www_form_urlencoded_tuple("channel_id", "C1")
+            .x_www_form_urlencoded_tuple("initial_comment", "Q3 numbers")
+            .x_www_form_urlencoded_tuple("files", r#"[{"id":"F1","title":"q3.pdf"}]"#);
+        then.status(200)
+            .json_body(json!({ "ok": true, "files": [{ "id": "F1" }] }));
+    });
+    let info = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/files.info")
+            .query_param("file", "F1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "file": { "id": "F1", "permalink": "https://acme.slack.com/files/U1/F1/q3.pdf" }
+        }));
+    });
+
+    let out = upload(
+        &server,
+        &path,
+        &["--comment", "Q3 numbers", "--filename", "q3.pdf"],
+    );
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "uploaded F1 (q3.pdf)\nhttps://acme.slack.com/files/U1/F1/q3.pdf\n"
+    );
+    get_url.assert();
+    bytes.assert();
+    complete.assert();
+    info.assert();
+}
+
+#[test]
+fn upload_url_failure_is_named() {
+    let server = MockServer::start();
+    let (_dir, path) = report();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/files.getUploadURLExternal");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "missing_scope" }));
+    });
+
+    let out = upload(&server, &path, &[]);
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        err.contains("could not get an upload URL for report.pdf"),
+        "{err}"
+    );
+    assert!(err.contains("missing_scope"), "{err}");
+}
+
+#[test]
+fn byte_upload_failure_is_named() {
+    let server = MockServer::start();
+    let (_dir, path) = report();
+    url_ok(&server);
+    server.mock(|when, then| {
+        when.method(POST).path("/upload/F1");
+        then.status(500);
+    });
+    let complete = server.mock(|when, then| {
+        when.method(POST).path("/api/files.completeUploadExternal");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+
+    let out = upload(&server, &path, &[]);
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        err.contains("HTTP 500 Internal Server Error sending report.pdf to its upload URL"),
+        "{err}"
+    );
+    complete.assert_hits(0);
+}
+
+#[test]
+fn complete_failure_is_named() {
+    let server = MockServer::start();
+    let (_dir, path) = report();
+    url_ok(&server);
+    server.mock(|when, then| {
+        when.method(POST).path("/upload/F1");
+        then.status(200);
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/files.completeUploadExternal");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "channel_not_found" }));
+    });
+
+    let out = upload(&server, &path, &[]);
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        err.contains("uploaded F1 but files.completeUploadExternal failed"),
+        "{err}"
+    );
+    assert!(err.contains("channel_not_found"), "{err}");
+}
+
+#[test]
+fn upload_shares_several_files_in_one_message() {
+    let server = MockServer::start();
+    let dir = tempfile::tempdir().unwrap();
+    for name in ["b.png", "a.png", "notes.txt"] {
+        std::fs::write(dir.path().join(name), name).unwrap();
+    }
+    std::fs::create_dir(dir.path().join("sub.png")).unwrap();
+    let extra = dir.path().join("notes.txt");
+    for (name, id) in [("a.png", "F1"), ("b.png", "F2"), ("notes.txt", "F3")] {
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/api/files.getUploadURLExternal")
+                .x_www_form_urlencoded_tuple("filename", name);
+            then.status(200).json_body(json!({
+                "ok": true,
+                "upload_url": server.url(format!("/upload/{id}")),
+                "file_id": id
+            }));
+        });
+    }
+    server.mock(|when, then| {
+        when.method(POST).path_contains("/upload/F");
+        then.status(200);
+    });
+    let complete = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.completeUploadExternal")
+            .x_www_form_urlencoded_tuple(
+                "files",
+                r#"[{"id":"F3","title":"notes.txt"},{"id":"F1","title":"a.png"},{"id":"F2","title":"b.png"}]"#,
+            );
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/api/files.info");
+        then.status(200)
+            .json_body(json!({ "ok": true, "file": { "permalink": "https://x/F" } }));
+    });
+
+    let out = upload(
+        &server,
+        &extra,
+        &["--dir", dir.path().to_str().unwrap(), "--glob", "*.png"],
+    );
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "uploaded F3 (notes.txt)\nhttps://x/F\nuploaded F1 (a.png)\nhttps://x/F\n\
+         uploaded F2 (b.png)\nhttps://x/F\n"
+    );
+    complete.assert();
+}
+
+#[test]
+fn upload_shares_the_files_that_made_it_and_reports_the_rest() {
+    let server = MockServer::start();
+    let (dir, path) = report();
+    let missing = dir.path().join("missing.png");
+    url_ok(&server);
+    server.mock(|when, then| {
+        when.method(POST).path("/upload/F1");
+        then.status(200);
+    });
+    let complete = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/files.completeUploadExternal")
+            .x_www_form_urlencoded_tuple("files", r#"[{"id":"F1","title":"report.pdf"}]"#);
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+    server.mock(|when, then| {
+        when.method(GET).path("/api/files.info");
+        then.status(200)
+            .json_body(json!({ "ok": true, "file": { "permalink": "https://x/F1" } }));
+    });
+
+    let out = upload(&server, &path, &["--file", missing.to_str().unwrap()]);
+    assert_eq!(out.status.code(), Some(1), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "uploaded F1 (report.pdf)\nhttps://x/F1\n"
+    );
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("failed to read"), "{err}");
+    assert!(
+        err.contains("1 of 2 file(s) could not be uploaded"),
+        "{err}"
+    );
+    complete.assert();
+}
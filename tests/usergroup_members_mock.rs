@@ -0,0 +1,79 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn stub_group(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST).path("/api/usergroups.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "usergroups": [
+                { "id": "S0123ABCD", "handle": "backend-team", "name": "Backend", "user_count": 2 }
+            ]
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/usergroups.users.list")
+            .x_www_form_urlencoded_tuple("usergroup", "S0123ABCD");
+        then.status(200)
+            .json_body(json!({ "ok": true, "users": ["U1", "U2"] }));
+    });
+}
+
+#[test]
+fn members_by_handle_resolved() {
+    let server = MockServer::start();
+    stub_group(&server);
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "members": [
+                { "id": "U1", "name": "ana", "profile": { "display_name": "ana", "real_name": "Ana Lima", "email": "ana@example.com" } },
+                { "id": "U2", "name": "bo", "profile": { "display_name": "", "real_name": "Bo Chen" } }
+            ]
+        }));
+    });
+
+    let out = run(
+        &server,
+        &["usergroup-members", "--group", "@Backend-Team", "--resolve"],
+    );
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "U1\tana\tAna Lima\tana@example.com\n\
+         U2\tbo\tBo Chen\t-\n"
+    );
+
+    let out = run(
+        &server,
+        &[
+            "usergroup-members",
+            "--group",
+            "S0123ABCD",
+            "--format",
+            "json",
+        ],
+    );
+    assert!(out.status.success(), "{out:?}");
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(v[1]["id"], "U2");
+    assert_eq!(v[1]["display_name"], serde_json::Value::Null);
+}
+
+#[test]
+fn unknown_handle_is_an_error() {
+    let server = MockServer::start();
+    stub_group(&server);
+
+    let out = run(&server, &["usergroup-members", "--group", "@frontend"]);
+    assert!(!out.status.success());
+    assert!(
+        String::from_utf8_lossy(&out.stderr).contains("no user group with handle @frontend"),
+        "{out:?}"
+    );
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn update_sends_the_full_member_set() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/usergroups.users.list")
+            .x_www_form_urlencoded_tuple("usergroup", "S0123ABCD");
+        then.status(200)
+            .json_body(json!({ "ok": true, "users": ["U1", "U3", "U4"] }));
+    });
+    let update = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/usergroups.users.update")
+            .x_www_form_urlencoded_tuple("usergroup", "S0123ABCD")
+            .x_www_form_urlencoded_tuple("users", "U1,U4,U2");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "usergroup": { "id": "S0123ABCD", "users": ["U1", "U2", "U4"] }
+        }));
+    });
+
+    let out = run(
+        &server,
+        &[
+            "usergroup-update",
+            "--group",
+            "S0123ABCD",
+            "--add-users",
+            "U1,U2",
+            "--remove-users",
+            "U3",
+        ],
+    );
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "added: U2\nremoved: U3\nmembers (3): U1,U2,U4\n"
+    );
+    update.assert_hits(1);
+}
+
+#[test]
+fn disable_and_create() {
+    let server = MockServer::start();
+    let disable = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/usergroups.disable")
+            .x_www_form_urlencoded_tuple("usergroup", "S0123ABCD");
+        then.status(200).json_body(json!({ "ok": true }));
+    });
+    let create = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/usergroups.create")
+            .x_www_form_urlencoded_tuple("name", "Backend")
+            .x_www_form_urlencoded_tuple("handle", "backend")
+            .x_www_form_urlencoded_tuple("channels", "C1,C2");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "usergroup": { "id": "S0999", "handle": "backend", "name": "Backend" }
+        }));
+    });
+
+    let out = run(
+        &server,
+        &["usergroup-update", "--group", "S0123ABCD", "--disable"],
+    );
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "disabled S0123ABCD\n");
+
+    let out = run(
+        &server,
+        &[
+            "usergroup-create",
+            "--name",
+            "Backend",
+            "--handle",
+            "@backend",
+            "--channels",
+            "C1,C2",
+        ],
+    );
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "created S0999 @backend (Backend)\n"
+    );
+    disable.assert_hits(1);
+    create.assert_hits(1);
+
+    let out = run(&server, &["usergroup-update", "--group", "S0123ABCD"]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("nothing to change"));
+}
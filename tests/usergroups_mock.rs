@@ -0,0 +1,53 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn usergroups_lists_counts_and_includes_disabled_on_request() {
+    let server = MockServer::start();
+    let list = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/usergroups.list")
+            .x_www_form_urlencoded_tuple("include_count", "true")
+            .x_www_form_urlencoded_tuple("include_disabled", "true");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "usergroups": [
+                { "id": "S1", "handle": "backend-team", "name": "Backend", "user_count": 12, "date_delete": 0 },
+                { "id": "S2", "handle": "old-oncall", "name": "Old on-call", "user_count": 0, "date_delete": 1712345678 }
+            ]
+        }));
+    });
+
+    let out = run(
+        &server,
+        &["usergroups", "--include-disabled", "--format", "json"],
+    );
+    assert!(out.status.success(), "{out:?}");
+    let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(v[0]["handle"], "backend-team");
+    assert_eq!(v[0]["members"], 12);
+    assert_eq!(v[1]["disabled"], true);
+
+    let out = run(&server, &["usergroups", "--include-disabled"]);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("@backend-team"), "{stdout}");
+    assert!(stdout.contains("disabled"), "{stdout}");
+    list.assert_hits(2);
+}
+
+#[test]
+fn usergroups_names_the_missing_scope() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/usergroups.list");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "missing_scope" }));
+    });
+
+    let out = run(&server, &["usergroups"]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("usergroups:read"));
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn run(server: &MockServer, user: &str) -> std::process::Output {
+    common::run(server, &["userinfo", "--user", user])
+}
+
+#[test]
+fn userinfo_by_id_or_email() {
+    let server = MockServer::start();
+    let user = json!({
+        "id": "U1",
+        "is_restricted": true,
+        "profile": {
+            "display_name": "jane",
+            "real_name": "Jane Doe",
+            "email": "jane@example.com",
+            "title": "Staff Engineer"
+        }
+    });
+    let info = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.info")
+            .x_www_form_urlencoded_tuple("user", "U1");
+        then.status(200)
+            .json_body(json!({ "ok": true, "user": user.clone() }));
+    });
+    let by_email = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.lookupByEmail")
+            .x_www_form_urlencoded_tuple("email", "jane@example.com");
+        then.status(200)
+            .json_body(json!({ "ok": true, "user": user.clone() }));
+    });
+
+    for who in ["U1", "jane@example.com"] {
+        let out = run(&server, who);
+        assert!(out.status.success(), "{out:?}");
+        let stdout = String::from_utf8_lossy(&out.stdout);
+        assert!(stdout.starts_with("id: U1\ndisplay name: jane\nreal name: Jane Doe\n"));
+        assert!(
+            stdout.contains("title: Staff Engineer\nphone: -\n"),
+            "{stdout}"
+        );
+        assert!(stdout.contains("account: guest\n"), "{stdout}");
+    }
+    info.assert_hits(1);
+    by_email.assert_hits(1);
+}
+
+#[test]
+fn unknown_users_get_a_clear_error() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.info");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "user_not_found" }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.lookupByEmail");
+        then.status(200)
+            .json_body(json!({ "ok": false, "error": "users_not_found" }));
+    });
+
+    let out = run(&server, "U404");
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("no user with ID U404"));
+    let out = run(&server, "nobody@example.com");
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("no user with email nobody@example.com"));
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+const HEADER: &str = "user_id,username,display_name,real_name,email,title,tz,is_admin,is_bot,is_restricted,deleted,updated\r\n";
+
+fn stub_roster(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.list")
+            .x_www_form_urlencoded_tuple("cursor", "next");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "members": [
+                { "id": "U2", "name": "bot", "is_bot": true, "deleted": true, "profile": {} }
+            ]
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "members": [
+                { "id": "U1", "name": "jane", "tz": "Europe/Lisbon", "is_admin": true, "updated": 1712345678,
+                  "profile": { "display_name": "jane", "real_name": "Doe, Jane", "email": "jane@example.com", "title": "SRE \"lead\"" } }
+            ],
+            "response_metadata": { "next_cursor": "next" }
+        }));
+    });
+}
+
+#[test]
+fn users_export_writes_every_page_as_csv() {
+    let server = MockServer::start();
+    stub_roster(&server);
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("users.csv");
+
+    let out = run(&server, &["users-export", "--out", path.to_str().unwrap()]);
+    assert!(out.status.success(), "{out:?}");
+    assert!(String::from_utf8_lossy(&out.stdout).starts_with("exported 2 users to "));
+    let csv = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(
+        csv,
+        format!(
+            "{HEADER}\
+             U1,jane,jane,\"Doe, Jane\",jane@example.com,\"SRE \"\"lead\"\"\",Europe/Lisbon,true,false,false,false,2024-04-05 19:34\r\n\
+             U2,bot,,,,,,false,true,false,true,\r\n"
+        )
+    );
+
+    let out = run(&server, &["users-export", "--out", "-"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(String::from_utf8_lossy(&out.stdout), csv);
+    assert!(String::from_utf8_lossy(&out.stderr).contains("exported 2 users"));
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+
+fn stub_roster(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.list")
+            .x_www_form_urlencoded_tuple("cursor", "page2");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "members": [
+                { "id": "U3", "name": "deploybot", "is_bot": true, "profile": {} },
+                { "id": "U4", "name": "old", "deleted": true, "is_restricted": true, "profile": { "real_name": "Old Guest" } }
+            ],
+            "response_metadata": { "next_cursor": "" }
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST).path("/api/users.list");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "members": [
+                { "id": "U1", "name": "jane", "is_admin": true, "profile": { "display_name": "jane", "real_name": "Jane Doe", "email": "jane@example.com" } },
+                { "id": "U2", "name": "kim", "profile": { "real_name": "Kim Ito" } }
+            ],
+            "response_metadata": { "next_cursor": "page2" }
+        }));
+    });
+}
+
+#[test]
+fn users_pages_and_hides_bots_and_deleted_by_default() {
+    let server = MockServer::start();
+    stub_roster(&server);
+
+    let out = run(&server, &["users"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "U1\t@jane\tJane Doe\tjane@example.com\t[admin]\n\
+         U2\t@kim\tKim Ito\t\t\n"
+    );
+
+    let out = run(&server, &["users", "--guests-only", "--include-deleted"]);
+    assert!(out.status.success(), "{out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "U4\t@old\tOld Guest\t\t[guest][deleted]\n"
+    );
+
+    let out = run(&server, &["users", "--include-bots", "--limit", "3"]);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert_eq!(stdout.lines().count(), 3, "{stdout}");
+    assert!(stdout.contains("U3\t@deploybot"), "{stdout}");
+}
+
+#[test]
+fn users_on_grid_filters_by_team_and_pages_with_team_id() {
+    let server = MockServer::start();
+    let second = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.list")
+            .x_www_form_urlencoded_tuple("team_id", "T1")
+            .x_www_form_urlencoded_tuple("cursor", "page2");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "members": [
+                { "id": "W3", "name": "lee", "team_id": "T2", "enterprise_user": { "teams": ["T2", "T1"] }, "profile": {} }
+            ],
+            "response_metadata": { "next_cursor": "" }
+        }));
+    });
+    let first = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/users.list")
+            .x_www_form_urlencoded_tuple("team_id", "T1");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "members": [
+                { "id": "W1", "name": "jane", "team_id": "T1", "profile": {} },
+                { "id": "W2", "name": "kim", "team_id": "T2", "profile": {} }
+            ],
+            "response_metadata": { "next_cursor": "page2" }
+        }));
+    });
+
+    let out = run(&server, &["--team-id", "T1", "users", "--team", "T1"]);
+    assert!(out.status.success(), "{out:?}");
+    // lee's home is T2 but they're in T1 too; kim isn't.
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "W1\t@jane\t\t\t\tT1\n\
+         W3\t@lee\t\t\t\tT2\n"
+    );
+    first.assert_hits(1);
+    second.assert_hits(1);
+}
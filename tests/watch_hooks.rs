@@ -0,0 +1,104 @@
+#![cfg(unix)]
+
+use assert_cmd::prelude::*;
+use httpmock::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+fn mock_channel(server: &MockServer) {
+    server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.list");
+        then.status(200).json_body(serde_json::json!({
+            "ok": true,
+            "channels": [{ "id": "C9", "name": "alerts" }]
+        }));
+    });
+    server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.history")
+            .x_www_form_urlencoded_tuple("channel", "C9");
+        then.status(200).json_body(serde_json::json!({
+            "ok": true,
+            "messages": [
+                { "ts": "300.000003", "user": "U2", "text": "disk full on db1" },
+                { "ts": "200.000002", "user": "U1", "text": "all good" },
+                { "ts": "100.000001", "user": "U2", "text": "disk almost full" }
+            ]
+        }));
+    });
+}
+
+fn watch(server: &MockServer) -> Command {
+    let mut cmd = Command::cargo_bin("mdslackcli").unwrap();
+    cmd.env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["watch", "--channel", "#alerts", "--since", "0", "--once"]);
+    cmd
+}
+
+#[test]
+fn exec_hook_gets_env_and_stdin_for_each_matching_message() {
+    let server = MockServer::start();
+    mock_channel(&server);
+    let dir = tempfile::tempdir().unwrap();
+    let log = dir.path().join("hook.log");
+    let script = dir.path().join("hook.sh");
+    std::fs::write(
+        &script,
+        format!(
+            "#!/bin/sh\nbody=$(cat)\necho \"$SLACK_CHANNEL $SLACK_TS $SLACK_USER $SLACK_TEXT | $body\" >> {}\n",
+            log.display()
+        ),
+    )
+    .unwrap();
+    std::fs::set_permissions(&script, std::os::unix::fs::PermissionsExt::from_mode(0o755)).unwrap();
+
+    watch(&server)
+        .args(["--contains", "disk", "--exec", script.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let out = std::fs::read_to_string(&log).unwrap();
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines.len(), 2, "{out}");
+    assert!(lines[0].starts_with("C9 100.000001 U2 disk almost full | {"));
+    assert!(lines[0].contains("\"ts\":\"100.000001\""));
+    assert!(lines[1].starts_with("C9 300.000003 U2 disk full on db1 | {"));
+}
+
+#[test]
+fn failing_hook_is_logged_and_watch_continues() {
+    let server = MockServer::start();
+    mock_channel(&server);
+
+    watch(&server)
+        .args(["--exec", "cat >/dev/null; exit 3"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("hook for ts=100.000001 exited"))
+        .stderr(predicate::str::contains("hook for ts=300.000003 exited"));
+}
+
+#[test]
+fn webhook_receives_filtered_message_json() {
+    let server = MockServer::start();
+    mock_channel(&server);
+    let hook = server.mock(|when, then| {
+        when.method(POST)
+            .path("/hook")
+            .json_body_partial(r#"{ "channel": "C9", "ts": "200.000002" }"#);
+        then.status(200);
+    });
+    let others = server.mock(|when, then| {
+        when.method(POST).path("/hook");
+        then.status(500);
+    });
+
+    watch(&server)
+        .args(["--from", "U1", "--retries", "1"])
+        .args(["--webhook", &server.url("/hook")])
+        .assert()
+        .success();
+    hook.assert_hits(1);
+    others.assert_hits(0);
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use common::run;
+use httpmock::prelude::*;
+use serde_json::json;
+use std::process::Command;
+
+#[test]
+fn watch_reads_every_page_since_the_last_poll() {
+    let server = MockServer::start();
+    // The cursor page first: the first registered mock that matches wins.
+    let older = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.history")
+            .x_www_form_urlencoded_tuple("cursor", "page2");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "messages": [
+                { "ts": "1712345679.000200", "user": "U1", "text": "two" },
+                { "ts": "1712345678.000200", "user": "U1", "text": "one" }
+            ],
+            "has_more": false
+        }));
+    });
+    let newest = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/conversations.history")
+            .x_www_form_urlencoded_tuple("channel", "C1")
+            .x_www_form_urlencoded_tuple("oldest", "1712345678.000100");
+        then.status(200).json_body(json!({
+            "ok": true,
+            "messages": [
+                { "ts": "1712345681.000200", "user": "U2", "text": "four" },
+                { "ts": "1712345680.000200", "user": "U2", "text": "three" }
+            ],
+            "has_more": true,
+            "response_metadata": { "next_cursor": "page2" }
+        }));
+    });
+
+    let out = run(
+        &server,
+        &[
+            "watch",
+            "--channel",
+            "C1",
+            "--since",
+            "1712345678.000100",
+            "--once",
+        ],
+    );
+    assert!(out.status.success(), "{out:?}");
+    newest.assert();
+    older.assert();
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let texts: Vec<&str> = stdout
+        .lines()
+        .map(|l| l.rsplit(": ").next().unwrap())
+        .collect();
+    assert_eq!(texts, ["one", "two", "three", "four"], "{stdout}");
+}
+
+#[test]
+fn watch_keeps_polling_after_a_failed_poll() {
+    let server = MockServer::start();
+    let history = server.mock(|when, then| {
+        when.method(POST).path("/api/conversations.history");
+        then.status(503).body("upstream unavailable");
+    });
+
+    let mut child = Command::cargo_bin("mdslackcli")
+        .unwrap()
+        .env("SLACK_TOKEN", "xoxp-test")
+        .env("SLACK_API_BASE", format!("{}/api", server.base_url()))
+        .args(["watch", "--channel", "C1", "--since", "1712345678.000100"])
+        .args(["--interval", "1"])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(2500));
+    let still_running = child.try_wait().unwrap().is_none();
+    child.kill().unwrap();
+    let out = child.wait_with_output().unwrap();
+
+    assert!(still_running, "{out:?}");
+    assert!(history.hits() >= 2);
+    assert!(String::from_utf8_lossy(&out.stderr).contains("trying again next interval"));
+}
@@ -0,0 +1,87 @@
+use assert_cmd::prelude::*;
+use httpmock::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+fn slack(config: &tempfile::TempDir, server: &MockServer) -> Command {
+    std::fs::write(
+        config.path().join("config.toml"),
+        format!(
+            "[workflows]\nping = \"{}\"\nintake = {{ url = \"{}\", variables = [\"title\", \"severity\"] }}\n",
+            server.url("/hooks/ping"),
+            server.url("/hooks/intake")
+        ),
+    )
+    .unwrap();
+    let mut cmd = Command::cargo_bin("mdslackcli").unwrap();
+    cmd.env("SLACK_TOKEN", "xoxp-secret")
+        .env("SLACK_CONFIG_DIR", config.path());
+    cmd
+}
+
+#[test]
+fn trigger_posts_variables_as_json_without_the_token() {
+    let server = MockServer::start();
+    let config = tempfile::tempdir().unwrap();
+    let hook = server.mock(|when, then| {
+        when.method(POST)
+            .path("/hooks/intake")
+            .header("content-type", "application/json")
+            .json_body(serde_json::json!({ "title": "Broken build", "severity": "high" }))
+            .matches(|req| {
+                !req.headers.as_ref().is_some_and(|h| {
+                    h.iter()
+                        .any(|(k, _)| k.eq_ignore_ascii_case("authorization"))
+                })
+            });
+        then.status(200).body(r#"{"ok":true}"#);
+    });
+
+    slack(&config, &server)
+        .args(["workflow", "trigger", "--name", "intake"])
+        .args(["--var", "title=Broken build", "--var", "severity=high"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("HTTP 200 OK"))
+        .stdout(predicate::str::contains(r#"{"ok":true}"#));
+    hook.assert();
+}
+
+#[test]
+fn non_2xx_response_fails() {
+    let server = MockServer::start();
+    let config = tempfile::tempdir().unwrap();
+    server.mock(|when, then| {
+        when.method(POST).path("/hooks/ping");
+        then.status(400).body("invalid_payload");
+    });
+    slack(&config, &server)
+        .args(["workflow", "trigger", "--name", "ping"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("invalid_payload"))
+        .stderr(predicate::str::contains("HTTP 400"));
+}
+
+#[test]
+fn bad_names_and_missing_variables_fail_before_any_request() {
+    let server = MockServer::start();
+    let config = tempfile::tempdir().unwrap();
+    let any = server.mock(|when, then| {
+        when.any_request();
+        then.status(200);
+    });
+    slack(&config, &server)
+        .args(["workflow", "trigger", "--name", "nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("configured: intake, ping"));
+    slack(&config, &server)
+        .args([
+            "workflow", "trigger", "--name", "intake", "--var", "title=x",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("missing --var for: severity"));
+    any.assert_hits(0);
+}